@@ -1,5 +1,5 @@
 /// Tier of stealth to use.
-#[derive(PartialEq, Debug, Default, Copy, Clone)]
+#[derive(PartialEq, Eq, Debug, Default, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tier {
     /// Basic spoofing.
@@ -35,13 +35,87 @@ pub enum Tier {
     BasicNoExtra,
     /// Basic spoofing without webgl only gpu and no extra.
     BasicNoWebglWithGPUNoExtra,
-    /// Extra only.
+    /// Extra only. The speech synthesis, screen, permissions, media, plugins and user agent data
+    /// extras without the core chrome, webdriver and gpu patches.
     Extra,
     /// No spoofing
     None,
 }
 
+/// Every tier.
+pub const TIERS: &[Tier] = &[
+    Tier::Basic,
+    Tier::BasicWithConsole,
+    Tier::BasicNoWebgl,
+    Tier::BasicNoWebglWithGPU,
+    Tier::BasicNoWebglWithGPUcWithConsole,
+    Tier::BasicNoWorker,
+    Tier::HideOnly,
+    Tier::HideOnlyWithConsole,
+    Tier::HideOnlyChrome,
+    Tier::Low,
+    Tier::LowWithPlugins,
+    Tier::LowWithNavigator,
+    Tier::Mid,
+    Tier::Full,
+    Tier::BasicNoExtra,
+    Tier::BasicNoWebglWithGPUNoExtra,
+    Tier::Extra,
+    Tier::None,
+];
+
 impl Tier {
+    /// The spoof modules rendered by the tier in render order. See [`crate::spoof_modules`].
+    pub fn modules(&self) -> &'static [&'static str] {
+        use crate::spoof_modules::names::*;
+
+        match self {
+            Tier::Basic | Tier::BasicNoExtra => &[CHROME, CONSOLE, WORKER, GPU_ADAPTER, NAVIGATOR],
+            Tier::BasicNoWorker => &[CHROME, CONSOLE, GPU_ADAPTER, NAVIGATOR],
+            Tier::BasicWithConsole => &[CHROME, WORKER, CONCURRENCY, GPU_ADAPTER, NAVIGATOR],
+            Tier::BasicNoWebgl | Tier::BasicNoWebglWithGPU | Tier::BasicNoWebglWithGPUNoExtra => {
+                &[CHROME, CONSOLE, WORKER, CONCURRENCY, NAVIGATOR]
+            }
+            Tier::BasicNoWebglWithGPUcWithConsole => &[CHROME, WORKER, CONCURRENCY, NAVIGATOR],
+            Tier::HideOnly => &[CHROME, CONSOLE, WEBDRIVER],
+            Tier::HideOnlyWithConsole => &[CHROME, WEBDRIVER],
+            Tier::HideOnlyChrome => &[CHROME],
+            Tier::Low | Tier::LowWithPlugins => {
+                &[CHROME, CONSOLE, WORKER, CONCURRENCY, GPU_ADAPTER, WEBDRIVER]
+            }
+            Tier::LowWithNavigator => &[
+                CHROME,
+                CONSOLE,
+                WORKER,
+                CONCURRENCY,
+                GPU_ADAPTER,
+                WEBDRIVER,
+                NAVIGATOR,
+            ],
+            Tier::Mid => &[
+                CHROME,
+                CONSOLE,
+                WORKER,
+                CONCURRENCY,
+                GPU_ADAPTER,
+                NAVIGATOR,
+                WEBDRIVER,
+            ],
+            Tier::Full => &[
+                CHROME,
+                CONSOLE,
+                WORKER,
+                CONCURRENCY,
+                GPU_ADAPTER,
+                WEBDRIVER,
+                NAVIGATOR,
+                WGSL,
+            ],
+            Tier::Extra => crate::spoof_modules::EXTRA_MODULES,
+            Tier::None => &[],
+        }
+    }
+
     /// Stealth mode enabled.
     pub fn stealth(&self) -> bool {
         match &self {
@@ -0,0 +1,306 @@
+use crate::configs::{AgentOs, Tier};
+use crate::profiles::gpu::GpuProfile;
+use crate::spoof_viewport::Viewport;
+use crate::EmulationConfiguration;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Salt for the stealth script random stream.
+const SCRIPT_STREAM: u64 = 0x9E37_79B9_7F4A_7C15;
+/// Salt for the http headers random stream.
+const HEADER_STREAM: u64 = 0xC2B2_AE3D_27D4_EB4F;
+
+/// A reproducible fingerprint identity.
+///
+/// Every random choice made while building the stealth script and headers (gpu profile,
+/// screen sizes, device memory, history length, referrer, full version...) is drawn from
+/// a rng seeded by the identity. The same seed always regenerates byte-identical output,
+/// so a crawler session that restarts keeps the same fingerprint.
+///
+/// The script and header generation use separate streams so calling one does not shift
+/// the values produced by the other.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Identity {
+    /// The seed driving every random choice.
+    pub seed: u64,
+}
+
+impl Identity {
+    /// Create a new identity from a seed.
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+
+    /// Create a new identity from a session key. The key is hashed with FNV-1a so the seed is
+    /// stable across processes, platforms and releases.
+    pub fn from_session_key(key: &str) -> Self {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for b in key.as_bytes() {
+            hash ^= *b as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        Self::new(hash)
+    }
+
+    /// Create a new identity with a random seed.
+    pub fn random() -> Self {
+        Self::new(rand::random())
+    }
+
+    /// The rng used for the stealth script choices.
+    pub fn script_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ SCRIPT_STREAM)
+    }
+
+    /// The rng used for the http header choices.
+    pub fn header_rng(&self) -> StdRng {
+        StdRng::seed_from_u64(self.seed ^ HEADER_STREAM)
+    }
+
+    /// Generate the initial stealth script to send in one command.
+    pub fn build_stealth_script(&self, tier: Tier, os: AgentOs) -> String {
+        crate::build_stealth_script_rng(tier, os, &mut self.script_rng())
+    }
+
+    /// Emulate a real chrome browser.
+    pub fn emulate(
+        &self,
+        user_agent: &str,
+        config: &EmulationConfiguration,
+        viewport: &Option<&Viewport>,
+        evaluate_on_new_document: &Option<Box<String>>,
+    ) -> Option<String> {
+        crate::emulate_base_rng(
+            user_agent,
+            config,
+            viewport,
+            evaluate_on_new_document,
+            None,
+            &mut self.script_rng(),
+        )
+    }
+
+    /// Emulate a real chrome browser with a gpu profile.
+    pub fn emulate_with_profile(
+        &self,
+        user_agent: &str,
+        config: &EmulationConfiguration,
+        viewport: &Option<&Viewport>,
+        evaluate_on_new_document: &Option<Box<String>>,
+        gpu_profile: &GpuProfile,
+    ) -> Option<String> {
+        crate::emulate_base_rng(
+            user_agent,
+            config,
+            viewport,
+            evaluate_on_new_document,
+            Some(gpu_profile),
+            &mut self.script_rng(),
+        )
+    }
+
+    /// Generate the persona for the identity. The script values match [`Identity::emulate`]
+    /// and the header choices match [`Identity::emulate_headers`] for the same `chrome` handling
+    /// and referer domain.
+    #[cfg_attr(not(feature = "headers"), allow(unused_variables))]
+    pub fn persona(
+        &self,
+        user_agent: &str,
+        config: &EmulationConfiguration,
+        viewport: &Option<&Viewport>,
+        chrome: bool,
+        domain_parsed: &Option<Box<url::Url>>,
+    ) -> crate::Persona {
+        let info = crate::parse_user_agent(user_agent);
+        let persona = crate::Persona::resolve_rng(
+            user_agent,
+            &info,
+            config,
+            viewport,
+            None,
+            &mut self.script_rng(),
+        );
+
+        #[cfg(feature = "headers")]
+        let persona = crate::Persona {
+            headers: crate::spoof_headers::HeaderChoices::random_from_info_rng(
+                &info,
+                chrome,
+                domain_parsed,
+                &mut self.header_rng(),
+            )
+            .with_device(&persona.device),
+            ..persona
+        };
+
+        persona
+    }
+
+    #[cfg(feature = "headers")]
+    /// Emulate real HTTP chrome headers. The headers are rendered from [`Identity::persona`] so
+    /// the client hints, `Device-Memory` and `Accept-Language` match the script.
+    #[allow(clippy::too_many_arguments)]
+    pub fn emulate_headers(
+        &self,
+        user_agent: &str,
+        config: &EmulationConfiguration,
+        header_map: &Option<&http::HeaderMap>,
+        hostname: &Option<&str>,
+        chrome: bool,
+        viewport: &Option<&Viewport>,
+        domain_parsed: &Option<Box<url::Url>>,
+        detail_level: &Option<crate::spoof_headers::HeaderDetailLevel>,
+    ) -> http::HeaderMap {
+        self.persona(user_agent, config, viewport, chrome, domain_parsed)
+            .emulate_headers(header_map, hostname, chrome, detail_level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+
+    #[test]
+    fn same_seed_same_script() {
+        let mut config = EmulationConfiguration::setup_defaults(UA);
+        config.enable_device_memory = true;
+        config.disable_history_length = false;
+        let viewport = Viewport::new(1920, 1080);
+
+        let a = Identity::new(42).emulate(UA, &config, &Some(&viewport), &None);
+        let b = Identity::new(42).emulate(UA, &config, &Some(&viewport), &None);
+
+        assert!(a.is_some());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn different_seed_different_script() {
+        let config = EmulationConfiguration::setup_defaults(UA);
+        let viewport = Viewport::new(1920, 1080);
+
+        let a = Identity::new(1).emulate(UA, &config, &Some(&viewport), &None);
+        let b = Identity::new(2).emulate(UA, &config, &Some(&viewport), &None);
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn same_seed_same_stealth_script() {
+        let a = Identity::new(7).build_stealth_script(Tier::Full, AgentOs::Mac);
+        let b = Identity::new(7).build_stealth_script(Tier::Full, AgentOs::Mac);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn session_key_is_stable() {
+        assert_eq!(
+            Identity::from_session_key("session-1"),
+            Identity::from_session_key("session-1")
+        );
+        assert_ne!(
+            Identity::from_session_key("session-1"),
+            Identity::from_session_key("session-2")
+        );
+    }
+
+    #[test]
+    fn persona_matches_identity() {
+        let config = EmulationConfiguration::setup_defaults(UA);
+        let viewport = Viewport::new(1920, 1080);
+        let identity = Identity::new(11);
+
+        assert_eq!(
+            identity
+                .persona(UA, &config, &Some(&viewport), false, &None)
+                .emulate(&None),
+            identity.emulate(UA, &config, &Some(&viewport), &None)
+        );
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn same_seed_same_headers() {
+        let config = EmulationConfiguration::setup_defaults(UA);
+        let viewport = Viewport::new(1920, 1080);
+        let identity = Identity::from_session_key("crawl-42");
+        let headers = || {
+            identity.emulate_headers(
+                UA,
+                &config,
+                &None,
+                &Some("example.com"),
+                false,
+                &Some(&viewport),
+                &None,
+                &None,
+            )
+        };
+
+        assert_eq!(headers(), headers());
+        assert_eq!(
+            headers(),
+            identity
+                .persona(UA, &config, &Some(&viewport), false, &None)
+                .emulate_headers(&None, &Some("example.com"), false, &None)
+        );
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn headers_follow_the_user_agent_data_toggle() {
+        let mut config = EmulationConfiguration::setup_defaults(UA);
+        let identity = Identity::new(3);
+        let headers = |config: &EmulationConfiguration| {
+            identity.emulate_headers(
+                UA,
+                config,
+                &None,
+                &None,
+                false,
+                &None,
+                &None,
+                &Some(crate::spoof_headers::HeaderDetailLevel::Extensive),
+            )
+        };
+        let full_version_list = |config: &EmulationConfiguration| {
+            headers(config)
+                .get("sec-ch-ua-full-version-list")
+                .and_then(|v| v.to_str().ok().map(String::from))
+                .unwrap_or_default()
+        };
+        assert!(!full_version_list(&config).contains(r#""Chromium";v="138.0.0.0""#));
+
+        // without userAgentData there are no rolled full versions to match
+        config.user_agent_data = Some(false);
+        assert!(full_version_list(&config).contains(r#""Chromium";v="138.0.0.0""#));
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn headers_match_the_script_full_versions() {
+        let config = EmulationConfiguration::setup_defaults(UA);
+        let identity = Identity::new(5);
+        let script = identity
+            .emulate(UA, &config, &None, &None)
+            .unwrap_or_default();
+        let headers =
+            identity.emulate_headers(UA, &config, &None, &None, false, &None, &None, &None);
+        let full_version_list = headers
+            .get("sec-ch-ua-full-version-list")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+
+        let chromium = full_version_list
+            .split(", ")
+            .find_map(|brand| brand.strip_prefix(r#""Chromium";v=""#))
+            .map(|version| version.trim_end_matches('"'))
+            .expect("chromium brand");
+
+        assert!(!chromium.ends_with(".0.0.0"));
+        assert!(script.contains(&format!("{{brand:'Chromium',version:'{chromium}'}}")));
+    }
+}
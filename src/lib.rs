@@ -3,50 +3,69 @@ pub mod versions;
 
 /// Builder types.
 pub mod configs;
+/// Seeded, reproducible identities.
+pub mod identity;
+/// Resolved, storable fingerprints.
+pub mod persona;
 /// Custom static profiles.
 pub mod profiles;
+/// Audio spoofs.
+pub mod spoof_audio;
+/// Font spoofs.
+pub mod spoof_fonts;
 /// GPU spoofs.
 pub mod spoof_gpu;
 #[cfg(feature = "headers")]
 /// Spoof HTTP headers.
 pub mod spoof_headers;
+/// Locale spoofs.
+pub mod spoof_locale;
+/// Composable stealth script modules.
+pub mod spoof_modules;
 /// Spoof mouse-movement.
 pub mod spoof_mouse_movement;
 /// Referer headers.
 pub mod spoof_refererer;
+/// Timezone spoofs.
+pub mod spoof_timezone;
 /// User agent.
 pub mod spoof_user_agent;
 /// Spoof viewport.
 pub mod spoof_viewport;
 /// WebGL spoofs.
 pub mod spoof_webgl;
+/// WebRTC spoofs.
+pub mod spoof_webrtc;
 /// Generic spoofs.
 pub mod spoofs;
+/// User agent parsing.
+pub mod user_agent;
+/// Cross-layer consistency checks.
+pub mod validate;
 
 /// Referrer domains index.
 mod referrers_domains_index;
 /// High quality referrer index.
 mod referrers_hq_index;
+/// Script evaluation for the tests.
+#[cfg(test)]
+mod test_js;
 
+pub use identity::Identity;
+pub use persona::Persona;
 #[cfg(feature = "headers")]
-pub use spoof_headers::emulate_headers;
+pub use spoof_headers::{emulate_headers, emulate_headers_with_locale};
 pub use spoof_refererer::spoof_referrer;
+pub use user_agent::{parse_user_agent, UserAgentInfo};
 
 use configs::{AgentOs, Tier};
 use profiles::{
-    gpu::{select_random_gpu_profile, GpuProfile},
-    gpu_limits::{build_gpu_request_adapter_script_from_limits, GpuLimits},
+    gpu::{select_random_gpu_profile, select_random_gpu_profile_rng, GpuProfile},
+    gpu_limits::GpuLimits,
 };
-use rand::prelude::IndexedRandom;
 use rand::Rng;
 use spoof_gpu::{
-    build_gpu_spoof_script_wgsl, FP_JS, FP_JS_GPU_LINUX, FP_JS_GPU_MAC, FP_JS_GPU_WINDOWS,
-    FP_JS_LINUX, FP_JS_MAC, FP_JS_WINDOWS,
-};
-use spoofs::{
-    resolve_dpr, spoof_device_memory, spoof_history_length_script, spoof_media_codecs_script,
-    spoof_media_labels_script, spoof_screen_script_rng, spoof_touch_screen, CLEANUP_CDP_MARKERS,
-    DISABLE_DIALOGS, HIDE_SELENIUM_MARKERS, SPOOF_NOTIFICATIONS, SPOOF_PERMISSIONS_QUERY,
+    FP_JS, FP_JS_GPU_LINUX, FP_JS_GPU_MAC, FP_JS_GPU_WINDOWS, FP_JS_LINUX, FP_JS_MAC, FP_JS_WINDOWS,
 };
 
 #[cfg(feature = "headers")]
@@ -58,12 +77,9 @@ pub use versions::{
     LATEST_CHROME_FULL_VERSION_FULL,
 };
 
-use crate::spoofs::{
-    PATCH_SPEECH_SYNTHESIS, PLUGIN_AND_MIMETYPE_SPOOF, PLUGIN_AND_MIMETYPE_SPOOF_CHROME,
-};
-
 /// The kind of browser.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BrowserKind {
     /// Chrome
     Chrome,
@@ -77,6 +93,7 @@ pub enum BrowserKind {
     Edge,
     /// Opera
     Opera,
+    #[default]
     /// Other
     Other,
 }
@@ -132,7 +149,7 @@ lazy_static::lazy_static! {
     pub(crate) static ref BROWSER_MATCH: aho_corasick::AhoCorasick = aho_corasick::AhoCorasickBuilder::new()
             .ascii_case_insensitive(true)
             .build(&[
-                "edg/", "edgios", "edge/",        // Edge
+                "edg/", "edgios", "edge/", "edga/", // Edge
                 "opr/", "opera", "opios",         // Opera
                 "firefox", "fxios",               // Firefox
                 "chrome/", "crios", "chromium",   // Chrome
@@ -142,11 +159,6 @@ lazy_static::lazy_static! {
             .expect("valid device patterns");
 
 
-        // Detect Chrome/CriOS first (we only classify Chrome-family UAs).
-        static ref CHROME_AC: aho_corasick::AhoCorasick = aho_corasick::AhoCorasickBuilder::new()
-                .ascii_case_insensitive(true)
-                .build(["Chrome", "CriOS"]) .expect("valid device patterns");
-
         /// OS patterns. Order doesn’t matter; we store priorities separately.
         static ref OS_PATTERNS:[&'static str; 12] =[
             // iOS family first (iPad/iPhone contain "Mac OS X" too, so give them better priority)
@@ -159,7 +171,7 @@ lazy_static::lazy_static! {
             "Macintosh", "Mac OS X", "Mac",
             // Linux
             "Linux",
-            // ChromeOS if you later add an enum variant:
+            // ChromeOS
             "CrOS",
         ];
 
@@ -181,7 +193,7 @@ lazy_static::lazy_static! {
             (AgentOs::Mac,     6),
             (AgentOs::Mac,     7),
             (AgentOs::Linux,   9),
-            (AgentOs::Linux,   8), // CrOS → Linux fallback
+            (AgentOs::ChromeOS, 8),
         ];
 
         static ref FF_PATTERNS: [&'static str; 6] = [
@@ -264,12 +276,12 @@ pub fn detect_browser(ua: &str) -> &'static str {
 
     for m in BROWSER_MATCH.find_iter(ua) {
         match m.pattern().as_u32() {
-            0..=2 => edge = true,    // edg..., edge/
-            3..=5 => opera = true,   // opr/opera/opios
-            6 | 7 => firefox = true, // firefox/fxios
-            8..=10 => chrome = true, // chrome/, crios, chromium
-            11 => safari = true,     // safari
-            12 => brave = true,      // brave
+            0..=3 => edge = true,    // edg..., edge/, edga/
+            4..=6 => opera = true,   // opr/opera/opios
+            7 | 8 => firefox = true, // firefox/fxios
+            9..=11 => chrome = true, // chrome/, crios, chromium
+            12 => safari = true,     // safari
+            13 => brave = true,      // brave
             _ => (),
         }
     }
@@ -389,110 +401,43 @@ pub fn get_random_hardware_concurrency(user_agent: &str) -> usize {
 
 /// Generate the initial stealth script to send in one command.
 fn build_stealth_script_base(
-    gpu_profile: &'static GpuProfile,
+    gpu_profile: &GpuProfile,
     tier: Tier,
     os: AgentOs,
     concurrency: bool,
     browser: BrowserKind,
 ) -> String {
-    use crate::spoofs::{
-        spoof_hardware_concurrency, unified_worker_override, worker_override, HIDE_CHROME,
-        HIDE_CONSOLE, HIDE_WEBDRIVER, NAVIGATOR_SCRIPT, REMOVE_CHROME,
-    };
-
-    // Only spoof window.chrome for Chromium-based browsers.
-    // Non-Chromium browsers (Safari, Firefox) should never get a fake window.chrome
-    // even when agent_os is manually overridden, as it creates a detectable mismatch.
-    let chrome = browser.is_chromium();
-
-    let spoof_worker = if tier == Tier::BasicNoWorker {
-        Default::default()
-    } else if concurrency {
-        unified_worker_override(
-            gpu_profile.hardware_concurrency,
-            gpu_profile.webgl_vendor,
-            gpu_profile.webgl_renderer,
-            !matches!(
-                tier,
-                |Tier::BasicNoWebglWithGPU| Tier::BasicNoWebglWithGPUNoExtra
-                    | Tier::BasicNoWebglWithGPUcWithConsole
-            ),
-        )
-    } else {
-        worker_override(gpu_profile.webgl_vendor, gpu_profile.webgl_renderer)
-    };
-
-    let spoof_concurrency = if concurrency {
-        spoof_hardware_concurrency(gpu_profile.hardware_concurrency)
-    } else {
-        Default::default()
-    };
-
-    let gpu_limit = GpuLimits::for_os(os).with_variation(gpu_profile.hardware_concurrency);
-
-    let spoof_gpu_adapter = build_gpu_request_adapter_script_from_limits(
-        gpu_profile.webgpu_vendor,
-        gpu_profile.webgpu_architecture,
-        "",
-        "",
-        &gpu_limit,
-    );
-
-    let chrome_spoof = if chrome { HIDE_CHROME } else { REMOVE_CHROME };
-
-    match tier {
-        Tier::Basic | Tier::BasicNoWorker | Tier::BasicNoExtra => {
-            format!(
-                r#"{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_gpu_adapter};{NAVIGATOR_SCRIPT}"#
-            )
-        }
-        Tier::BasicWithConsole => {
-            format!(
-                r#"{chrome_spoof};{spoof_worker};{spoof_concurrency};{spoof_gpu_adapter};{NAVIGATOR_SCRIPT}"#
-            )
-        }
-        Tier::BasicNoWebgl | Tier::BasicNoWebglWithGPU | Tier::BasicNoWebglWithGPUNoExtra => {
-            format!(
-                r#"{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_concurrency};{NAVIGATOR_SCRIPT}"#
-            )
-        }
-        Tier::BasicNoWebglWithGPUcWithConsole => {
-            format!(r#"{chrome_spoof};{spoof_worker};{spoof_concurrency};{NAVIGATOR_SCRIPT}"#)
-        }
-        Tier::HideOnly => {
-            format!(r#"{chrome_spoof};{HIDE_CONSOLE};{HIDE_WEBDRIVER}"#)
-        }
-        Tier::HideOnlyWithConsole => {
-            format!(r#"{chrome_spoof};{HIDE_WEBDRIVER}"#)
-        }
-        Tier::HideOnlyChrome => chrome_spoof.into(),
-        Tier::Low => {
-            format!(
-                r#"{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_concurrency};{spoof_gpu_adapter};{HIDE_WEBDRIVER}"#
-            )
-        }
-        Tier::LowWithPlugins => {
-            format!(
-                r#"{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_concurrency};{spoof_gpu_adapter};{HIDE_WEBDRIVER}"#
-            )
-        }
-        Tier::LowWithNavigator => {
-            format!(
-                r#"{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_concurrency};{spoof_gpu_adapter};{HIDE_WEBDRIVER};{NAVIGATOR_SCRIPT}"#
-            )
-        }
-        Tier::Mid => {
-            format!(
-                r#"{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_concurrency};{spoof_gpu_adapter};{NAVIGATOR_SCRIPT};{HIDE_WEBDRIVER}"#
-            )
-        }
-        Tier::Full => {
-            let spoof_gpu = build_gpu_spoof_script_wgsl(gpu_profile.canvas_format);
+    build_stealth_script_base_limits(
+        gpu_profile,
+        tier,
+        os,
+        concurrency,
+        browser,
+        &gpu_profile.limits,
+    )
+}
 
-            format!("{chrome_spoof};{HIDE_CONSOLE};{spoof_worker};{spoof_concurrency};{spoof_gpu_adapter};{HIDE_WEBDRIVER};{NAVIGATOR_SCRIPT};{spoof_gpu}")
-        }
-        _ => Default::default(),
-    }
+/// Generate the initial stealth script to send in one command with resolved gpu limits.
+fn build_stealth_script_base_limits(
+    gpu_profile: &GpuProfile,
+    tier: Tier,
+    os: AgentOs,
+    concurrency: bool,
+    browser: BrowserKind,
+    gpu_limit: &GpuLimits,
+) -> String {
+    spoof_modules::render_modules(
+        tier.modules(),
+        &spoof_modules::ModuleContext {
+            tier,
+            agent_os: os,
+            browser,
+            concurrency,
+            gpu: gpu_profile,
+            gpu_limits: gpu_limit,
+            persona: None,
+        },
+    )
 }
 
 /// Generate the initial stealth script to send in one command.
@@ -501,6 +446,12 @@ pub fn build_stealth_script(tier: Tier, os: AgentOs) -> String {
     build_stealth_script_base(gpu_profile, tier, os, true, BrowserKind::Other)
 }
 
+/// Generate the initial stealth script to send in one command using the provided rng.
+pub fn build_stealth_script_rng<R: Rng>(tier: Tier, os: AgentOs, rng: &mut R) -> String {
+    let gpu_profile = select_random_gpu_profile_rng(os, rng);
+    build_stealth_script_base(gpu_profile, tier, os, true, BrowserKind::Other)
+}
+
 /// Generate the initial stealth script to send in one command without hardware concurrency.
 pub fn build_stealth_script_no_concurrency(tier: Tier, os: AgentOs) -> String {
     let gpu_profile = select_random_gpu_profile(os);
@@ -509,7 +460,7 @@ pub fn build_stealth_script_no_concurrency(tier: Tier, os: AgentOs) -> String {
 
 /// Generate the initial stealth script to send in one command and profile.
 pub fn build_stealth_script_with_profile(
-    gpu_profile: &'static GpuProfile,
+    gpu_profile: &GpuProfile,
     tier: Tier,
     os: AgentOs,
 ) -> String {
@@ -518,7 +469,7 @@ pub fn build_stealth_script_with_profile(
 
 /// Generate the initial stealth script to send in one command and profile.
 pub fn build_stealth_script_with_profile_and_browser(
-    gpu_profile: &'static GpuProfile,
+    gpu_profile: &GpuProfile,
     tier: Tier,
     os: AgentOs,
     browser: BrowserKind,
@@ -528,7 +479,7 @@ pub fn build_stealth_script_with_profile_and_browser(
 
 /// Generate the initial stealth script to send in one command without hardware concurrency and profile.
 pub fn build_stealth_script_no_concurrency_with_profile_and_browser(
-    gpu_profile: &'static GpuProfile,
+    gpu_profile: &GpuProfile,
     tier: Tier,
     os: AgentOs,
     browser: BrowserKind,
@@ -538,7 +489,7 @@ pub fn build_stealth_script_no_concurrency_with_profile_and_browser(
 
 /// Generate the initial stealth script to send in one command without hardware concurrency and profile.
 pub fn build_stealth_script_no_concurrency_with_profile(
-    gpu_profile: &'static GpuProfile,
+    gpu_profile: &GpuProfile,
     tier: Tier,
     os: AgentOs,
 ) -> String {
@@ -619,8 +570,25 @@ pub struct EmulationConfiguration {
     pub disable_touch_screen: bool,
     /// Disable the plugins spoof.
     pub disable_plugins: bool,
+    /// Disable the font availability spoof.
+    pub disable_fonts: bool,
     /// Disable the stealth emulation.
     pub disable_stealth: bool,
+    /// The locale driving navigator.language(s), the Intl defaults and the Accept-Language header.
+    /// Leaves the browser locale untouched when unset.
+    pub locale: Option<spoof_locale::Locale>,
+    /// The IANA time zone driving the Date offsets and the Intl default time zone.
+    /// Leaves the browser time zone untouched when unset.
+    pub timezone: Option<spoof_timezone::Timezone>,
+    /// The WebRTC leak protection. Keeps `RTCPeerConnection` and masks the local ip.
+    /// Leaves WebRTC untouched when unset.
+    pub webrtc: Option<spoof_webrtc::WebRtcMode>,
+    /// Enable the audio fingerprint noise without the rest of the basic fingerprint.
+    /// Always enabled with `Fingerprint::Basic`.
+    pub enable_audio_noise: bool,
+    /// Jitter the WebGPU limits of the gpu profile. Disabled by default - real adapters report
+    /// the exact limits of their driver.
+    pub enable_gpu_limits_jitter: bool,
     /// Enable device memory spoofing (navigator.deviceMemory).
     /// Disabled by default - opt-in for extra stealth.
     pub enable_device_memory: bool,
@@ -632,11 +600,9 @@ pub struct EmulationConfiguration {
     pub enable_selenium_marker_cleanup: bool,
 }
 
-/// Fast Chrome-only OS detection using Aho-Corasick (ASCII case-insensitive).
+/// Fast OS detection for every engine using Aho-Corasick (ASCII case-insensitive).
+/// The browser specific spoofs are picked by the `BrowserKind`, not the os.
 pub fn get_agent_os(user_agent: &str) -> AgentOs {
-    if !CHROME_AC.is_match(user_agent) {
-        return AgentOs::Unknown;
-    }
     let mut best: Option<(u8, usize, AgentOs)> = None;
     for m in OS_AC.find_iter(user_agent) {
         let (os, pri) = OS_MAP[m.pattern()];
@@ -655,6 +621,12 @@ pub fn get_agent_os(user_agent: &str) -> AgentOs {
     best.map(|t| t.2).unwrap_or(AgentOs::Unknown)
 }
 
+/// iPadOS requests the desktop site with a Mac user agent by default. The configured iPad os
+/// with a Mac user agent emulates this desktop mode.
+pub fn is_ipad_desktop_mode(user_agent: &str, agent_os: AgentOs) -> bool {
+    agent_os == AgentOs::IPad && user_agent.contains("Macintosh")
+}
+
 /// Agent Operating system to string
 pub fn agent_os_strings(os: AgentOs) -> &'static str {
     match os {
@@ -672,14 +644,9 @@ pub fn agent_os_strings(os: AgentOs) -> &'static str {
 impl EmulationConfiguration {
     /// Setup the defaults.
     pub fn setup_defaults(user_agent: &str) -> EmulationConfiguration {
-        let mut firefox_agent = false;
-
+        let firefox_agent = user_agent.contains("Firefox");
         let agent_os = get_agent_os(user_agent);
 
-        if agent_os == AgentOs::Unknown {
-            firefox_agent = user_agent.contains("Firefox");
-        }
-
         let mut emulation_config = Self::default();
 
         emulation_config.firefox_agent = firefox_agent;
@@ -692,6 +659,17 @@ impl EmulationConfiguration {
 
         emulation_config
     }
+
+    /// Setup the defaults for iPadOS in desktop mode with a Mac user agent.
+    /// Pair it with an iPad viewport from `randomize_ipad_viewport`.
+    pub fn setup_ipad_desktop_mode(user_agent: &str) -> EmulationConfiguration {
+        let mut emulation_config = Self::setup_defaults(user_agent);
+
+        emulation_config.agent_os = AgentOs::IPad;
+        emulation_config.touch_screen = true;
+
+        emulation_config
+    }
 }
 
 /// Join the scrips pre-allocated.
@@ -721,34 +699,46 @@ pub fn emulate_base(
     config: &EmulationConfiguration,
     viewport: &Option<&crate::spoof_viewport::Viewport>,
     evaluate_on_new_document: &Option<Box<String>>,
-    gpu_profile: Option<&'static GpuProfile>,
+    gpu_profile: Option<&GpuProfile>,
 ) -> Option<String> {
-    let stealth = config.tier.stealth();
-    let agent_os = if config.agent_os == AgentOs::Unknown {
-        get_agent_os(user_agent)
-    } else {
-        config.agent_os
-    };
-    let spoof_user_agent_data = if stealth
-        && config.user_agent_data.unwrap_or(true)
-        && ua_allows_gethighentropy(user_agent)
-    {
-        &crate::spoof_user_agent::spoof_user_agent_data_high_entropy_values(
-            &crate::spoof_user_agent::build_high_entropy_data(&Some(user_agent)),
-        )
-    } else {
-        &Default::default()
-    };
+    emulate_base_rng(
+        user_agent,
+        config,
+        viewport,
+        evaluate_on_new_document,
+        gpu_profile,
+        &mut rand::rng(),
+    )
+}
 
-    let spoof_speech_syn = if stealth && agent_os != AgentOs::Unknown {
-        PATCH_SPEECH_SYNTHESIS
-    } else {
-        Default::default()
-    };
-    let linux = agent_os == AgentOs::Linux;
+/// Emulate a real chrome browser drawing every random choice from the provided rng.
+pub fn emulate_base_rng<R: Rng>(
+    user_agent: &str,
+    config: &EmulationConfiguration,
+    viewport: &Option<&crate::spoof_viewport::Viewport>,
+    evaluate_on_new_document: &Option<Box<String>>,
+    gpu_profile: Option<&GpuProfile>,
+    rng: &mut R,
+) -> Option<String> {
+    let info = parse_user_agent(user_agent);
+
+    emulate_persona(
+        &Persona::resolve_rng(user_agent, &info, config, viewport, gpu_profile, rng),
+        &info,
+        evaluate_on_new_document.as_deref().map(String::as_str),
+    )
+}
 
-    let no_extra =
-        config.tier == Tier::BasicNoExtra || config.tier == Tier::BasicNoWebglWithGPUNoExtra;
+/// Emulate a real chrome browser from a resolved persona and its parsed user agent.
+pub(crate) fn emulate_persona(
+    persona: &Persona,
+    info: &UserAgentInfo,
+    evaluate_on_new_document: Option<&str>,
+) -> Option<String> {
+    let config = &persona.config;
+    let stealth = config.tier.stealth();
+    let agent_os = config.agent_os;
+    let linux = matches!(agent_os, AgentOs::Linux | AgentOs::ChromeOS);
 
     let (fingerprint, fingerprint_gpu) = match config.fingerprint {
         Fingerprint::Basic => (true, false),
@@ -782,159 +772,37 @@ pub fn emulate_base(
         &Default::default()
     };
 
-    let mut mobile_device = false;
-
-    let screen_spoof = if let Some(viewport) = &viewport {
-        mobile_device = viewport.emulating_mobile;
-        let dpr = resolve_dpr(
-            viewport.emulating_mobile,
-            viewport.device_scale_factor,
-            agent_os,
-        );
-
-        spoof_screen_script_rng(
-            viewport.width,
-            viewport.height,
-            dpr,
-            viewport.emulating_mobile,
-            &mut rand::rng(),
-            agent_os,
-        )
+    // The WebRTC module keeps the peer connection so the blanket removal is dropped.
+    let rtc_fp_script;
+    let fp_script = if fingerprint && config.webrtc.is_some() {
+        rtc_fp_script = fp_script.replacen(spoof_gpu::SPOOF_RTC, "", 1);
+        &rtc_fp_script
     } else {
-        Default::default()
+        fp_script
     };
 
-    let gpu_profile = gpu_profile.unwrap_or(select_random_gpu_profile(agent_os));
-    let browser_kind = detect_browser_kind(user_agent);
-
-    let plugin_spoof = if browser_kind == BrowserKind::Chrome {
-        PLUGIN_AND_MIMETYPE_SPOOF_CHROME
-    } else {
-        PLUGIN_AND_MIMETYPE_SPOOF
-    };
-
-    let st = if config.hardware_concurrency {
-        crate::build_stealth_script_with_profile_and_browser(
-            gpu_profile,
-            config.tier,
-            agent_os,
-            browser_kind,
-        )
-    } else {
-        crate::build_stealth_script_no_concurrency_with_profile_and_browser(
-            gpu_profile,
-            config.tier,
-            agent_os,
-            browser_kind,
-        )
-    };
-
-    let touch_screen_script = if config.touch_screen {
-        spoof_touch_screen(mobile_device)
-    } else {
-        Default::default()
-    };
-
-    let eval_script = if let Some(script) = evaluate_on_new_document.as_deref() {
+    let eval_script = if let Some(script) = evaluate_on_new_document {
         wrap_eval_script(script)
     } else {
         Default::default()
     };
 
-    // Device memory spoof (opt-in) - realistic values per platform
-    let device_memory_script = if config.enable_device_memory {
-        let memory = match agent_os {
-            AgentOs::Android | AgentOs::IPhone | AgentOs::IPad => {
-                *[2, 3, 4].choose(&mut rand::rng()).unwrap_or(&4)
-            }
-            _ => *[4, 8].choose(&mut rand::rng()).unwrap_or(&8),
-        };
-        spoof_device_memory(memory)
-    } else {
-        Default::default()
-    };
-
-    let stealth_scripts = if stealth {
-        join_scripts([
-            if no_extra || config.disable_speech_syntheses {
-                Default::default()
-            } else {
-                spoof_speech_syn
-            },
-            if no_extra || config.disable_user_agent_data {
-                Default::default()
-            } else {
-                spoof_user_agent_data
-            },
-            if no_extra || config.dismiss_dialogs {
-                DISABLE_DIALOGS
-            } else {
-                ""
-            },
-            if no_extra || config.disable_screen {
-                Default::default()
-            } else {
-                &screen_spoof
-            },
-            if no_extra || config.disable_notifications {
-                Default::default()
-            } else {
-                SPOOF_NOTIFICATIONS
-            },
-            if no_extra || config.disable_permissions {
-                Default::default()
-            } else {
-                SPOOF_PERMISSIONS_QUERY
-            },
-            if no_extra || config.disable_media_codecs {
-                Default::default()
-            } else {
-                spoof_media_codecs_script()
-            },
-            if no_extra || config.disable_touch_screen {
-                Default::default()
-            } else {
-                touch_screen_script
-            },
-            &if no_extra || config.disable_media_labels {
-                Default::default()
-            } else {
-                spoof_media_labels_script(agent_os)
-            },
-            &if no_extra || config.disable_history_length {
-                Default::default()
-            } else {
-                spoof_history_length_script(rand::rng().random_range(1..=6))
-            },
-            &if no_extra || config.disable_plugins && config.tier != Tier::LowWithPlugins {
-                Default::default()
-            } else {
-                plugin_spoof
-            },
-            // Opt-in spoofs for extra stealth (non-intrusive, safe across profiles)
-            &device_memory_script,
-            if config.enable_cdp_marker_cleanup {
-                CLEANUP_CDP_MARKERS
-            } else {
-                ""
-            },
-            if config.enable_selenium_marker_cleanup {
-                HIDE_SELENIUM_MARKERS
-            } else {
-                ""
-            },
-            &if config.disable_stealth {
-                Default::default()
-            } else {
-                st
-            },
-        ])
-    } else {
-        Default::default()
-    };
+    // The noise and the extras are toggled by the configuration, the core patches by the tier.
+    let stealth_scripts = spoof_modules::render_modules(
+        &spoof_modules::tier_modules(config.tier),
+        &spoof_modules::ModuleContext {
+            tier: config.tier,
+            agent_os,
+            browser: info.browser,
+            concurrency: config.hardware_concurrency,
+            gpu: &persona.gpu,
+            gpu_limits: &persona.gpu_limits,
+            persona: Some(persona),
+        },
+    );
 
     // Final combined script to inject
-    if stealth || fingerprint {
+    if stealth || fingerprint || !stealth_scripts.is_empty() {
         Some(join_scripts_with_capacity(
             [fp_script, &stealth_scripts, &eval_script],
             fp_script.capacity() + stealth_scripts.capacity() + eval_script.capacity(),
@@ -962,7 +830,7 @@ pub fn emulate_with_profile(
     config: &EmulationConfiguration,
     viewport: &Option<&crate::spoof_viewport::Viewport>,
     evaluate_on_new_document: &Option<Box<String>>,
-    gpu_profile: &'static GpuProfile,
+    gpu_profile: &GpuProfile,
 ) -> Option<String> {
     emulate_base(
         user_agent,
@@ -1063,6 +931,10 @@ mod tests {
             ("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
              AgentOs::Linux),
 
+            // ChromeOS (Chrome)
+            ("Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
+             AgentOs::ChromeOS),
+
             // Android (Chrome)
             ("Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Mobile Safari/537.36",
              AgentOs::Android),
@@ -1083,9 +955,17 @@ mod tests {
             ("mozilla/5.0 (x11; linux x86_64) applewebkit/537.36 (khtml, like gecko) chrome/120.0.0.0 safari/537.36",
              AgentOs::Linux),
 
-            // Non-Chrome (Firefox) -> Unknown due to Chrome/CriOS gate
+            // Firefox -> Windows
             ("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:124.0) Gecko/20100101 Firefox/124.0",
-             AgentOs::Unknown),
+             AgentOs::Windows),
+
+            // Firefox on Linux
+            ("Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:126.0) Gecko/20100101 Firefox/126.0",
+             AgentOs::Linux),
+
+            // Safari on iPhone (no Chrome token)
+            ("Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1",
+             AgentOs::IPhone),
 
             // Not a browser UA
             ("curl/8.0.1",
@@ -1422,17 +1302,65 @@ mod tests {
     // --- Agent OS detection edge cases ---
 
     #[test]
-    fn safari_ua_returns_unknown_os() {
-        // Safari-only UAs don't contain Chrome/CriOS, so get_agent_os returns Unknown.
-        // This is critical — it prevents Safari from accidentally getting Chrome spoofs.
+    fn safari_ua_returns_mac_os() {
+        // The os is detected for every engine, the Chrome spoofs are gated by the browser kind.
         let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
-        assert_eq!(get_agent_os(ua), AgentOs::Unknown);
+        assert_eq!(get_agent_os(ua), AgentOs::Mac);
+
+        let config = EmulationConfiguration::setup_defaults(ua);
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        assert!(!script.contains("metricsPrivate"));
     }
 
     #[test]
-    fn firefox_ua_returns_unknown_os() {
+    fn safari_ua_gets_no_chrome_only_patches() {
+        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.tier = Tier::Full;
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+
+        assert!(!script.contains("Google US English"));
+        assert!(!script.contains("ANGLE"));
+        assert!(!script.contains("requestAdapter"));
+        assert!(script.contains("Apple GPU"));
+
         let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:124.0) Gecko/20100101 Firefox/124.0";
-        assert_eq!(get_agent_os(ua), AgentOs::Unknown);
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.tier = Tier::Full;
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+
+        assert!(!script.contains("Google US English"));
+        assert!(!script.contains("ANGLE"));
+        assert!(!script.contains("requestAdapter"));
+        // the workers report the same concurrency as the window
+        assert!(config.hardware_concurrency);
+        assert!(script.contains("importScripts"));
+        assert!(!script.contains("getParameter"));
+    }
+
+    #[test]
+    fn firefox_ua_returns_windows_os() {
+        use crate::profiles::gpu::gpu_profiles_for_os;
+        use crate::Persona;
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:124.0) Gecko/20100101 Firefox/124.0";
+        assert_eq!(get_agent_os(ua), AgentOs::Windows);
+
+        let config = EmulationConfiguration::setup_defaults(ua);
+        assert!(config.firefox_agent);
+        assert_eq!(config.agent_os, AgentOs::Windows);
+
+        let persona = Persona::generate_rng(
+            ua,
+            &config,
+            &None,
+            None,
+            false,
+            &None,
+            &mut StdRng::seed_from_u64(3),
+        );
+        assert!(gpu_profiles_for_os(AgentOs::Windows).contains(&&persona.gpu));
     }
 
     // --- Tier-specific tests ---
@@ -1527,7 +1455,7 @@ mod tests {
 
     #[test]
     fn speech_synthesis_not_applied_for_unknown_os() {
-        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:124.0) Gecko/20100101 Firefox/124.0";
+        let ua = "Mozilla/5.0 (compatible; Konqueror/4.5; FreeBSD) KHTML/4.5.4 (like Gecko)";
         let config = EmulationConfiguration::setup_defaults(ua);
         assert_eq!(config.agent_os, AgentOs::Unknown);
         // When OS is unknown, speech synthesis should not be in the output
@@ -1539,4 +1467,95 @@ mod tests {
             );
         }
     }
+
+    // --- Extra tier ---
+
+    #[test]
+    fn extra_tier_emits_extras_without_core_patches() {
+        let script = crate::build_stealth_script(Tier::Extra, AgentOs::Mac);
+        assert!(script.contains("speechSynthesis"));
+        assert!(script.contains("navigator.permissions"));
+        assert!(!script.contains(HIDE_CHROME));
+        assert!(!script.contains(REMOVE_CHROME));
+        assert!(!script.contains(crate::spoofs::HIDE_WEBDRIVER));
+    }
+
+    #[test]
+    fn extra_tier_emulation_is_not_empty() {
+        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.tier = Tier::Extra;
+        let viewport = crate::spoof_viewport::Viewport::new(1440, 900);
+        let script = emulate(ua, &config, &Some(&viewport), &None).unwrap_or_default();
+
+        assert!(script.contains("speechSynthesis"));
+        assert!(script.contains("getHighEntropyValues"));
+        assert!(!script.contains(HIDE_CHROME));
+        assert!(!script.contains(crate::spoofs::HIDE_WEBDRIVER));
+    }
+
+    #[test]
+    fn chromeos_is_emulated_first_class() {
+        use crate::profiles::gpu::{select_random_gpu_profile, GPU_PROFILES_CHROMEOS};
+        use crate::spoof_user_agent::build_high_entropy_data;
+
+        let ua = "Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let config = EmulationConfiguration::setup_defaults(ua);
+        assert_eq!(config.agent_os, AgentOs::ChromeOS);
+        assert!(GPU_PROFILES_CHROMEOS.contains(select_random_gpu_profile(AgentOs::ChromeOS)));
+
+        let data = build_high_entropy_data(&Some(ua));
+        assert_eq!(data.platform, "Chrome OS");
+        assert_eq!(data.platform_version, "16295.54.0");
+        assert_eq!(data.architecture, "x86");
+
+        let script = emulate(ua, &config, &None, &None).expect("script");
+        assert!(script.contains("'Chrome OS'"));
+        assert!(GPU_PROFILES_CHROMEOS
+            .iter()
+            .any(|p| script.contains(p.webgl_renderer)));
+    }
+
+    #[test]
+    fn ipad_desktop_mode_is_consistent() {
+        use crate::profiles::gpu::GPU_PROFILES_IPAD;
+        use crate::spoof_viewport::{randomize_ipad_viewport_rng, IPAD_SCREEN_SIZES};
+        use crate::{is_ipad_desktop_mode, Persona};
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Safari/605.1.15";
+        let chrome_mac = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        assert_eq!(get_agent_os(chrome_mac), AgentOs::Mac);
+        assert!(!is_ipad_desktop_mode(chrome_mac, AgentOs::Mac));
+        assert!(is_ipad_desktop_mode(ua, AgentOs::IPad));
+
+        let mut rng = StdRng::seed_from_u64(5);
+        let config = EmulationConfiguration::setup_ipad_desktop_mode(ua);
+        let viewport = randomize_ipad_viewport_rng(&mut rng);
+        assert!(IPAD_SCREEN_SIZES.contains(&(viewport.width, viewport.height)));
+        assert!(viewport.has_touch && !viewport.emulating_mobile);
+
+        let persona =
+            Persona::generate_rng(ua, &config, &Some(&viewport), None, false, &None, &mut rng);
+        assert_eq!(persona.config.agent_os, AgentOs::IPad);
+        assert!(GPU_PROFILES_IPAD.contains(&persona.gpu));
+        let screen = persona.screen.expect("screen");
+        assert_eq!((screen.screen_x, screen.screen_y), (0, 0));
+        assert_eq!(screen.device_pixel_ratio, 2.0);
+        assert!(persona.validate().is_empty());
+
+        let script = persona.emulate(&None).expect("script");
+        assert!(script.contains("const mtp=()=>5;"));
+
+        let persona = Persona::generate_rng(
+            chrome_mac,
+            &config,
+            &Some(&viewport),
+            None,
+            false,
+            &None,
+            &mut rng,
+        );
+        assert_eq!(persona.high_entropy, None);
+    }
 }
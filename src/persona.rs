@@ -0,0 +1,455 @@
+use crate::configs::{AgentOs, Tier};
+use crate::profiles::device::DeviceProfile;
+use crate::profiles::gpu::{select_random_gpu_profile_rng, GpuProfile};
+use crate::profiles::gpu_limits::GpuLimits;
+use crate::spoof_user_agent::build_high_entropy_data_from_info_rng;
+use crate::spoof_user_agent::HighEntropyUaData;
+use crate::spoof_viewport::Viewport;
+use crate::spoofs::{resolve_dpr, ScreenProfile};
+use crate::{is_ipad_desktop_mode, parse_user_agent, EmulationConfiguration, UserAgentInfo};
+use rand::{rng, Rng};
+
+/// A fully resolved fingerprint.
+///
+/// Every value that is normally rolled while emulating (gpu profile, WebGPU limits, device,
+/// screen metrics, device memory, history length, UA-CH data, noise seed, header choices) is captured up front.
+/// The persona can be stored and later rendered into the stealth script and the matching
+/// headers without re-rolling anything.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Persona {
+    /// The user agent.
+    pub user_agent: String,
+    /// The emulation configuration with the agent os resolved.
+    pub config: EmulationConfiguration,
+    /// The gpu profile.
+    pub gpu: GpuProfile,
+    /// The WebGPU adapter limits.
+    pub gpu_limits: GpuLimits,
+    /// The cores, memory and screen of the device drawn with the gpu profile.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub device: DeviceProfile,
+    /// The viewport.
+    pub viewport: Option<Viewport>,
+    /// The window and screen metrics. Set when a viewport is used.
+    pub screen: Option<ScreenProfile>,
+    /// The `navigator.deviceMemory` value. Set when device memory spoofing is enabled.
+    pub device_memory: Option<u8>,
+    /// The `history.length` value. Set when the history length spoof is used.
+    pub history_length: Option<u32>,
+    /// The `navigator.userAgentData` high entropy values. Set when the UA allows them.
+    pub high_entropy: Option<HighEntropyUaData>,
+    /// The seed of the fingerprint noise. Keeps the noise stable across calls and pages.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub noise_seed: u32,
+    #[cfg(feature = "headers")]
+    /// The http header choices.
+    pub headers: crate::spoof_headers::HeaderChoices,
+}
+
+impl Persona {
+    /// Generate a new persona. The header choices are rolled for the `chrome` header handling
+    /// and the referer domain.
+    pub fn generate(
+        user_agent: &str,
+        config: &EmulationConfiguration,
+        viewport: &Option<&Viewport>,
+        gpu_profile: Option<&GpuProfile>,
+        chrome: bool,
+        domain_parsed: &Option<Box<url::Url>>,
+    ) -> Self {
+        Self::generate_rng(
+            user_agent,
+            config,
+            viewport,
+            gpu_profile,
+            chrome,
+            domain_parsed,
+            &mut rng(),
+        )
+    }
+
+    /// Generate a new persona drawing every random choice from the provided rng.
+    #[cfg_attr(not(feature = "headers"), allow(unused_variables))]
+    pub fn generate_rng<R: Rng>(
+        user_agent: &str,
+        config: &EmulationConfiguration,
+        viewport: &Option<&Viewport>,
+        gpu_profile: Option<&GpuProfile>,
+        chrome: bool,
+        domain_parsed: &Option<Box<url::Url>>,
+        rng: &mut R,
+    ) -> Self {
+        let info = parse_user_agent(user_agent);
+        let persona = Self::resolve_rng(user_agent, &info, config, viewport, gpu_profile, rng);
+
+        #[cfg(feature = "headers")]
+        let persona = Self {
+            headers: crate::spoof_headers::HeaderChoices::random_from_info_rng(
+                &info,
+                chrome,
+                domain_parsed,
+                rng,
+            )
+            .with_device(&persona.device),
+            ..persona
+        };
+
+        persona
+    }
+
+    /// Resolve the script values from the parsed user agent, drawing from the rng in the same
+    /// order as the emulation.
+    pub(crate) fn resolve_rng<R: Rng>(
+        user_agent: &str,
+        info: &UserAgentInfo,
+        config: &EmulationConfiguration,
+        viewport: &Option<&Viewport>,
+        gpu_profile: Option<&GpuProfile>,
+        rng: &mut R,
+    ) -> Self {
+        let agent_os = if config.agent_os == AgentOs::Unknown {
+            info.os
+        } else {
+            config.agent_os
+        };
+
+        // iPadOS browsers are WebKit even when requesting the desktop site.
+        let high_entropy = if config.tier.stealth()
+            && config.user_agent_data.unwrap_or(true)
+            && info.allows_high_entropy()
+            && !is_ipad_desktop_mode(user_agent, agent_os)
+        {
+            Some(build_high_entropy_data_from_info_rng(info, rng))
+        } else {
+            None
+        };
+
+        let screen = viewport.map(|viewport| {
+            ScreenProfile::random_rng(
+                viewport.width,
+                viewport.height,
+                resolve_dpr(
+                    viewport.emulating_mobile,
+                    viewport.device_scale_factor,
+                    agent_os,
+                ),
+                viewport.emulating_mobile,
+                rng,
+                agent_os,
+            )
+        });
+
+        let gpu = match gpu_profile {
+            Some(gpu_profile) => gpu_profile.clone(),
+            _ => select_random_gpu_profile_rng(agent_os, rng).clone(),
+        };
+
+        let gpu_limits = if config.enable_gpu_limits_jitter {
+            gpu.limits.with_variation_rng(gpu.hardware_concurrency, rng)
+        } else {
+            gpu.limits
+        };
+
+        let device = DeviceProfile::for_gpu_rng(&gpu, agent_os, rng);
+        let device = match &screen {
+            Some(screen) => DeviceProfile {
+                screen_width: screen.screen_width,
+                screen_height: screen.screen_height,
+                device_pixel_ratio: screen.device_pixel_ratio,
+                ..device
+            },
+            _ => device,
+        };
+
+        let device_memory = config.enable_device_memory.then_some(device.device_memory);
+
+        let no_extra =
+            config.tier == Tier::BasicNoExtra || config.tier == Tier::BasicNoWebglWithGPUNoExtra;
+
+        let history_length = if no_extra || config.disable_history_length {
+            None
+        } else {
+            Some(rng.random_range(1..=6))
+        };
+
+        let noise_seed = rng.random();
+
+        Self {
+            user_agent: user_agent.into(),
+            config: EmulationConfiguration {
+                agent_os,
+                ..*config
+            },
+            gpu,
+            gpu_limits,
+            device,
+            viewport: viewport.copied(),
+            screen,
+            device_memory,
+            history_length,
+            high_entropy,
+            noise_seed,
+            #[cfg(feature = "headers")]
+            headers: Default::default(),
+        }
+    }
+
+    /// Check the persona layers agree with each other.
+    pub fn validate(&self) -> crate::validate::ValidationReport {
+        let mut target = crate::validate::ValidationTarget::new(&self.user_agent, &self.config)
+            .with_gpu_profile(&self.gpu);
+        target.viewport = self.viewport;
+        target.validate()
+    }
+
+    /// Render the stealth script for the persona.
+    pub fn emulate(&self, evaluate_on_new_document: &Option<Box<String>>) -> Option<String> {
+        crate::emulate_persona(
+            self,
+            &parse_user_agent(&self.user_agent),
+            evaluate_on_new_document.as_deref().map(String::as_str),
+        )
+    }
+
+    #[cfg(feature = "headers")]
+    /// Render the http headers for the persona.
+    pub fn emulate_headers(
+        &self,
+        header_map: &Option<&http::HeaderMap>,
+        hostname: &Option<&str>,
+        chrome: bool,
+        detail_level: &Option<crate::spoof_headers::HeaderDetailLevel>,
+    ) -> http::HeaderMap {
+        crate::spoof_headers::emulate_headers_with_choices(
+            &self.user_agent,
+            header_map,
+            hostname,
+            chrome,
+            &self.viewport,
+            detail_level,
+            self.config.locale,
+            self.high_entropy.as_ref(),
+            &self.headers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const UA: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+
+    fn persona() -> Persona {
+        let mut config = EmulationConfiguration::setup_defaults(UA);
+        config.enable_device_memory = true;
+        config.disable_history_length = false;
+        let viewport = Viewport::new(1920, 1080);
+
+        Persona::generate_rng(
+            UA,
+            &config,
+            &Some(&viewport),
+            None,
+            false,
+            &None,
+            &mut StdRng::seed_from_u64(9),
+        )
+    }
+
+    #[test]
+    fn persona_renders_without_rerolling() {
+        let persona = persona();
+
+        assert!(persona.emulate(&None).is_some());
+        assert_eq!(persona.emulate(&None), persona.emulate(&None));
+        assert!(persona.device_memory.is_some());
+        assert!(persona.history_length.is_some());
+        assert!(persona.screen.is_some());
+        assert_eq!(persona.config.agent_os, AgentOs::Windows);
+    }
+
+    #[test]
+    fn persona_matches_emulate() {
+        let mut config = EmulationConfiguration::setup_defaults(UA);
+        config.enable_device_memory = true;
+        let viewport = Viewport::new(1440, 900);
+
+        let persona = Persona::resolve_rng(
+            UA,
+            &parse_user_agent(UA),
+            &config,
+            &Some(&viewport),
+            None,
+            &mut StdRng::seed_from_u64(3),
+        );
+        let script = crate::emulate_base_rng(
+            UA,
+            &config,
+            &Some(&viewport),
+            &None,
+            None,
+            &mut StdRng::seed_from_u64(3),
+        );
+
+        assert_eq!(persona.emulate(&None), script);
+    }
+
+    #[test]
+    fn persona_uses_exact_gpu_limits() {
+        let persona = persona();
+        assert_eq!(persona.gpu_limits, persona.gpu.limits);
+
+        let mut config = EmulationConfiguration::setup_defaults(UA);
+        config.enable_gpu_limits_jitter = true;
+        let jittered = Persona::generate_rng(
+            UA,
+            &config,
+            &None,
+            None,
+            false,
+            &None,
+            &mut StdRng::seed_from_u64(9),
+        );
+        assert_ne!(jittered.gpu_limits, jittered.gpu.limits);
+    }
+
+    #[test]
+    fn persona_without_viewport_keeps_the_window() {
+        let config = EmulationConfiguration::setup_defaults(UA);
+        let persona = Persona::generate_rng(
+            UA,
+            &config,
+            &None,
+            None,
+            false,
+            &None,
+            &mut StdRng::seed_from_u64(5),
+        );
+
+        assert_eq!(persona.screen, None);
+        assert!(persona
+            .emulate(&None)
+            .is_some_and(|script| !script.contains("Object.defineProperty(window,'innerWidth'")));
+    }
+
+    #[test]
+    fn persona_device_is_one_machine() {
+        let persona = persona();
+        let screen = persona.screen.expect("screen");
+
+        assert_eq!(
+            persona.device.hardware_concurrency,
+            persona.gpu.hardware_concurrency
+        );
+        assert_eq!(persona.device_memory, Some(persona.device.device_memory));
+        assert_eq!(persona.device.screen_width, screen.screen_width);
+        #[cfg(feature = "headers")]
+        {
+            assert_eq!(
+                persona.headers.device_memory,
+                f64::from(persona.device.device_memory)
+            );
+            assert_eq!(
+                persona.headers.device_pixel_ratio,
+                persona.device.device_pixel_ratio
+            );
+            let headers = persona.emulate_headers(&None, &Some("example.com"), false, &None);
+            assert_eq!(
+                headers
+                    .get("Device-Memory")
+                    .map(|v| v.to_str().unwrap_or_default()),
+                Some(persona.device.device_memory.to_string().as_str())
+            );
+        }
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn persona_headers_match_user_agent_data() {
+        use crate::spoof_user_agent::{
+            format_sec_ch_ua, spoof_user_agent_data_high_entropy_values,
+        };
+
+        let persona = persona();
+        let data = persona.high_entropy.as_ref().expect("high entropy");
+        let script = spoof_user_agent_data_high_entropy_values(data);
+        let headers = persona.emulate_headers(&None, &Some("example.com"), false, &None);
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_string()
+        };
+
+        assert_eq!(header("sec-ch-ua"), format_sec_ch_ua(&data.brands()));
+        assert_eq!(
+            header("sec-ch-ua-full-version-list"),
+            format_sec_ch_ua(&data.full_version_list)
+        );
+        for brand in &data.full_version_list {
+            assert!(script.contains(&format!(
+                "{{brand:'{}',version:'{}'}}",
+                brand.brand, brand.version
+            )));
+        }
+        for (name, value, key) in [
+            ("sec-ch-ua-platform", &data.platform, "platform"),
+            (
+                "sec-ch-ua-platform-version",
+                &data.platform_version,
+                "platformVersion",
+            ),
+            ("sec-ch-ua-arch", &data.architecture, "architecture"),
+            ("sec-ch-ua-bitness", &data.bitness, "bitness"),
+            ("sec-ch-ua-model", &data.model, "model"),
+        ] {
+            assert_eq!(header(name), format!("\"{value}\""));
+            assert!(script.contains(&format!("{key}:'{value}'")));
+        }
+        assert_eq!(header("sec-ch-ua-mobile"), "?0");
+        assert!(script.contains("mobile:!1"));
+    }
+
+    #[cfg(feature = "headers")]
+    #[test]
+    fn persona_rolls_the_referer_for_the_domain() {
+        let config = EmulationConfiguration::setup_defaults(UA);
+        let domain = url::Url::parse("https://example.com").ok().map(Box::new);
+
+        assert!((0..64).any(|seed| {
+            Persona::generate_rng(
+                UA,
+                &config,
+                &None,
+                None,
+                false,
+                &domain,
+                &mut StdRng::seed_from_u64(seed),
+            )
+            .headers
+            .referer
+            .is_some_and(|referer| referer.contains("google.com/search?q=example"))
+        }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn persona_serde_round_trip() {
+        let persona = persona();
+        let json = serde_json::to_string(&persona).expect("serialize");
+        let restored: Persona = serde_json::from_str(&json).expect("deserialize");
+
+        assert_eq!(restored, persona);
+        assert_eq!(restored.emulate(&None), persona.emulate(&None));
+        #[cfg(feature = "headers")]
+        assert_eq!(
+            restored.emulate_headers(&None, &Some("example.com"), false, &None),
+            persona.emulate_headers(&None, &Some("example.com"), false, &None)
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use super::gpu::{select_random_gpu_profile_rng, GpuProfile};
+use crate::configs::AgentOs;
+use rand::distr::{weighted::WeightedIndex, Distribution};
+use rand::prelude::IndexedRandom;
+use rand::Rng;
+
+/// Desktop screens as `(width, height, device pixel ratio)`.
+static SCREENS_DESKTOP: &[(u32, u32, f64)] = &[
+    (1920, 1080, 1.0),
+    (1536, 864, 1.25),
+    (1366, 768, 1.0),
+    (2560, 1440, 1.0),
+    (1440, 900, 1.0),
+    (1280, 720, 1.5),
+];
+
+/// Mac screens as `(width, height, device pixel ratio)`.
+static SCREENS_MAC: &[(u32, u32, f64)] = &[
+    (1440, 900, 2.0),
+    (1470, 956, 2.0),
+    (1512, 982, 2.0),
+    (1728, 1117, 2.0),
+    (1920, 1080, 1.0),
+    (2560, 1440, 1.0),
+];
+
+/// Chromebook screens as `(width, height, device pixel ratio)`.
+static SCREENS_CHROMEOS: &[(u32, u32, f64)] = &[
+    (1366, 768, 1.0),
+    (1536, 864, 1.25),
+    (1280, 800, 1.5),
+    (1920, 1080, 1.0),
+];
+
+/// Android phone screens as `(width, height, device pixel ratio)`.
+static SCREENS_ANDROID: &[(u32, u32, f64)] = &[
+    (412, 915, 2.625),
+    (393, 873, 2.75),
+    (360, 800, 3.0),
+    (384, 854, 2.8125),
+    (412, 892, 2.625),
+];
+
+/// iPhone screens as `(width, height, device pixel ratio)`.
+static SCREENS_IPHONE: &[(u32, u32, f64)] = &[
+    (390, 844, 3.0),
+    (393, 852, 3.0),
+    (430, 932, 3.0),
+    (375, 812, 3.0),
+    (402, 874, 3.0),
+];
+
+/// One plausible machine. The cores and the gpu come from the gpu profile, the memory and
+/// screen are drawn for that device so every surface reporting them agrees.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceProfile {
+    /// The `navigator.hardwareConcurrency` value.
+    pub hardware_concurrency: usize,
+    /// The `navigator.deviceMemory` and `Device-Memory` header value in GB.
+    pub device_memory: u8,
+    /// The `screen.width` value.
+    pub screen_width: u32,
+    /// The `screen.height` value.
+    pub screen_height: u32,
+    /// The `window.devicePixelRatio` value.
+    pub device_pixel_ratio: f64,
+}
+
+impl DeviceProfile {
+    /// Draw a random device for the os.
+    pub fn random(os: AgentOs) -> Self {
+        Self::random_rng(os, &mut rand::rng())
+    }
+
+    /// Draw a random device for the os using the provided rng.
+    pub fn random_rng<R: Rng>(os: AgentOs, rng: &mut R) -> Self {
+        let gpu = select_random_gpu_profile_rng(os, rng);
+        Self::for_gpu_rng(gpu, os, rng)
+    }
+
+    /// Draw the memory and screen of the device using the gpu profile.
+    pub fn for_gpu_rng<R: Rng>(gpu: &GpuProfile, os: AgentOs, rng: &mut R) -> Self {
+        let device_memory = device_memory_for_rng(os, gpu.hardware_concurrency, rng);
+
+        let (screen_width, screen_height, device_pixel_ratio) = match os {
+            AgentOs::IPad => crate::spoof_viewport::IPAD_SCREEN_SIZES
+                .choose(rng)
+                .map(|(width, height)| (*width, *height, 2.0)),
+            _ => screens_for_os(os).choose(rng).copied(),
+        }
+        .unwrap_or((1920, 1080, 1.0));
+
+        Self {
+            hardware_concurrency: gpu.hardware_concurrency,
+            device_memory,
+            screen_width,
+            screen_height,
+            device_pixel_ratio,
+        }
+    }
+}
+
+/// The screens of the os.
+fn screens_for_os(os: AgentOs) -> &'static [(u32, u32, f64)] {
+    match os {
+        AgentOs::Mac => SCREENS_MAC,
+        AgentOs::ChromeOS => SCREENS_CHROMEOS,
+        AgentOs::Android => SCREENS_ANDROID,
+        AgentOs::IPhone | AgentOs::IPad => SCREENS_IPHONE,
+        AgentOs::Windows | AgentOs::Linux | AgentOs::Unknown => SCREENS_DESKTOP,
+    }
+}
+
+/// Draw a `navigator.deviceMemory` value for a device with the cores. Chrome reports the
+/// memory rounded down to a power of two and capped at 8.
+pub fn device_memory_for_rng<R: Rng>(os: AgentOs, hardware_concurrency: usize, rng: &mut R) -> u8 {
+    let cores = hardware_concurrency;
+
+    let (levels, weights): (&[u8], &[u32]) = match os {
+        // Apple silicon ships with at least 8GB.
+        AgentOs::Mac => (&[8], &[1]),
+        AgentOs::IPhone => (&[4, 8], &[3, 1]),
+        AgentOs::IPad if cores >= 8 => (&[8], &[1]),
+        AgentOs::IPad => (&[4, 8], &[3, 1]),
+        AgentOs::Android if cores >= 8 => (&[4, 8], &[1, 2]),
+        AgentOs::Android => (&[2, 4], &[1, 2]),
+        AgentOs::ChromeOS if cores >= 8 => (&[4, 8], &[1, 1]),
+        AgentOs::ChromeOS if cores >= 4 => (&[4, 8], &[3, 1]),
+        AgentOs::ChromeOS => (&[4], &[1]),
+        _ if cores >= 8 => (&[4, 8], &[1, 9]),
+        _ if cores >= 4 => (&[4, 8], &[1, 1]),
+        _ => (&[2, 4], &[1, 2]),
+    };
+
+    match WeightedIndex::new(weights) {
+        Ok(dist) => levels[dist.sample(rng)],
+        _ => *levels.choose(rng).unwrap_or(&8),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::gpu::gpu_profiles_for_os;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn device_follows_the_gpu_profile() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for os in [
+            AgentOs::Windows,
+            AgentOs::Mac,
+            AgentOs::Linux,
+            AgentOs::ChromeOS,
+            AgentOs::Android,
+            AgentOs::IPhone,
+            AgentOs::IPad,
+        ] {
+            for _ in 0..50 {
+                let device = DeviceProfile::random_rng(os, &mut rng);
+
+                assert!(gpu_profiles_for_os(os)
+                    .iter()
+                    .any(|p| p.hardware_concurrency == device.hardware_concurrency));
+                assert!(device.device_memory.is_power_of_two() && device.device_memory <= 8);
+                assert!(device.screen_width > 0 && device.device_pixel_ratio >= 1.0);
+
+                if os == AgentOs::Mac {
+                    assert_eq!(device.device_memory, 8);
+                }
+                if device.hardware_concurrency <= 2 {
+                    assert!(device.device_memory <= 4);
+                }
+            }
+        }
+    }
+}
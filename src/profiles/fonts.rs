@@ -0,0 +1,229 @@
+use crate::AgentOs;
+
+/// The fonts of a default Windows 10/11 install.
+pub static FONTS_WINDOWS: &[&str] = &[
+    "Arial",
+    "Arial Black",
+    "Bahnschrift",
+    "Calibri",
+    "Cambria",
+    "Cambria Math",
+    "Candara",
+    "Comic Sans MS",
+    "Consolas",
+    "Constantia",
+    "Corbel",
+    "Courier New",
+    "Ebrima",
+    "Franklin Gothic Medium",
+    "Gabriola",
+    "Gadugi",
+    "Georgia",
+    "HoloLens MDL2 Assets",
+    "Impact",
+    "Ink Free",
+    "Javanese Text",
+    "Leelawadee UI",
+    "Lucida Console",
+    "Lucida Sans Unicode",
+    "Malgun Gothic",
+    "Marlett",
+    "Microsoft Himalaya",
+    "Microsoft JhengHei",
+    "Microsoft New Tai Lue",
+    "Microsoft PhagsPa",
+    "Microsoft Sans Serif",
+    "Microsoft Tai Le",
+    "Microsoft YaHei",
+    "Microsoft Yi Baiti",
+    "MingLiU-ExtB",
+    "Mongolian Baiti",
+    "MS Gothic",
+    "MV Boli",
+    "Myanmar Text",
+    "Nirmala UI",
+    "Palatino Linotype",
+    "Segoe MDL2 Assets",
+    "Segoe Print",
+    "Segoe Script",
+    "Segoe UI",
+    "Segoe UI Emoji",
+    "Segoe UI Historic",
+    "Segoe UI Symbol",
+    "SimSun",
+    "Sitka",
+    "Sylfaen",
+    "Symbol",
+    "Tahoma",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+    "Webdings",
+    "Wingdings",
+    "Yu Gothic",
+];
+
+/// The fonts of a default macOS install.
+pub static FONTS_MAC: &[&str] = &[
+    "American Typewriter",
+    "Andale Mono",
+    "Apple Color Emoji",
+    "Apple SD Gothic Neo",
+    "Arial",
+    "Arial Black",
+    "Arial Narrow",
+    "Arial Rounded MT Bold",
+    "Arial Unicode MS",
+    "Avenir",
+    "Avenir Next",
+    "Avenir Next Condensed",
+    "Baskerville",
+    "Big Caslon",
+    "Bodoni 72",
+    "Bradley Hand",
+    "Brush Script MT",
+    "Chalkboard",
+    "Chalkboard SE",
+    "Chalkduster",
+    "Charter",
+    "Cochin",
+    "Comic Sans MS",
+    "Copperplate",
+    "Courier",
+    "Courier New",
+    "Didot",
+    "DIN Alternate",
+    "DIN Condensed",
+    "Futura",
+    "Geneva",
+    "Georgia",
+    "Gill Sans",
+    "Helvetica",
+    "Helvetica Neue",
+    "Herculanum",
+    "Hiragino Sans",
+    "Hoefler Text",
+    "Impact",
+    "Lucida Grande",
+    "Luminari",
+    "Marker Felt",
+    "Menlo",
+    "Microsoft Sans Serif",
+    "Monaco",
+    "Noteworthy",
+    "Optima",
+    "Palatino",
+    "Papyrus",
+    "Phosphate",
+    "PingFang SC",
+    "Rockwell",
+    "Savoye LET",
+    "SignPainter",
+    "Skia",
+    "Snell Roundhand",
+    "Tahoma",
+    "Times",
+    "Times New Roman",
+    "Trattatello",
+    "Trebuchet MS",
+    "Verdana",
+    "Zapfino",
+];
+
+/// The fonts of a default Ubuntu desktop install.
+pub static FONTS_LINUX: &[&str] = &[
+    "Cantarell",
+    "DejaVu Sans",
+    "DejaVu Sans Mono",
+    "DejaVu Serif",
+    "Droid Sans Fallback",
+    "FreeMono",
+    "FreeSans",
+    "FreeSerif",
+    "Liberation Mono",
+    "Liberation Sans",
+    "Liberation Sans Narrow",
+    "Liberation Serif",
+    "Noto Color Emoji",
+    "Noto Mono",
+    "Noto Sans",
+    "Noto Sans CJK SC",
+    "Noto Serif",
+    "Ubuntu",
+    "Ubuntu Condensed",
+    "Ubuntu Mono",
+];
+
+/// The fonts of ChromeOS.
+pub static FONTS_CHROME_OS: &[&str] = &[
+    "Arimo",
+    "Cousine",
+    "Noto Color Emoji",
+    "Noto Sans",
+    "Noto Sans CJK SC",
+    "Noto Serif",
+    "Roboto",
+    "Tinos",
+];
+
+/// The fonts of Android.
+pub static FONTS_ANDROID: &[&str] = &[
+    "Carrois Gothic SC",
+    "Coming Soon",
+    "Cutive Mono",
+    "Dancing Script",
+    "Droid Sans Mono",
+    "Noto Color Emoji",
+    "Noto Sans",
+    "Noto Sans CJK SC",
+    "Noto Serif",
+    "Roboto",
+    "Roboto Condensed",
+];
+
+/// The fonts of iOS and iPadOS.
+pub static FONTS_IOS: &[&str] = &[
+    "Apple Color Emoji",
+    "Arial",
+    "Avenir",
+    "Avenir Next",
+    "Baskerville",
+    "Chalkboard SE",
+    "Copperplate",
+    "Courier",
+    "Courier New",
+    "Didot",
+    "Futura",
+    "Georgia",
+    "Gill Sans",
+    "Helvetica",
+    "Helvetica Neue",
+    "Hiragino Sans",
+    "Kailasa",
+    "Marker Felt",
+    "Menlo",
+    "Noteworthy",
+    "Optima",
+    "Palatino",
+    "PingFang SC",
+    "Savoye LET",
+    "Snell Roundhand",
+    "Thonburi",
+    "Times New Roman",
+    "Trebuchet MS",
+    "Verdana",
+    "Zapfino",
+];
+
+/// The installed fonts for the os. Empty for an unknown os.
+pub fn fonts_for_os(os: AgentOs) -> &'static [&'static str] {
+    match os {
+        AgentOs::Windows => FONTS_WINDOWS,
+        AgentOs::Mac => FONTS_MAC,
+        AgentOs::Linux => FONTS_LINUX,
+        AgentOs::ChromeOS => FONTS_CHROME_OS,
+        AgentOs::Android => FONTS_ANDROID,
+        AgentOs::IPhone | AgentOs::IPad => FONTS_IOS,
+        AgentOs::Unknown => &[],
+    }
+}
@@ -1,12 +1,23 @@
 pub use super::gpu_android::GPU_PROFILES_ANDROID;
+pub use super::gpu_chromeos::GPU_PROFILES_CHROMEOS;
+pub use super::gpu_ipad::GPU_PROFILES_IPAD;
 pub use super::gpu_iphone::GPU_PROFILES_IPHONE;
 pub use super::gpu_linux::GPU_PROFILES_LINUX;
 pub use super::gpu_mac::GPU_PROFILES_MAC;
-pub use super::gpu_profile::GpuProfile;
+pub use super::gpu_profile::{GpuProfile, DEFAULT_GPU_WEIGHT};
+pub use super::gpu_query::GpuQuery;
 pub use super::gpu_windows::GPU_PROFILES_WINDOWS;
+pub use super::webgl::WebGlCapabilities;
 
+use super::gpu_limits::LIMITS_VULKAN_INTEGRATED;
+use super::webgl::WEBGL_OPENGL_MESA;
+use super::webgpu::WEBGPU_FEATURES_DESKTOP;
 use crate::AgentOs;
+use rand::distr::{weighted::WeightedIndex, Distribution};
 use rand::prelude::IndexedRandom;
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
 
 /// Fallback GPU profile used when no valid match is found.
 pub static FALLBACK_GPU_PROFILE: GpuProfile = GpuProfile {
@@ -16,28 +27,346 @@ pub static FALLBACK_GPU_PROFILE: GpuProfile = GpuProfile {
     webgpu_architecture: "",
     canvas_format: "rgba8unorm",
     hardware_concurrency: 10,
+    webgl: WEBGL_OPENGL_MESA,
+    webgpu_features: WEBGPU_FEATURES_DESKTOP,
+    limits: LIMITS_VULKAN_INTEGRATED,
+    weight: DEFAULT_GPU_WEIGHT,
 };
 
 /// Select a random GPU profile.
 pub fn select_random_gpu_profile(os: crate::AgentOs) -> &'static GpuProfile {
+    select_random_gpu_profile_rng(os, &mut rand::rng())
+}
+
+/// Select a random GPU profile using the provided rng. Profiles are picked by their weight.
+pub fn select_random_gpu_profile_rng<R: Rng>(
+    os: crate::AgentOs,
+    rng: &mut R,
+) -> &'static GpuProfile {
+    select_weighted_gpu_profile_rng(gpu_profiles_for_os(os), None, rng)
+        .unwrap_or(&FALLBACK_GPU_PROFILE)
+}
+
+/// Select a random GPU profile with custom weights.
+pub fn select_random_gpu_profile_with_weights(
+    os: crate::AgentOs,
+    weights: &GpuWeights,
+) -> &'static GpuProfile {
+    select_random_gpu_profile_with_weights_rng(os, weights, &mut rand::rng())
+}
+
+/// Select a random GPU profile with custom weights using the provided rng.
+pub fn select_random_gpu_profile_with_weights_rng<R: Rng>(
+    os: crate::AgentOs,
+    weights: &GpuWeights,
+    rng: &mut R,
+) -> &'static GpuProfile {
+    select_weighted_gpu_profile_rng(gpu_profiles_for_os(os), Some(weights), rng)
+        .unwrap_or(&FALLBACK_GPU_PROFILE)
+}
+
+/// Select a profile from the list by weight. The custom weights take precedence over the
+/// profile weights. Falls back to a uniform pick when every weight is zero.
+pub fn select_weighted_gpu_profile_rng<'a, R: Rng>(
+    profiles: &[&'a GpuProfile],
+    weights: Option<&GpuWeights>,
+    rng: &mut R,
+) -> Option<&'a GpuProfile> {
+    let weight = |profile: &GpuProfile| match weights {
+        Some(weights) => weights.weight(profile),
+        _ => profile.weight,
+    };
+
+    match WeightedIndex::new(profiles.iter().map(|profile| weight(profile))) {
+        Ok(dist) => profiles.get(dist.sample(rng)).copied(),
+        _ => profiles.choose(rng).copied(),
+    }
+}
+
+/// Custom selection weights keyed by the webgl renderer. Profiles not listed keep their weight.
+///
+/// Deserializes from a map e.g. `{"ANGLE (Apple, ANGLE Metal Renderer: Apple M1, Unspecified Version)": 60}`
+/// so the weights can live in a config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct GpuWeights {
+    /// The weight per webgl renderer.
+    pub weights: std::collections::HashMap<String, u32>,
+}
+
+impl GpuWeights {
+    /// Set the weight of the webgl renderer.
+    pub fn with_weight(mut self, webgl_renderer: &str, weight: u32) -> Self {
+        self.weights.insert(webgl_renderer.into(), weight);
+        self
+    }
+
+    /// The weight of the profile.
+    pub fn weight(&self, profile: &GpuProfile) -> u32 {
+        self.weights
+            .get(profile.webgl_renderer)
+            .copied()
+            .unwrap_or(profile.weight)
+    }
+}
+
+lazy_static::lazy_static! {
+    /// The shipped profiles of each table as references.
+    static ref BUILTIN_GPU_PROFILES: Vec<(&'static [GpuProfile], Vec<&'static GpuProfile>)> = [
+        GPU_PROFILES_MAC,
+        GPU_PROFILES_IPAD,
+        GPU_PROFILES_IPHONE,
+        GPU_PROFILES_WINDOWS,
+        GPU_PROFILES_LINUX,
+        GPU_PROFILES_CHROMEOS,
+        GPU_PROFILES_ANDROID,
+    ]
+    .into_iter()
+    .map(|table| (table, table.iter().collect()))
+    .collect();
+    /// The profiles of each table with the registered profiles appended. Each profile is leaked
+    /// once and a registration leaks a new list of references, so the lists handed out stay valid.
+    static ref CUSTOM_GPU_PROFILES: RwLock<Vec<(&'static [GpuProfile], &'static [&'static GpuProfile])>> =
+        Default::default();
+}
+
+/// Set once a custom profile is registered, the shipped profiles are used without a lock until then.
+static CUSTOM_GPU_PROFILES_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// The GPU profiles shipped for the os.
+pub fn builtin_gpu_profiles_for_os(os: crate::AgentOs) -> &'static [GpuProfile] {
     match os {
-        AgentOs::Mac => GPU_PROFILES_MAC
-            .choose(&mut rand::rng())
-            .unwrap_or(&FALLBACK_GPU_PROFILE),
-        AgentOs::IPad => GPU_PROFILES_IPHONE
-            .choose(&mut rand::rng())
-            .unwrap_or(&FALLBACK_GPU_PROFILE),
-        AgentOs::IPhone => GPU_PROFILES_IPHONE
-            .choose(&mut rand::rng())
-            .unwrap_or(&FALLBACK_GPU_PROFILE),
-        AgentOs::Windows => GPU_PROFILES_WINDOWS
-            .choose(&mut rand::rng())
-            .unwrap_or(&FALLBACK_GPU_PROFILE),
-        AgentOs::Linux | AgentOs::Unknown | AgentOs::ChromeOS => GPU_PROFILES_LINUX
-            .choose(&mut rand::rng())
-            .unwrap_or(&FALLBACK_GPU_PROFILE),
-        AgentOs::Android => GPU_PROFILES_ANDROID
-            .choose(&mut rand::rng())
-            .unwrap_or(&FALLBACK_GPU_PROFILE),
+        AgentOs::Mac => GPU_PROFILES_MAC,
+        AgentOs::IPad => GPU_PROFILES_IPAD,
+        AgentOs::IPhone => GPU_PROFILES_IPHONE,
+        AgentOs::Windows => GPU_PROFILES_WINDOWS,
+        AgentOs::Linux | AgentOs::Unknown => GPU_PROFILES_LINUX,
+        AgentOs::ChromeOS => GPU_PROFILES_CHROMEOS,
+        AgentOs::Android => GPU_PROFILES_ANDROID,
+    }
+}
+
+/// The shipped profiles of the table as references.
+fn builtin_gpu_profile_refs(builtin: &'static [GpuProfile]) -> &'static [&'static GpuProfile] {
+    BUILTIN_GPU_PROFILES
+        .iter()
+        .find(|(table, _)| std::ptr::eq(*table, builtin))
+        .map(|(_, profiles)| profiles.as_slice())
+        .unwrap_or_default()
+}
+
+/// The GPU profiles of the os, the shipped profiles followed by the registered profiles.
+pub fn gpu_profiles_for_os(os: crate::AgentOs) -> &'static [&'static GpuProfile] {
+    let builtin = builtin_gpu_profiles_for_os(os);
+
+    if !CUSTOM_GPU_PROFILES_REGISTERED.load(Ordering::Acquire) {
+        return builtin_gpu_profile_refs(builtin);
+    }
+
+    CUSTOM_GPU_PROFILES
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(table, _)| std::ptr::eq(*table, builtin))
+        .map(|(_, profiles)| *profiles)
+        .unwrap_or_else(|| builtin_gpu_profile_refs(builtin))
+}
+
+/// Register a custom GPU profile for the os. It is used by the random selection, queries and
+/// validation like a shipped profile. Registering an existing profile returns the existing one.
+pub fn register_gpu_profile(os: crate::AgentOs, profile: GpuProfile) -> &'static GpuProfile {
+    let builtin = builtin_gpu_profiles_for_os(os);
+
+    if let Some(existing) = builtin.iter().find(|p| **p == profile) {
+        return existing;
+    }
+
+    let mut tables = CUSTOM_GPU_PROFILES
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let index = match tables
+        .iter()
+        .position(|(table, _)| std::ptr::eq(*table, builtin))
+    {
+        Some(index) => index,
+        _ => {
+            tables.push((builtin, builtin_gpu_profile_refs(builtin)));
+            tables.len() - 1
+        }
+    };
+
+    let profiles = tables[index].1;
+
+    if let Some(existing) = profiles[builtin.len()..].iter().find(|p| ***p == profile) {
+        return existing;
+    }
+
+    let profile: &'static GpuProfile = Box::leak(Box::new(profile));
+    let mut next = profiles.to_vec();
+    next.push(profile);
+    tables[index].1 = Box::leak(next.into_boxed_slice());
+    CUSTOM_GPU_PROFILES_REGISTERED.store(true, Ordering::Release);
+
+    profile
+}
+
+/// Register custom GPU profiles for the os. Each new profile is leaked once to get a static reference.
+pub fn register_gpu_profiles<I: IntoIterator<Item = GpuProfile>>(
+    os: crate::AgentOs,
+    profiles: I,
+) -> Vec<&'static GpuProfile> {
+    profiles
+        .into_iter()
+        .map(|profile| register_gpu_profile(os, profile))
+        .collect()
+}
+
+/// A custom GPU profile with its os, e.g. an entry of a JSON or TOML profile file.
+///
+/// The os sits next to the profile fields: `{"os": "Windows", "webgl_vendor": "...", ...}`.
+/// Missing WebGL capabilities, WebGPU features, limits and weight are derived from the renderer
+/// and adapter like the shipped profiles.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CustomGpuProfile {
+    /// The os the profile is used for.
+    pub os: AgentOs,
+    /// The gpu profile.
+    #[serde(flatten)]
+    pub profile: GpuProfile,
+}
+
+#[cfg(feature = "serde")]
+impl CustomGpuProfile {
+    /// Register the profile for its os.
+    pub fn register(self) -> &'static GpuProfile {
+        register_gpu_profile(self.os, self.profile)
+    }
+}
+
+/// Register the custom GPU profiles, e.g. the entries read from a profile file at startup.
+#[cfg(feature = "serde")]
+pub fn register_custom_gpu_profiles<I: IntoIterator<Item = CustomGpuProfile>>(
+    profiles: I,
+) -> Vec<&'static GpuProfile> {
+    profiles
+        .into_iter()
+        .map(CustomGpuProfile::register)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gpu_profiles_are_picked_by_weight() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let m1 = GPU_PROFILES_MAC[0].webgl_renderer;
+        let ultra = GPU_PROFILES_MAC[3].webgl_renderer;
+        let (mut m1_count, mut ultra_count) = (0, 0);
+
+        for _ in 0..2000 {
+            let renderer = select_random_gpu_profile_rng(AgentOs::Mac, &mut rng).webgl_renderer;
+            m1_count += (renderer == m1) as u32;
+            ultra_count += (renderer == ultra) as u32;
+        }
+        assert!(m1_count > ultra_count * 5);
+
+        let weights = GPU_PROFILES_MAC
+            .iter()
+            .fold(GpuWeights::default(), |w, p| {
+                w.with_weight(p.webgl_renderer, 0)
+            })
+            .with_weight(ultra, 1);
+        for _ in 0..20 {
+            assert_eq!(
+                select_random_gpu_profile_with_weights_rng(AgentOs::Mac, &weights, &mut rng)
+                    .webgl_renderer,
+                ultra
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn custom_gpu_profiles_load_from_json() {
+        use crate::EmulationConfiguration;
+
+        let json = r#"[{
+            "os": "Linux",
+            "webgl_vendor": "Google Inc. (Custom)",
+            "webgl_renderer": "ANGLE (Custom, Custom GPU 9000 (0x00009000), Vulkan 1.3)",
+            "webgpu_vendor": "custom",
+            "webgpu_architecture": "custom-1",
+            "canvas_format": "rgba8unorm",
+            "hardware_concurrency": 24,
+            "weight": 0
+        }]"#;
+        let profiles: Vec<CustomGpuProfile> = serde_json::from_str(json).expect("profiles");
+        let registered = register_custom_gpu_profiles(profiles.clone());
+        let profile = registered[0];
+
+        assert_eq!(profile.webgl_renderer, profiles[0].profile.webgl_renderer);
+        assert_eq!(register_custom_gpu_profiles(profiles)[0], profile);
+        assert!(std::ptr::eq(
+            register_gpu_profile(AgentOs::Linux, profile.clone()),
+            profile
+        ));
+        let listed_once = |profile: &GpuProfile| {
+            gpu_profiles_for_os(AgentOs::Linux)
+                .iter()
+                .filter(|p| std::ptr::eq(**p, profile))
+                .count()
+                == 1
+        };
+        assert!(listed_once(profile));
+
+        let listed = r#"{
+            "webgl_vendor": "Google Inc. (Custom)",
+            "webgl_renderer": "ANGLE (Custom, Custom GPU 9100 (0x00009100), Vulkan 1.3)",
+            "webgpu_vendor": "custom",
+            "webgpu_architecture": "custom-2",
+            "canvas_format": "rgba8unorm",
+            "hardware_concurrency": 24,
+            "webgpu_features": ["custom-feature-a", "custom-feature-b"],
+            "weight": 0
+        }"#;
+        let first: GpuProfile = serde_json::from_str(listed).expect("profile");
+        let second: GpuProfile = serde_json::from_str(listed).expect("profile");
+        assert!(std::ptr::eq(first.webgpu_features, second.webgpu_features));
+
+        let registered = register_gpu_profile(AgentOs::Linux, first);
+        for _ in 0..3 {
+            assert!(std::ptr::eq(
+                register_gpu_profile(AgentOs::Linux, second.clone()),
+                registered
+            ));
+        }
+        assert!(listed_once(profile) && listed_once(registered));
+        assert!(gpu_profiles_for_os(AgentOs::Linux)
+            .iter()
+            .zip(GPU_PROFILES_LINUX)
+            .all(|(listed, builtin)| std::ptr::eq(*listed, builtin)));
+        assert_eq!(
+            GpuQuery::new().with_renderer("Custom GPU 9000").pick(),
+            Some(profile)
+        );
+
+        let ua = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let config = EmulationConfiguration::setup_defaults(ua);
+        let script =
+            crate::emulate_with_profile(ua, &config, &None, &None, profile).expect("script");
+        assert!(script.contains("Custom GPU 9000"));
+
+        let owned = profile.clone();
+        assert!(crate::emulate_with_profile(ua, &config, &None, &None, &owned).is_some());
     }
 }
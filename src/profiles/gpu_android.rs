@@ -1,4 +1,7 @@
+use super::gpu_limits::{LIMITS_ADRENO, LIMITS_MALI};
 use super::gpu_profile::GpuProfile;
+use super::webgl::{WEBGL_ADRENO, WEBGL_MALI};
+use super::webgpu::WEBGPU_FEATURES_MOBILE;
 
 pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
     // Google Pixel 6 / 6a (ARM Mali-G78)
@@ -9,6 +12,10 @@ pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
         webgpu_architecture: "opengl",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_MALI,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_MALI,
+        weight: 15,
     },
     // Samsung Galaxy S22 (Adreno 730)
     GpuProfile {
@@ -18,6 +25,10 @@ pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
         webgpu_architecture: "opengl",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_ADRENO,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_ADRENO,
+        weight: 15,
     },
     // Samsung Galaxy A52 (Adreno 618)
     GpuProfile {
@@ -27,6 +38,10 @@ pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
         webgpu_architecture: "opengl",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_ADRENO,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_ADRENO,
+        weight: 20,
     },
     // Xiaomi Redmi Note 11 (ARM Mali-G57)
     GpuProfile {
@@ -36,6 +51,10 @@ pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
         webgpu_architecture: "mali-g57",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_MALI,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_MALI,
+        weight: 20,
     },
     // OnePlus 9 Pro (Adreno 660)
     GpuProfile {
@@ -45,6 +64,10 @@ pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
         webgpu_architecture: "adreno-660",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_ADRENO,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_ADRENO,
+        weight: 15,
     },
     // Google Pixel 5 (Adreno 620)
     GpuProfile {
@@ -54,5 +77,9 @@ pub static GPU_PROFILES_ANDROID: &[GpuProfile] = &[
         webgpu_architecture: "adreno-620",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_ADRENO,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_ADRENO,
+        weight: 15,
     },
 ];
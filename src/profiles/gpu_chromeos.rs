@@ -0,0 +1,112 @@
+use super::gpu_limits::{LIMITS_ADRENO, LIMITS_MALI, LIMITS_VULKAN_INTEGRATED};
+use super::gpu_profile::GpuProfile;
+use super::webgl::{WEBGL_ADRENO, WEBGL_MALI, WEBGL_OPENGL_MESA};
+use super::webgpu::{WEBGPU_FEATURES_DESKTOP, WEBGPU_FEATURES_MOBILE};
+
+pub static GPU_PROFILES_CHROMEOS: &[GpuProfile] = &[
+    // Intel Celeron N4500 / N5100 (Jasper Lake)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (Intel)",
+        webgl_renderer: "ANGLE (Intel, Mesa Intel(R) UHD Graphics (JSL), OpenGL ES 3.2)",
+        webgpu_vendor: "intel",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 30,
+    },
+    // Intel Celeron N4020 / N4120 (Gemini Lake)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (Intel)",
+        webgl_renderer: "ANGLE (Intel, Mesa Intel(R) UHD Graphics 600 (GLK 2), OpenGL ES 3.2)",
+        webgpu_vendor: "intel",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 2,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 20,
+    },
+    // Intel N100 / N200 (Alder Lake-N)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (Intel)",
+        webgl_renderer: "ANGLE (Intel, Mesa Intel(R) Graphics (ADL-N), OpenGL ES 3.2)",
+        webgpu_vendor: "intel",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 18,
+    },
+    // Intel Core i5-1135G7 Chromebook Plus (Tiger Lake)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (Intel)",
+        webgl_renderer: "ANGLE (Intel, Mesa Intel(R) Xe Graphics (TGL GT2), OpenGL ES 3.2)",
+        webgpu_vendor: "intel",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 8,
+    },
+    // AMD Ryzen 5 3500C (Picasso)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (AMD)",
+        webgl_renderer:
+            "ANGLE (AMD, AMD Radeon Vega 8 Graphics (radeonsi, raven, LLVM 17.0.0), OpenGL ES 3.2)",
+        webgpu_vendor: "amd",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 3,
+    },
+    // MediaTek Kompanio 520 (MT8186, Mali-G52)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (ARM)",
+        webgl_renderer: "ANGLE (ARM, Mali-G52 MC2, OpenGL ES 3.2)",
+        webgpu_vendor: "arm",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_MALI,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_MALI,
+        weight: 14,
+    },
+    // MediaTek Kompanio 500 (MT8183, Mali-G72)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (ARM)",
+        webgl_renderer: "ANGLE (ARM, Mali-G72 MP3, OpenGL ES 3.2)",
+        webgpu_vendor: "arm",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_MALI,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_MALI,
+        weight: 8,
+    },
+    // Qualcomm Snapdragon 7c Gen 2 (Adreno 618)
+    GpuProfile {
+        webgl_vendor: "Google Inc. (Qualcomm)",
+        webgl_renderer: "ANGLE (Qualcomm, Adreno (TM) 618, OpenGL ES 3.2)",
+        webgpu_vendor: "qualcomm",
+        webgpu_architecture: "vulkan",
+        canvas_format: "rgba8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_ADRENO,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_ADRENO,
+        weight: 4,
+    },
+];
@@ -0,0 +1,124 @@
+use super::gpu_limits::LIMITS_APPLE_MOBILE;
+use super::gpu_profile::GpuProfile;
+use super::webgl::WEBGL_APPLE_MOBILE;
+use super::webgpu::WEBGPU_FEATURES_APPLE;
+
+pub static GPU_PROFILES_IPAD: &[GpuProfile] = &[
+    // iPad Pro 11-inch / 13-inch (Apple M4)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple M4 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-3",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 10,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 10,
+    },
+    // iPad Air 11-inch / 13-inch (Apple M3)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple M3 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-3",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 10,
+    },
+    // iPad Air 11-inch / 13-inch and iPad Pro 6th gen (Apple M2)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple M2 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-3",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 12,
+    },
+    // iPad Air 5th gen and iPad Pro 5th gen (Apple M1)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple M1 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-3",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 8,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 14,
+    },
+    // iPad 11th gen (Apple A16)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple A16 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-3",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 20,
+    },
+    // iPad 10th gen (Apple A14 Bionic)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple A14 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-2",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 20,
+    },
+    // iPad mini 7th gen (Apple A17 Pro)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple A17 Pro GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-3",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 8,
+    },
+    // iPad mini 6th gen (Apple A15 Bionic)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple A15 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-2",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 8,
+    },
+    // iPad 9th gen (Apple A13 Bionic)
+    GpuProfile {
+        webgl_vendor: "Apple Inc.",
+        webgl_renderer: "Apple A13 GPU",
+        webgpu_vendor: "apple",
+        webgpu_architecture: "metal-2",
+        canvas_format: "bgra8unorm",
+        hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 10,
+    },
+];
@@ -1,4 +1,7 @@
+use super::gpu_limits::LIMITS_APPLE_MOBILE;
 use super::gpu_profile::GpuProfile;
+use super::webgl::WEBGL_APPLE_MOBILE;
+use super::webgpu::WEBGPU_FEATURES_APPLE;
 
 pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
     // iPhone 13 / 13 Pro / 13 Pro Max (Apple A15 Bionic)
@@ -9,6 +12,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-2",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 20,
     },
     // iPhone 14 / 14 Plus (Apple A15 Bionic, reused)
     GpuProfile {
@@ -18,6 +25,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-2",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 20,
     },
     // iPhone 14 Pro / Pro Max (Apple A16 Bionic)
     GpuProfile {
@@ -27,6 +38,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 18,
     },
     // iPhone 15 / 15 Plus (Apple A16 Bionic)
     GpuProfile {
@@ -36,6 +51,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 18,
     },
     // iPhone 15 Pro / Pro Max (Apple A17 Pro)
     GpuProfile {
@@ -45,6 +64,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 15,
     },
     // iPhone 16 / 16 Plus (Apple A18 Bionic)
     GpuProfile {
@@ -54,6 +77,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 15,
     },
     // iPhone 16 Pro / 16 Pro Max (Apple A18 Pro)
     GpuProfile {
@@ -63,6 +90,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 12,
     },
     // iPhone 17 (Apple A19 Bionic) — projected spoof
     GpuProfile {
@@ -72,6 +103,10 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 8,
     },
     // iPhone 17 Pro / 17 Pro Max (Apple A19 Pro) — projected spoof
     GpuProfile {
@@ -81,5 +116,9 @@ pub static GPU_PROFILES_IPHONE: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_APPLE_MOBILE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_MOBILE,
+        weight: 6,
     },
 ];
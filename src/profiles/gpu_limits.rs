@@ -1,10 +1,13 @@
+use super::gpu_profile::GpuProfile;
+use super::webgpu::is_fallback_adapter;
 use crate::configs::AgentOs;
+use rand::Rng;
 
 /// Representing WebGPU hardware limit values used to spoof `navigator.gpu.requestAdapter().limits`.
 ///
 /// These limits help simulate realistic platform-specific GPU profiles (e.g., macOS Metal vs. NVIDIA on Linux).
 /// The values are injected into the adapter returned by WebGPU in spoofed environments.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GpuLimits {
     /// Maximum dimension for 1D textures.
@@ -71,6 +74,131 @@ pub struct GpuLimits {
     pub max_compute_workgroups_per_dimension: u64,
 }
 
+/// Chrome on D3D with an NVIDIA or AMD discrete gpu.
+pub const LIMITS_D3D_DISCRETE: GpuLimits = GpuLimits {
+    max_texture_dimension_1d: 16384,
+    max_texture_dimension_2d: 16384,
+    max_texture_dimension_3d: 2048,
+    max_texture_array_layers: 2048,
+    max_bind_groups: 4,
+    max_bind_groups_plus_vertex_buffers: 24,
+    max_bindings_per_bind_group: 1000,
+    max_dynamic_uniform_buffers_per_pipeline_layout: 10,
+    max_dynamic_storage_buffers_per_pipeline_layout: 8,
+    max_sampled_textures_per_shader_stage: 16,
+    max_samplers_per_shader_stage: 16,
+    max_storage_buffers_per_shader_stage: 10,
+    max_storage_textures_per_shader_stage: 8,
+    max_uniform_buffers_per_shader_stage: 12,
+    max_uniform_buffer_binding_size: 65536,
+    max_storage_buffer_binding_size: 2147483644,
+    min_uniform_buffer_offset_alignment: 256,
+    min_storage_buffer_offset_alignment: 256,
+    max_vertex_buffers: 8,
+    max_buffer_size: 4294967296,
+    max_vertex_attributes: 30,
+    max_vertex_buffer_array_stride: 2048,
+    max_inter_stage_shader_variables: 28,
+    max_color_attachments: 8,
+    max_color_attachment_bytes_per_sample: 128,
+    max_compute_workgroup_storage_size: 32768,
+    max_compute_invocations_per_workgroup: 1024,
+    max_compute_workgroup_size_x: 1024,
+    max_compute_workgroup_size_y: 1024,
+    max_compute_workgroup_size_z: 64,
+    max_compute_workgroups_per_dimension: 65535,
+};
+
+/// Chrome on D3D with an integrated gpu sharing the system memory.
+pub const LIMITS_D3D_INTEGRATED: GpuLimits = GpuLimits {
+    max_storage_buffer_binding_size: 1073741824,
+    max_buffer_size: 2147483648,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Chrome on Vulkan with an NVIDIA or AMD discrete gpu.
+pub const LIMITS_VULKAN_DISCRETE: GpuLimits = GpuLimits {
+    max_storage_buffer_binding_size: 4294967292,
+    min_storage_buffer_offset_alignment: 32,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Chrome on Vulkan with an integrated gpu.
+pub const LIMITS_VULKAN_INTEGRATED: GpuLimits = GpuLimits {
+    max_storage_buffer_binding_size: 2147483644,
+    min_storage_buffer_offset_alignment: 64,
+    max_buffer_size: 2147483648,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Chrome on Metal with Apple silicon.
+pub const LIMITS_APPLE_SILICON: GpuLimits = GpuLimits {
+    max_storage_buffer_binding_size: 4294967292,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Chrome on Metal with an Intel or AMD gpu.
+pub const LIMITS_MAC_LEGACY: GpuLimits = GpuLimits {
+    max_storage_buffer_binding_size: 2147483644,
+    max_buffer_size: 2147483648,
+    max_compute_workgroup_storage_size: 16384,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Apple A-series gpus of iPhones and iPads.
+pub const LIMITS_APPLE_MOBILE: GpuLimits = GpuLimits {
+    max_storage_buffer_binding_size: 1073741824,
+    max_buffer_size: 1073741824,
+    max_vertex_attributes: 31,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Chrome on Android with a Qualcomm Adreno gpu.
+pub const LIMITS_ADRENO: GpuLimits = GpuLimits {
+    max_texture_dimension_1d: 8192,
+    max_texture_dimension_2d: 8192,
+    max_texture_array_layers: 256,
+    max_dynamic_uniform_buffers_per_pipeline_layout: 8,
+    max_dynamic_storage_buffers_per_pipeline_layout: 4,
+    max_storage_buffers_per_shader_stage: 8,
+    max_storage_textures_per_shader_stage: 4,
+    max_storage_buffer_binding_size: 134217728,
+    min_storage_buffer_offset_alignment: 64,
+    max_buffer_size: 268435456,
+    max_vertex_attributes: 16,
+    max_inter_stage_shader_variables: 16,
+    max_color_attachment_bytes_per_sample: 32,
+    max_compute_workgroup_storage_size: 32768,
+    ..LIMITS_D3D_DISCRETE
+};
+
+/// Chrome on Android with an ARM Mali gpu.
+pub const LIMITS_MALI: GpuLimits = GpuLimits {
+    max_compute_workgroup_storage_size: 16384,
+    max_compute_invocations_per_workgroup: 512,
+    max_compute_workgroup_size_x: 512,
+    max_compute_workgroup_size_y: 512,
+    min_storage_buffer_offset_alignment: 256,
+    ..LIMITS_ADRENO
+};
+
+/// Chrome on the software SwiftShader adapter.
+pub const LIMITS_SWIFTSHADER: GpuLimits = GpuLimits {
+    max_texture_dimension_1d: 8192,
+    max_texture_dimension_2d: 8192,
+    max_texture_array_layers: 256,
+    max_storage_buffer_binding_size: 134217728,
+    max_buffer_size: 268435456,
+    max_vertex_attributes: 16,
+    max_inter_stage_shader_variables: 16,
+    max_color_attachment_bytes_per_sample: 32,
+    max_compute_workgroup_storage_size: 16384,
+    max_compute_invocations_per_workgroup: 256,
+    max_compute_workgroup_size_x: 256,
+    max_compute_workgroup_size_y: 256,
+    ..LIMITS_D3D_DISCRETE
+};
+
 impl Default for GpuLimits {
     /// standard mac m1 max gpu.
     fn default() -> Self {
@@ -112,13 +240,19 @@ impl Default for GpuLimits {
 
 impl GpuLimits {
     /// Returns a slightly jittered version of the limits, padded based on hardware_concurrency.
+    /// Real adapters report exact limits so the jitter is only applied on request.
     pub fn with_variation(&self, hardware_concurrency: usize) -> Self {
+        self.with_variation_rng(hardware_concurrency, &mut rand::rng())
+    }
+
+    /// Returns a slightly jittered version of the limits using the provided rng.
+    pub fn with_variation_rng<R: Rng>(&self, hardware_concurrency: usize, rng: &mut R) -> Self {
         // Normalized range multiplier (1x for 2–4 cores, up to ~1.5x for 16+)
         let scale = (hardware_concurrency as f32 / 8.0).clamp(0.5, 1.5);
 
-        let bump = |base: u64, jitter: u64| {
+        let mut bump = |base: u64, jitter: u64| {
             let jitter_scaled = ((jitter as f32) * scale) as u64;
-            base + rand::random_range(0..=jitter_scaled)
+            base + rng.random_range(0..=jitter_scaled)
         };
 
         Self {
@@ -184,7 +318,73 @@ impl GpuLimits {
         }
     }
 
-    /// Get the GouLimit for the OS.
+    /// The limits a WebGPU device gets when no higher limit is requested.
+    pub fn webgpu_defaults() -> Self {
+        Self {
+            max_texture_dimension_1d: 8192,
+            max_texture_dimension_2d: 8192,
+            max_texture_dimension_3d: 2048,
+            max_texture_array_layers: 256,
+            max_bind_groups: 4,
+            max_bind_groups_plus_vertex_buffers: 24,
+            max_bindings_per_bind_group: 1000,
+            max_dynamic_uniform_buffers_per_pipeline_layout: 8,
+            max_dynamic_storage_buffers_per_pipeline_layout: 4,
+            max_sampled_textures_per_shader_stage: 16,
+            max_samplers_per_shader_stage: 16,
+            max_storage_buffers_per_shader_stage: 8,
+            max_storage_textures_per_shader_stage: 4,
+            max_uniform_buffers_per_shader_stage: 12,
+            max_uniform_buffer_binding_size: 65536,
+            max_storage_buffer_binding_size: 134217728,
+            min_uniform_buffer_offset_alignment: 256,
+            min_storage_buffer_offset_alignment: 256,
+            max_vertex_buffers: 8,
+            max_buffer_size: 268435456,
+            max_vertex_attributes: 16,
+            max_vertex_buffer_array_stride: 2048,
+            max_inter_stage_shader_variables: 16,
+            max_color_attachments: 8,
+            max_color_attachment_bytes_per_sample: 32,
+            max_compute_workgroup_storage_size: 16384,
+            max_compute_invocations_per_workgroup: 256,
+            max_compute_workgroup_size_x: 256,
+            max_compute_workgroup_size_y: 256,
+            max_compute_workgroup_size_z: 64,
+            max_compute_workgroups_per_dimension: 65535,
+        }
+    }
+
+    /// Get the limits matching the adapter vendor and architecture.
+    pub fn for_adapter(webgpu_vendor: &str, webgpu_architecture: &str) -> Self {
+        let vendor = webgpu_vendor.to_ascii_lowercase();
+        let architecture = webgpu_architecture.to_ascii_lowercase();
+        let discrete = matches!(vendor.as_str(), "nvidia" | "amd");
+
+        if vendor == "google" {
+            LIMITS_SWIFTSHADER
+        } else if architecture.starts_with("d3d") {
+            if discrete {
+                LIMITS_D3D_DISCRETE
+            } else {
+                LIMITS_D3D_INTEGRATED
+            }
+        } else if vendor == "apple" {
+            LIMITS_APPLE_SILICON
+        } else if vendor == "qualcomm" || architecture.starts_with("adreno") {
+            LIMITS_ADRENO
+        } else if vendor == "arm" || architecture.starts_with("mali") {
+            LIMITS_MALI
+        } else if architecture.is_empty() {
+            LIMITS_MAC_LEGACY
+        } else if discrete {
+            LIMITS_VULKAN_DISCRETE
+        } else {
+            LIMITS_VULKAN_INTEGRATED
+        }
+    }
+
+    /// Get the GouLimit for the OS. Prefer the exact limits of the gpu profile.
     pub fn for_os(os: AgentOs) -> Self {
         match os {
             AgentOs::Mac => Self {
@@ -276,7 +476,18 @@ pub fn build_gpu_request_adapter_script_from_limits(
         vendor, architecture, device, description
     );
 
-    let limits_str = format!(
+    let limits_str = limits_js(limits);
+
+    format!(
+        r#"(()=>{{try{{const g=navigator&&navigator.gpu;if(!g||typeof g.requestAdapter!=="function")return;const def=(o,m)=>Object.defineProperties(o,Object.fromEntries(Object.entries(m).map(([k,v])=>[k,{{value:v,enumerable:!0,configurable:!0}}]))),orig=g.requestAdapter.bind(g),I={{{info}}},M={{{limits_str}}};g.requestAdapter=async opts=>{{const a=await orig(opts);if(!a||!a.limits)return a;const lim=a.limits;try{{a.info&&def(a.info,I)}}catch(_){{}}try{{for(const k of Object.getOwnPropertyNames(lim))if(!(k in M))delete lim[k];def(lim,M)}}catch(_){{}}return a}}}}catch(_){{}}}})();"#,
+        info = info,
+        limits_str = limits_str
+    )
+}
+
+/// The limits as the body of a JS object literal.
+fn limits_js(limits: &GpuLimits) -> String {
+    format!(
         "maxTextureDimension1D:{},maxTextureDimension2D:{},maxTextureDimension3D:{},maxTextureArrayLayers:{},maxBindGroups:{},maxBindGroupsPlusVertexBuffers:{},maxBindingsPerBindGroup:{},maxDynamicUniformBuffersPerPipelineLayout:{},maxDynamicStorageBuffersPerPipelineLayout:{},maxSampledTexturesPerShaderStage:{},maxSamplersPerShaderStage:{},maxStorageBuffersPerShaderStage:{},maxStorageTexturesPerShaderStage:{},maxUniformBuffersPerShaderStage:{},maxUniformBufferBindingSize:{},maxStorageBufferBindingSize:{},minUniformBufferOffsetAlignment:{},minStorageBufferOffsetAlignment:{},maxVertexBuffers:{},maxBufferSize:{},maxVertexAttributes:{},maxVertexBufferArrayStride:{},maxInterStageShaderVariables:{},maxColorAttachments:{},maxColorAttachmentBytesPerSample:{},maxComputeWorkgroupStorageSize:{},maxComputeInvocationsPerWorkgroup:{},maxComputeWorkgroupSizeX:{},maxComputeWorkgroupSizeY:{},maxComputeWorkgroupSizeZ:{},maxComputeWorkgroupsPerDimension:{}",
         limits.max_texture_dimension_1d,
         limits.max_texture_dimension_2d,
@@ -309,11 +520,122 @@ pub fn build_gpu_request_adapter_script_from_limits(
         limits.max_compute_workgroup_size_y,
         limits.max_compute_workgroup_size_z,
         limits.max_compute_workgroups_per_dimension,
-    );
+    )
+}
+
+/// The WebGPU adapter and device model shared by the adapter scripts.
+///
+/// Defines `A()` building a fake adapter and `W(adapter)` patching a real one. Both answer the
+/// features, limits, info and `isFallbackAdapter` of the profile, and `requestDevice` validates
+/// the descriptor like Chrome and resolves a device with its own `features`, `limits`,
+/// `adapterInfo`, `queue` and `lost` promise.
+///
+/// The fake device of `A()` creates buffers, textures, shaders, layouts, pipelines and command
+/// encoders with the `GPU*` prototypes, but runs nothing: mapped ranges read back zeroed and
+/// submitted work completes at once.
+pub(crate) fn webgpu_model_script(
+    info: &str,
+    limits: &GpuLimits,
+    features: &[&str],
+    fallback: bool,
+) -> String {
+    let features = features
+        .iter()
+        .map(|f| format!("'{f}'"))
+        .collect::<Vec<_>>()
+        .join(",");
 
     format!(
-        r#"(()=>{{try{{const g=navigator&&navigator.gpu;if(!g||typeof g.requestAdapter!=="function")return;const def=(o,m)=>Object.defineProperties(o,Object.fromEntries(Object.entries(m).map(([k,v])=>[k,{{value:v,enumerable:!0,configurable:!0}}]))),orig=g.requestAdapter.bind(g),I={{{info}}},M={{{limits_str}}};g.requestAdapter=async opts=>{{const a=await orig(opts);if(!a||!a.limits)return a;const lim=a.limits;try{{a.info&&def(a.info,I)}}catch(_){{}}try{{for(const k of Object.getOwnPropertyNames(lim))if(!(k in M))delete lim[k];def(lim,M)}}catch(_){{}}return a}}}}catch(_){{}}}})();"#,
-        info = info,
-        limits_str = limits_str
+        r#"const I={{{info}}},M={{{limits}}},Z={{{defaults}}},F=[{features}],B={fallback},T=(n,d=Object.prototype)=>typeof self[n]==='function'?self[n].prototype:d,O=(p,v)=>{{const o=Object.create(p);for(const k in v)Object.defineProperty(o,k,{{value:v[k],enumerable:!0,configurable:!0,writable:k==='label'||k==='onuncapturederror'}});return o}},L=m=>O(T('GPUSupportedLimits'),m),N=()=>O(T('GPUAdapterInfo'),Object.assign({{}},I,{{isFallbackAdapter:B}})),X=(m,n)=>Promise.reject(typeof DOMException==='function'?new DOMException(m,n):new Error(m)),G=(n,d,v)=>O(T(n),Object.assign({{label:String(d&&d.label||'')}},v)),Q=m=>Object.fromEntries(m.split(' ').map(k=>[k,()=>{{}}])),K=(n,d)=>G(n,d,{{getBindGroupLayout:()=>G('GPUBindGroupLayout',{{}},{{}})}}),U=d=>{{if(!d||d.size===undefined||d.usage===undefined)throw new TypeError(`Failed to execute 'createBuffer' on 'GPUDevice': Failed to read the '${{d&&d.size!==undefined?'usage':'size'}}' property from 'GPUBufferDescriptor': Required member is undefined.`);let m=d.mappedAtCreation?'mapped':'unmapped';const b=G('GPUBuffer',d,{{size:+d.size,usage:+d.usage,getMappedRange:(o=0,z=d.size-o)=>new ArrayBuffer(z),mapAsync(){{m='mapped';return Promise.resolve()}},unmap(){{m='unmapped'}},destroy(){{m='unmapped'}}}});Object.defineProperty(b,'mapState',{{get:()=>m,enumerable:!0,configurable:!0}});return b}},V=d=>{{const s=d&&d.size||[],w=Array.isArray(s)?s:[s.width,s.height,s.depthOrArrayLayers];return G('GPUTexture',d,{{width:+w[0],height:+w[1]||1,depthOrArrayLayers:+w[2]||1,mipLevelCount:d.mipLevelCount||1,sampleCount:d.sampleCount||1,dimension:d.dimension||'2d',format:d.format,usage:d.usage,createView:v=>G('GPUTextureView',v,{{}}),destroy(){{}}}})}},E=d=>G('GPUCommandEncoder',d,Object.assign(Q('copyBufferToBuffer copyBufferToTexture copyTextureToBuffer copyTextureToTexture clearBuffer resolveQuerySet pushDebugGroup popDebugGroup insertDebugMarker'),{{beginComputePass:p=>G('GPUComputePassEncoder',p,Q('setPipeline setBindGroup dispatchWorkgroups dispatchWorkgroupsIndirect end pushDebugGroup popDebugGroup insertDebugMarker')),beginRenderPass:p=>G('GPURenderPassEncoder',p,Q('setPipeline setBindGroup setVertexBuffer setIndexBuffer draw drawIndexed drawIndirect drawIndexedIndirect setViewport setScissorRect setBlendConstant setStencilReference executeBundles beginOcclusionQuery endOcclusionQuery end pushDebugGroup popDebugGroup insertDebugMarker')),finish:p=>G('GPUCommandBuffer',p,{{}})}})),Y=s=>{{let r;const e=typeof EventTarget==='function'?new EventTarget():{{}},l=new Promise(x=>r=x),v=Object.assign({{}},s,{{queue:O(T('GPUQueue'),{{label:'',submit(){{}},onSubmittedWorkDone:()=>Promise.resolve(),writeBuffer(){{}},writeTexture(){{}},copyExternalImageToTexture(){{}}}}),lost:l,onuncapturederror:null,destroy(){{r(O(T('GPUDeviceLostInfo'),{{reason:'destroyed',message:'Device was destroyed.'}}))}},pushErrorScope(){{}},popErrorScope:()=>Promise.resolve(null),createBuffer:U,createTexture:V,createSampler:d=>G('GPUSampler',d,{{}}),createShaderModule:d=>G('GPUShaderModule',d,{{getCompilationInfo:()=>Promise.resolve(O(T('GPUCompilationInfo'),{{messages:[]}}))}}),createBindGroupLayout:d=>G('GPUBindGroupLayout',d,{{}}),createPipelineLayout:d=>G('GPUPipelineLayout',d,{{}}),createBindGroup:d=>G('GPUBindGroup',d,{{}}),createComputePipeline:d=>K('GPUComputePipeline',d),createComputePipelineAsync:d=>Promise.resolve(K('GPUComputePipeline',d)),createRenderPipeline:d=>K('GPURenderPipeline',d),createRenderPipelineAsync:d=>Promise.resolve(K('GPURenderPipeline',d)),createCommandEncoder:E,createRenderBundleEncoder:d=>G('GPURenderBundleEncoder',d,Object.assign(Q('setPipeline setBindGroup setVertexBuffer setIndexBuffer draw drawIndexed drawIndirect drawIndexedIndirect pushDebugGroup popDebugGroup insertDebugMarker'),{{finish:p=>G('GPURenderBundle',p,{{}})}})),createQuerySet:d=>G('GPUQuerySet',d,{{type:d.type,count:d.count,destroy(){{}}}}),importExternalTexture:d=>G('GPUExternalTexture',d,{{}})}});Object.setPrototypeOf(e,T('GPUDevice',Object.getPrototypeOf(e)));for(const k in v)Object.defineProperty(e,k,{{value:v[k],enumerable:!0,configurable:!0,writable:k==='label'||k==='onuncapturederror'}});return e}},R=(a,d,o)=>{{d=d||{{}};const f=[...(d.requiredFeatures||[])],l=d.requiredLimits||{{}},m=Object.assign({{}},Z);for(const k of f)if(!F.includes(k))return Promise.reject(new TypeError(`Failed to execute 'requestDevice' on 'GPUAdapter': Unsupported feature: ${{k}}`));for(const k in l){{const v=l[k],n=k.startsWith('min');if(v===undefined)continue;if(!(k in M))return X(`The limit "${{k}}" is not recognized.`,'OperationError');if(n?v<M[k]:v>M[k])return X(`Required limit (${{v}}) is ${{n?'lower':'greater'}} than the supported limit (${{M[k]}}).`,'OperationError');m[k]=n?Math.min(m[k],v):Math.max(m[k],v)}}if(F.includes('core-features-and-limits')&&!f.includes('core-features-and-limits'))f.push('core-features-and-limits');const s={{label:String(d.label||''),features:new Set(f),limits:L(m),adapterInfo:a.info}};return o?o(d).then(v=>{{for(const k in s)try{{Object.defineProperty(v,k,{{value:s[k],enumerable:!0,configurable:!0,writable:k==='label'}})}}catch(_){{}}return v}}):Promise.resolve(Y(s))}},A=()=>{{const a=O(T('GPUAdapter'),{{features:new Set(F),limits:L(M),info:N(),isFallbackAdapter:B,requestAdapterInfo:()=>Promise.resolve(a.info),requestDevice:d=>R(a,d)}});return a}},W=a=>{{if(!a)return a;const g=a.requestDevice,q={{}},h=a.features;try{{for(const k in M)q[k]=a.limits[k]}}catch(_){{}}const s={{features:new Set(F),limits:L(M),info:N(),isFallbackAdapter:B,requestAdapterInfo:()=>Promise.resolve(a.info),requestDevice:d=>R(a,d,x=>g.call(a,x&&Object.assign({{}},x,{{requiredFeatures:[...(x.requiredFeatures||[])].filter(k=>h&&h.has(k)),requiredLimits:Object.fromEntries(Object.entries(x.requiredLimits||{{}}).filter(([k,v])=>k in q&&(k.startsWith('min')?v>=q[k]:v<=q[k])))}})))}};for(const k in s)try{{Object.defineProperty(a,k,{{value:s[k],enumerable:!0,configurable:!0}})}}catch(_){{}}return a}};"#,
+        limits = limits_js(limits),
+        defaults = limits_js(&GpuLimits::webgpu_defaults()),
+        fallback = if fallback { "!0" } else { "!1" },
     )
 }
+
+/// Build the gpu request adapter patch answering the features, limits, info and devices of
+/// the profile.
+pub fn build_gpu_request_adapter_script_from_profile(
+    profile: &GpuProfile,
+    limits: &GpuLimits,
+) -> String {
+    format!(
+        r#"(()=>{{try{{const g=navigator&&navigator.gpu;if(!g||typeof g.requestAdapter!=="function")return;{model}const orig=g.requestAdapter.bind(g);g.requestAdapter=async opts=>W(await orig(opts))}}catch(_){{}}}})();"#,
+        model = webgpu_model_script(
+            &adapter_info_js(profile.webgpu_vendor, profile.webgpu_architecture),
+            limits,
+            profile.webgpu_features,
+            is_fallback_adapter(profile.webgpu_vendor),
+        )
+    )
+}
+
+/// The adapter info as the body of a JS object literal.
+pub(crate) fn adapter_info_js(vendor: &str, architecture: &str) -> String {
+    format!("vendor:'{vendor}',architecture:'{architecture}',device:'',description:''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_device_creates_gpu_objects() {
+        let script = webgpu_model_script(
+            &adapter_info_js("nvidia", "ampere"),
+            &GpuLimits::default(),
+            &[],
+            false,
+        );
+        let check = r#"
+            const names = ['GPUDevice', 'GPUBuffer', 'GPUShaderModule', 'GPUCommandEncoder',
+                'GPUBindGroupLayout', 'GPUComputePipeline', 'GPUComputePassEncoder',
+                'GPUCommandBuffer', 'GPUCompilationInfo'];
+            for (const n of names) globalThis[n] = class {};
+            const is = (o, n) => Object.getPrototypeOf(o) === globalThis[n].prototype;
+            const device = await A().requestDevice({ label: 'd' });
+            const buffer = device.createBuffer({ size: 16, usage: 0x80, mappedAtCreation: true });
+            const module = device.createShaderModule({ code: '@compute @workgroup_size(1) fn main() {}' });
+            const layout = device.createBindGroupLayout({ entries: [] });
+            const pipeline = device.createComputePipeline({ layout: 'auto', compute: { module } });
+            const asyncPipeline = await device.createComputePipelineAsync({ layout: 'auto', compute: { module } });
+            const encoder = device.createCommandEncoder();
+            const pass = encoder.beginComputePass();
+            pass.setPipeline(pipeline);
+            pass.dispatchWorkgroups(1);
+            pass.end();
+            device.queue.submit([encoder.finish()]);
+            let error = '';
+            try { device.createBuffer({ usage: 1 }); } catch (e) { error = e.constructor.name; }
+            return [
+                is(device, 'GPUDevice'),
+                is(buffer, 'GPUBuffer') && buffer.size === 16 && buffer.mapState,
+                buffer.getMappedRange().byteLength,
+                is(module, 'GPUShaderModule') && (await module.getCompilationInfo()).messages.length,
+                is(layout, 'GPUBindGroupLayout'),
+                is(pipeline, 'GPUComputePipeline') && is(asyncPipeline, 'GPUComputePipeline'),
+                is(pipeline.getBindGroupLayout(0), 'GPUBindGroupLayout'),
+                is(encoder, 'GPUCommandEncoder') && is(pass, 'GPUComputePassEncoder'),
+                is(encoder.finish(), 'GPUCommandBuffer'),
+                error,
+            ];"#;
+
+        let value = crate::test_js::eval_js(&script, check, "UTC");
+
+        assert_eq!(
+            value,
+            serde_json::json!([
+                true,
+                "mapped",
+                16,
+                0,
+                true,
+                true,
+                true,
+                true,
+                true,
+                "TypeError"
+            ])
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use super::gpu_limits::{LIMITS_SWIFTSHADER, LIMITS_VULKAN_DISCRETE, LIMITS_VULKAN_INTEGRATED};
 use super::gpu_profile::GpuProfile;
+use super::webgl::{WEBGL_OPENGL_MESA, WEBGL_OPENGL_NVIDIA, WEBGL_SWIFTSHADER};
+use super::webgpu::{WEBGPU_FEATURES_DESKTOP, WEBGPU_FEATURES_SWIFTSHADER};
 
 pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
     // NVIDIA GTX 1050
@@ -9,6 +12,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 10,
     },
     // Intel UHD Graphics 620 with Mesa
     GpuProfile {
@@ -18,6 +25,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 20,
     },
     // AMD Radeon RX 580 with Mesa
     GpuProfile {
@@ -28,6 +39,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 4,
     },
     // NVIDIA RTX 3060 with proprietary driver
     GpuProfile {
@@ -37,6 +52,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 12,
     },
     // Intel Arc A770 (modern Intel GPU)
     GpuProfile {
@@ -46,6 +65,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 3,
     },
     // Additional exact data derived from provided prompt:
     // Mesa/X.org (Common Linux open-source default renderer using LLVMpipe)
@@ -56,6 +79,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 20,
     },
     // Mesa (Open-source OpenGL implementation, software rendering fallback)
     GpuProfile {
@@ -65,6 +92,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 20,
     },
     // Intel Inc Integrated Graphics common variant
     GpuProfile {
@@ -74,6 +105,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 12,
     },
     // AMD integrated GPU (e.g., Ryzen integrated graphics)
     GpuProfile {
@@ -83,6 +118,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 12,
     },
     // NVIDIA legacy profile (GTX 1080 variant)
     GpuProfile {
@@ -92,6 +131,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 5,
     },
     // NVIDIA RTX 4090 (desktop, proprietary)
     GpuProfile {
@@ -101,6 +144,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 32,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 3,
     },
     // NVIDIA RTX 3070 (desktop, proprietary)
     GpuProfile {
@@ -110,6 +157,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 8,
     },
     // NVIDIA Tesla T4 (cloud / servers, proprietary)
     GpuProfile {
@@ -119,6 +170,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 3,
     },
     // NVIDIA A100 PCIe (cloud / HPC, proprietary)
     GpuProfile {
@@ -128,6 +183,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 32,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 2,
     },
     // AMD Radeon RX 6600 XT (RDNA2, Mesa)
     GpuProfile {
@@ -137,6 +196,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 5,
     },
     // AMD Radeon RX 7900 XTX (RDNA3, Mesa)
     GpuProfile {
@@ -146,6 +209,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 24,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 2,
     },
     // ── Chrome ANGLE format (newer Chrome on Linux uses ANGLE by default) ──
     GpuProfile {
@@ -155,6 +222,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 4,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -163,6 +234,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 5,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -171,6 +246,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 5,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -179,6 +258,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 4,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -187,6 +270,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_DISCRETE,
+        weight: 3,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -195,6 +282,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -203,6 +294,10 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_OPENGL_MESA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_VULKAN_INTEGRATED,
+        weight: 3,
     },
     // SwiftShader (headless/CI environments — common for automated browsing)
     GpuProfile {
@@ -212,5 +307,9 @@ pub static GPU_PROFILES_LINUX: &[GpuProfile] = &[
         webgpu_architecture: "vulkan",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_SWIFTSHADER,
+        webgpu_features: WEBGPU_FEATURES_SWIFTSHADER,
+        limits: LIMITS_SWIFTSHADER,
+        weight: 10,
     },
 ];
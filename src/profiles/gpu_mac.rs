@@ -1,4 +1,7 @@
+use super::gpu_limits::{LIMITS_APPLE_SILICON, LIMITS_MAC_LEGACY};
 use super::gpu_profile::GpuProfile;
+use super::webgl::{WEBGL_METAL_APPLE, WEBGL_OPENGL_MAC};
+use super::webgpu::{WEBGPU_FEATURES_APPLE, WEBGPU_FEATURES_DESKTOP};
 
 pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
     // Apple M1 (MacBook Air/Pro base models)
@@ -9,6 +12,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 60,
     },
     // Apple M1 Pro (MacBook Pro 14/16-inch)
     GpuProfile {
@@ -18,6 +25,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 10,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 25,
     },
     // Apple M1 Max
     GpuProfile {
@@ -27,6 +38,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 10,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 10,
     },
     // Apple M1 Ultra (Mac Studio)
     GpuProfile {
@@ -36,6 +51,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 20,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 2,
     },
     // Apple M2 (MacBook Air, Mac mini)
     GpuProfile {
@@ -45,6 +64,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 45,
     },
     // Apple M2 Pro (MacBook Pro, Mac mini)
     GpuProfile {
@@ -54,6 +77,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 18,
     },
     // Apple M2 Max (MacBook Pro, Mac Studio)
     GpuProfile {
@@ -63,6 +90,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 8,
     },
     // Apple M2 Ultra (Mac Studio, Mac Pro)
     GpuProfile {
@@ -72,6 +103,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 24,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 2,
     },
     // Apple M3 (base model)
     GpuProfile {
@@ -81,6 +116,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 40,
     },
     // Apple M3 Pro (11-core or 12-core)
     GpuProfile {
@@ -90,6 +129,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 15,
     },
     // Apple M3 Max
     GpuProfile {
@@ -99,6 +142,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 7,
     },
     // Apple M4 (base model)
     GpuProfile {
@@ -108,6 +155,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 40,
     },
     // Apple M4 Pro
     GpuProfile {
@@ -117,6 +168,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 15,
     },
     // Apple M4 Max
     GpuProfile {
@@ -126,6 +181,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 6,
     },
     // Apple M4 Ultra (e.g., Mac Studio / Mac Pro)
     GpuProfile {
@@ -135,6 +194,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 24,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 1,
     },
     // Apple M5 (base model)
     GpuProfile {
@@ -144,6 +207,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 10,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 10,
     },
     // Apple M5 Pro
     GpuProfile {
@@ -153,6 +220,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 3,
     },
     // Apple M5 Max
     GpuProfile {
@@ -162,6 +233,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "metal-3",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_METAL_APPLE,
+        webgpu_features: WEBGPU_FEATURES_APPLE,
+        limits: LIMITS_APPLE_SILICON,
+        weight: 2,
     },
     // ── Intel Macs (still ~15% of macOS users) ──
     GpuProfile {
@@ -171,6 +246,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 4,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel Inc.)",
@@ -179,6 +258,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 6,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel Inc.)",
@@ -187,6 +270,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 6,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel Inc.)",
@@ -195,6 +282,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 4,
     },
     // Intel Macs with discrete AMD GPUs (MacBook Pro 15/16-inch 2018-2020)
     GpuProfile {
@@ -204,6 +295,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 3,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (ATI Technologies Inc.)",
@@ -212,6 +307,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 3,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (ATI Technologies Inc.)",
@@ -220,6 +319,10 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 2,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (ATI Technologies Inc.)",
@@ -228,5 +331,9 @@ pub static GPU_PROFILES_MAC: &[GpuProfile] = &[
         webgpu_architecture: "",
         canvas_format: "bgra8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_OPENGL_MAC,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_MAC_LEGACY,
+        weight: 1,
     },
 ];
@@ -1,3 +1,8 @@
+use super::gpu_limits::GpuLimits;
+use super::webgl::WebGlCapabilities;
+#[cfg(feature = "serde")]
+use super::webgpu::{webgpu_features_for, WEBGPU_FEATURE_LISTS};
+
 /// A profile that defines GPU-related fingerprinting properties for spoofing
 /// WebGL and WebGPU APIs.
 ///
@@ -6,7 +11,7 @@
 /// `WebGLRenderingContext.getParameter`, `navigator.gpu`, and
 /// `navigator.gpu.requestAdapter().then(...)`.
 #[derive(Debug, Clone, PartialEq)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GpuProfile {
     /// The spoofed value for `UNMASKED_VENDOR_WEBGL` (GL enum 37445).
     /// Typically returned by `gl.getParameter(...)` from WebGL.
@@ -25,4 +30,112 @@ pub struct GpuProfile {
     pub canvas_format: &'static str,
     /// The hardware concurrency limits
     pub hardware_concurrency: usize,
+    /// The WebGL parameters, precisions and extensions of the driver.
+    pub webgl: WebGlCapabilities,
+    /// The value of `navigator.gpu.requestAdapter().then(a => a.features)`.
+    pub webgpu_features: &'static [&'static str],
+    /// The value of `navigator.gpu.requestAdapter().then(a => a.limits)`.
+    pub limits: GpuLimits,
+    /// The relative share of the gpu in real traffic. Drives the random profile selection.
+    pub weight: u32,
+}
+
+/// The weight of profiles without a known market share.
+pub const DEFAULT_GPU_WEIGHT: u32 = 10;
+
+lazy_static::lazy_static! {
+    /// Strings owned by deserialized profiles and time zones. Each distinct value is leaked once.
+    static ref INTERNED: std::sync::Mutex<std::collections::HashSet<&'static str>> =
+        Default::default();
+}
+
+#[cfg(feature = "serde")]
+lazy_static::lazy_static! {
+    /// Lists owned by deserialized profiles. Each distinct list is leaked once.
+    static ref INTERNED_LISTS: std::sync::Mutex<std::collections::HashSet<&'static [&'static str]>> =
+        Default::default();
+}
+
+/// Get a static copy of the string, leaking it only the first time it is seen.
+pub(crate) fn intern_str(value: &str) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+
+    match interned.get(value) {
+        Some(v) => v,
+        _ => {
+            let leaked: &'static str = Box::leak(value.to_owned().into_boxed_str());
+            interned.insert(leaked);
+            leaked
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GpuProfile {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Owned mirror so profiles can be read from borrowed or streamed input.
+        #[derive(serde::Deserialize)]
+        struct OwnedGpuProfile {
+            webgl_vendor: String,
+            webgl_renderer: String,
+            webgpu_vendor: String,
+            webgpu_architecture: String,
+            canvas_format: String,
+            hardware_concurrency: usize,
+            #[serde(default)]
+            webgl: Option<WebGlCapabilities>,
+            #[serde(default)]
+            webgpu_features: Option<Vec<String>>,
+            #[serde(default)]
+            limits: Option<GpuLimits>,
+            #[serde(default)]
+            weight: Option<u32>,
+        }
+
+        let profile = OwnedGpuProfile::deserialize(deserializer)?;
+
+        Ok(GpuProfile {
+            webgl_vendor: intern_str(&profile.webgl_vendor),
+            webgl_renderer: intern_str(&profile.webgl_renderer),
+            webgpu_vendor: intern_str(&profile.webgpu_vendor),
+            webgpu_architecture: intern_str(&profile.webgpu_architecture),
+            canvas_format: intern_str(&profile.canvas_format),
+            hardware_concurrency: profile.hardware_concurrency,
+            webgl: profile
+                .webgl
+                .unwrap_or_else(|| WebGlCapabilities::for_renderer(&profile.webgl_renderer)),
+            webgpu_features: match profile.webgpu_features {
+                Some(features) => intern_list(features, WEBGPU_FEATURE_LISTS),
+                _ => webgpu_features_for(&profile.webgpu_vendor, &profile.webgpu_architecture),
+            },
+            limits: profile.limits.unwrap_or_else(|| {
+                GpuLimits::for_adapter(&profile.webgpu_vendor, &profile.webgpu_architecture)
+            }),
+            weight: profile.weight.unwrap_or(DEFAULT_GPU_WEIGHT),
+        })
+    }
+}
+
+/// Get a static list, reusing a matching known or interned list and leaking only the first
+/// copy of an unknown one.
+#[cfg(feature = "serde")]
+pub(crate) fn intern_list(
+    list: Vec<String>,
+    known: &[&'static [&'static str]],
+) -> &'static [&'static str] {
+    if let Some(known) = known.iter().find(|known| **known == list) {
+        return known;
+    }
+
+    let list: Vec<&'static str> = list.iter().map(|name| intern_str(name)).collect();
+    let mut interned = INTERNED_LISTS.lock().unwrap_or_else(|e| e.into_inner());
+
+    match interned.get(list.as_slice()) {
+        Some(v) => v,
+        _ => {
+            let leaked: &'static [&'static str] = Box::leak(list.into_boxed_slice());
+            interned.insert(leaked);
+            leaked
+        }
+    }
 }
@@ -0,0 +1,193 @@
+use super::gpu::gpu_profiles_for_os;
+use super::gpu_profile::GpuProfile;
+use crate::configs::AgentOs;
+use rand::distr::{weighted::WeightedIndex, Distribution};
+use rand::prelude::IndexedRandom;
+use rand::Rng;
+use std::ops::{Bound, RangeBounds};
+
+/// One os per profile table.
+const GPU_PROFILE_TABLES: [AgentOs; 7] = [
+    AgentOs::Windows,
+    AgentOs::Mac,
+    AgentOs::Linux,
+    AgentOs::Android,
+    AgentOs::IPhone,
+    AgentOs::IPad,
+    AgentOs::ChromeOS,
+];
+
+/// A filter over the shipped and registered GPU profiles.
+///
+/// ```
+/// use spider_fingerprint::configs::AgentOs;
+/// use spider_fingerprint::profiles::gpu_query::GpuQuery;
+///
+/// let query = GpuQuery::new()
+///     .with_os(AgentOs::Windows)
+///     .with_vendor("nvidia")
+///     .with_concurrency(12..);
+///
+/// assert!(query.iter().all(|p| p.hardware_concurrency >= 12));
+/// assert!(query.pick().is_some());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GpuQuery<'a> {
+    /// The os of the profile tables. Every table is searched when unset.
+    pub os: Option<AgentOs>,
+    /// The WebGPU vendor e.g. `nvidia`. Case-insensitive.
+    pub vendor: Option<&'a str>,
+    /// The prefix of the WebGPU architecture e.g. `metal` or `d3d11`. Case-insensitive.
+    pub architecture: Option<&'a str>,
+    /// A part of the webgl renderer e.g. `M2 Pro`. Case-insensitive.
+    pub renderer: Option<&'a str>,
+    /// The lowest hardware concurrency.
+    pub min_concurrency: Option<usize>,
+    /// The highest hardware concurrency.
+    pub max_concurrency: Option<usize>,
+    /// The preferred canvas format e.g. `bgra8unorm`.
+    pub canvas_format: Option<&'a str>,
+}
+
+impl<'a> GpuQuery<'a> {
+    /// A query matching every profile.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only search the profiles of the os.
+    pub fn with_os(mut self, os: AgentOs) -> Self {
+        self.os = Some(os);
+        self
+    }
+
+    /// Match the WebGPU vendor.
+    pub fn with_vendor(mut self, vendor: &'a str) -> Self {
+        self.vendor = Some(vendor);
+        self
+    }
+
+    /// Match the prefix of the WebGPU architecture.
+    pub fn with_architecture(mut self, architecture: &'a str) -> Self {
+        self.architecture = Some(architecture);
+        self
+    }
+
+    /// Match a part of the webgl renderer.
+    pub fn with_renderer(mut self, renderer: &'a str) -> Self {
+        self.renderer = Some(renderer);
+        self
+    }
+
+    /// Match the hardware concurrency range e.g. `8..=16` or `12..`.
+    pub fn with_concurrency<R: RangeBounds<usize>>(mut self, range: R) -> Self {
+        self.min_concurrency = match range.start_bound() {
+            Bound::Included(n) => Some(*n),
+            Bound::Excluded(n) => Some(n + 1),
+            Bound::Unbounded => None,
+        };
+        self.max_concurrency = match range.end_bound() {
+            Bound::Included(n) => Some(*n),
+            Bound::Excluded(n) => Some(n.saturating_sub(1)),
+            Bound::Unbounded => None,
+        };
+        self
+    }
+
+    /// Match the preferred canvas format.
+    pub fn with_canvas_format(mut self, canvas_format: &'a str) -> Self {
+        self.canvas_format = Some(canvas_format);
+        self
+    }
+
+    /// Does the profile match the query.
+    pub fn matches(&self, profile: &GpuProfile) -> bool {
+        let contains = |haystack: &str, needle: &str| {
+            haystack
+                .to_ascii_lowercase()
+                .contains(&needle.to_ascii_lowercase())
+        };
+
+        self.vendor
+            .is_none_or(|v| profile.webgpu_vendor.eq_ignore_ascii_case(v))
+            && self.architecture.is_none_or(|a| {
+                profile
+                    .webgpu_architecture
+                    .to_ascii_lowercase()
+                    .starts_with(&a.to_ascii_lowercase())
+            })
+            && self
+                .renderer
+                .is_none_or(|r| contains(profile.webgl_renderer, r))
+            && self
+                .min_concurrency
+                .is_none_or(|n| profile.hardware_concurrency >= n)
+            && self
+                .max_concurrency
+                .is_none_or(|n| profile.hardware_concurrency <= n)
+            && self
+                .canvas_format
+                .is_none_or(|f| profile.canvas_format == f)
+    }
+
+    /// The matching profiles.
+    pub fn iter(&self) -> impl Iterator<Item = &'static GpuProfile> + '_ {
+        let tables = match self.os {
+            Some(os) => vec![gpu_profiles_for_os(os)],
+            _ => GPU_PROFILE_TABLES.map(gpu_profiles_for_os).to_vec(),
+        };
+
+        tables
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(move |profile| self.matches(profile))
+    }
+
+    /// Pick a random matching profile by weight.
+    pub fn pick(&self) -> Option<&'static GpuProfile> {
+        self.pick_rng(&mut rand::rng())
+    }
+
+    /// Pick a random matching profile by weight using the provided rng.
+    pub fn pick_rng<R: Rng>(&self, rng: &mut R) -> Option<&'static GpuProfile> {
+        let profiles: Vec<&'static GpuProfile> = self.iter().collect();
+
+        match WeightedIndex::new(profiles.iter().map(|profile| profile.weight)) {
+            Ok(dist) => profiles.get(dist.sample(rng)).copied(),
+            _ => profiles.choose(rng).copied(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_filters_every_table() {
+        let m2_pro: Vec<_> = GpuQuery::new().with_renderer("m2 pro").iter().collect();
+        assert_eq!(m2_pro.len(), 1);
+        assert!(m2_pro[0].webgl_renderer.contains("Apple M2 Pro"));
+
+        let nvidia = GpuQuery::new()
+            .with_os(AgentOs::Windows)
+            .with_vendor("NVIDIA")
+            .with_concurrency(12..=16);
+        assert!(nvidia.iter().count() > 0);
+        assert!(nvidia.iter().all(|p| p.webgpu_vendor == "nvidia"
+            && (12..=16).contains(&p.hardware_concurrency)
+            && gpu_profiles_for_os(AgentOs::Windows).contains(&p)));
+        assert!(nvidia.pick().is_some_and(|p| nvidia.matches(p)));
+
+        assert!(GpuQuery::new()
+            .with_architecture("metal")
+            .iter()
+            .all(|p| p.webgpu_vendor == "apple"));
+        assert!(GpuQuery::new()
+            .with_canvas_format("bgra8unorm")
+            .with_os(AgentOs::Android)
+            .pick()
+            .is_none());
+    }
+}
@@ -1,4 +1,7 @@
+use super::gpu_limits::{LIMITS_D3D_DISCRETE, LIMITS_D3D_INTEGRATED};
 use super::gpu_profile::GpuProfile;
+use super::webgl::{WEBGL_D3D11_AMD, WEBGL_D3D11_INTEL, WEBGL_D3D11_NVIDIA};
+use super::webgpu::{WEBGPU_FEATURES_DESKTOP, WEBGPU_FEATURES_MOBILE};
 
 pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
     // NVIDIA RTX 3060
@@ -9,6 +12,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 45,
     },
     // NVIDIA RTX 4090
     GpuProfile {
@@ -18,6 +25,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 24,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 4,
     },
     // NVIDIA GTX 1650
     GpuProfile {
@@ -27,6 +38,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 35,
     },
     // Intel Iris Xe
     GpuProfile {
@@ -36,6 +51,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 60,
     },
     // Intel UHD Graphics 630
     GpuProfile {
@@ -45,6 +64,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 6,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 35,
     },
     // AMD Radeon RX 6700 XT
     GpuProfile {
@@ -54,6 +77,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 8,
     },
     // AMD Radeon RX 5600 XT
     GpuProfile {
@@ -63,6 +90,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 5,
     },
     // AMD Radeon RX Vega 10 Graphics (integrated GPU, common on Ryzen laptops)
     GpuProfile {
@@ -72,6 +103,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     // NVIDIA RTX 2060
     GpuProfile {
@@ -81,6 +116,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 25,
     },
     // Intel Arc A750 Graphics
     GpuProfile {
@@ -90,6 +129,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 3,
     },
     // ── NVIDIA RTX 40-series ──
     GpuProfile {
@@ -99,6 +142,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 40,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -107,6 +154,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 20,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -115,6 +166,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 20,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -123,6 +178,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -131,6 +190,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 6,
     },
     // ── NVIDIA RTX 30-series ──
     GpuProfile {
@@ -140,6 +203,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 25,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -148,6 +215,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 20,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -156,6 +227,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -164,6 +239,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -172,6 +251,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 5,
     },
     // ── NVIDIA GTX 16/10-series (hugely popular) ──
     GpuProfile {
@@ -181,6 +264,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 15,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -189,6 +276,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -197,6 +288,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 5,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -205,6 +300,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 4,
     },
     // ── NVIDIA Laptop GPUs ──
     GpuProfile {
@@ -214,6 +313,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 35,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -222,6 +325,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 35,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -230,6 +337,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 15,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (NVIDIA Corporation)",
@@ -238,6 +349,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_D3D11_NVIDIA,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     // ── AMD Radeon discrete ──
     GpuProfile {
@@ -247,6 +362,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -255,6 +374,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 8,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -263,6 +386,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 4,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -271,6 +398,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 5,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -279,6 +410,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 4,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -287,6 +422,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d12",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 24,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 3,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -295,6 +434,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 10,
     },
     // ── AMD Integrated (very common on Ryzen laptops) ──
     GpuProfile {
@@ -304,6 +447,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 30,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -312,6 +459,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 16,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 8,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (AMD)",
@@ -320,6 +471,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_AMD,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_DISCRETE,
+        weight: 6,
     },
     // ── Intel Integrated (extremely common on laptops) ──
     GpuProfile {
@@ -329,6 +484,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 50,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -337,6 +496,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 20,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -345,6 +508,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 8,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 20,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -353,6 +520,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 15,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -361,6 +532,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 10,
     },
     GpuProfile {
         webgl_vendor: "Google Inc. (Intel)",
@@ -369,6 +544,10 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 4,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_DESKTOP,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 10,
     },
     // ── Qualcomm Adreno (Snapdragon X laptops) ──
     GpuProfile {
@@ -378,5 +557,9 @@ pub static GPU_PROFILES_WINDOWS: &[GpuProfile] = &[
         webgpu_architecture: "d3d11",
         canvas_format: "rgba8unorm",
         hardware_concurrency: 12,
+        webgl: WEBGL_D3D11_INTEL,
+        webgpu_features: WEBGPU_FEATURES_MOBILE,
+        limits: LIMITS_D3D_INTEGRATED,
+        weight: 3,
     },
 ];
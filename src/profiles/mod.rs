@@ -1,7 +1,15 @@
+/// Joint device records of cores, memory, gpu and screen.
+pub mod device;
+/// Installed font lists per operating system.
+pub mod fonts;
 /// GPU-related utilities and GPU profile definitions.
 pub mod gpu;
 /// GPU profiles tailored specifically for Android devices.
 pub mod gpu_android;
+/// GPU profiles for Chromebooks running ChromeOS.
+pub mod gpu_chromeos;
+/// GPU profiles for Apple iPad systems with A-series and M-series chips.
+pub mod gpu_ipad;
 /// GPU profiles for Apple iPhone systems based on Apple Silicon (M-series).
 pub mod gpu_iphone;
 /// Constants defining realistic GPU limits such as maximum texture size and resources.
@@ -12,5 +20,11 @@ pub mod gpu_linux;
 pub mod gpu_mac;
 /// Defines the common GPU profile structure and utility methods shared across GPU platforms.
 pub mod gpu_profile;
+/// Filtering and lookup over every GPU profile table.
+pub mod gpu_query;
 /// GPU profiles specifically tailored for Windows systems.
 pub mod gpu_windows;
+/// WebGL capabilities per GPU driver.
+pub mod webgl;
+/// WebGPU features per GPU driver.
+pub mod webgpu;
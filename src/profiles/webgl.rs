@@ -0,0 +1,674 @@
+/// The WebGL capabilities reported by a GPU driver.
+///
+/// Served by `getParameter`, `getShaderPrecisionFormat`, `getSupportedExtensions`,
+/// `getExtension` and `getContextAttributes` on WebGL1 and WebGL2 contexts.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WebGlCapabilities {
+    /// `MAX_TEXTURE_SIZE` (3379).
+    pub max_texture_size: u32,
+    /// `MAX_CUBE_MAP_TEXTURE_SIZE` (34076).
+    pub max_cube_map_texture_size: u32,
+    /// `MAX_RENDERBUFFER_SIZE` (34024).
+    pub max_renderbuffer_size: u32,
+    /// `MAX_VIEWPORT_DIMS` (3386).
+    pub max_viewport_dims: [u32; 2],
+    /// `ALIASED_LINE_WIDTH_RANGE` (33902).
+    pub aliased_line_width_range: [f32; 2],
+    /// `ALIASED_POINT_SIZE_RANGE` (33901).
+    pub aliased_point_size_range: [f32; 2],
+    /// `MAX_VERTEX_ATTRIBS` (34921).
+    pub max_vertex_attribs: u32,
+    /// `MAX_VERTEX_UNIFORM_VECTORS` (36347).
+    pub max_vertex_uniform_vectors: u32,
+    /// `MAX_FRAGMENT_UNIFORM_VECTORS` (36349).
+    pub max_fragment_uniform_vectors: u32,
+    /// `MAX_VARYING_VECTORS` (36348).
+    pub max_varying_vectors: u32,
+    /// `MAX_TEXTURE_IMAGE_UNITS` (34930).
+    pub max_texture_image_units: u32,
+    /// `MAX_VERTEX_TEXTURE_IMAGE_UNITS` (35660).
+    pub max_vertex_texture_image_units: u32,
+    /// `MAX_COMBINED_TEXTURE_IMAGE_UNITS` (35661).
+    pub max_combined_texture_image_units: u32,
+    /// `MAX_TEXTURE_MAX_ANISOTROPY_EXT` (34047).
+    pub max_anisotropy: u32,
+    /// `MAX_SAMPLES` (36183). WebGL2 only.
+    pub max_samples: u32,
+    /// `MAX_3D_TEXTURE_SIZE` (32883). WebGL2 only.
+    pub max_3d_texture_size: u32,
+    /// `MAX_ARRAY_TEXTURE_LAYERS` (35071). WebGL2 only.
+    pub max_array_texture_layers: u32,
+    /// `MAX_DRAW_BUFFERS` (34852).
+    pub max_draw_buffers: u32,
+    /// `MAX_COLOR_ATTACHMENTS` (36063).
+    pub max_color_attachments: u32,
+    /// `[rangeMin, rangeMax, precision]` of `getShaderPrecisionFormat` for `LOW_FLOAT`,
+    /// `MEDIUM_FLOAT`, `HIGH_FLOAT`, `LOW_INT`, `MEDIUM_INT` and `HIGH_INT`.
+    pub shader_precision: [[i32; 3]; 6],
+    /// The `antialias` context attribute when requested.
+    pub antialias: bool,
+    /// `getSupportedExtensions()` on a WebGL1 context.
+    pub extensions: &'static [&'static str],
+    /// `getSupportedExtensions()` on a WebGL2 context.
+    pub extensions_webgl2: &'static [&'static str],
+}
+
+/// Desktop drivers expose full precision for every type.
+const PRECISION_DESKTOP: [[i32; 3]; 6] = [
+    [127, 127, 23],
+    [127, 127, 23],
+    [127, 127, 23],
+    [31, 30, 0],
+    [31, 30, 0],
+    [31, 30, 0],
+];
+
+/// Mobile drivers expose real low and medium precision.
+const PRECISION_MOBILE: [[i32; 3]; 6] = [
+    [15, 15, 10],
+    [15, 15, 10],
+    [127, 127, 23],
+    [15, 14, 0],
+    [15, 14, 0],
+    [31, 30, 0],
+];
+
+/// WebGL1 extensions of Chrome on ANGLE D3D11.
+pub static EXTENSIONS_D3D11: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_clip_control",
+    "EXT_color_buffer_half_float",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_polygon_offset_clamp",
+    "EXT_shader_texture_lod",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_float_linear",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_blend_func_extended",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+];
+
+/// WebGL2 extensions of Chrome on ANGLE D3D11.
+pub static EXTENSIONS_WEBGL2_D3D11: &[&str] = &[
+    "EXT_clip_control",
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_conservative_depth",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query_webgl2",
+    "EXT_float_blend",
+    "EXT_polygon_offset_clamp",
+    "EXT_render_snorm",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "NV_shader_noperspective_interpolation",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "OES_texture_float_linear",
+    "OVR_multiview2",
+    "WEBGL_blend_func_extended",
+    "WEBGL_clip_cull_distance",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBGL_provoking_vertex",
+    "WEBGL_stencil_texturing",
+];
+
+/// WebGL1 extensions of Chrome on ANGLE Metal with Apple silicon.
+pub static EXTENSIONS_METAL: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_clip_control",
+    "EXT_color_buffer_half_float",
+    "EXT_depth_clamp",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_polygon_offset_clamp",
+    "EXT_shader_texture_lod",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_float_linear",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_blend_func_extended",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_compressed_texture_pvrtc",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+];
+
+/// WebGL2 extensions of Chrome on ANGLE Metal with Apple silicon.
+pub static EXTENSIONS_WEBGL2_METAL: &[&str] = &[
+    "EXT_clip_control",
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_conservative_depth",
+    "EXT_depth_clamp",
+    "EXT_float_blend",
+    "EXT_polygon_offset_clamp",
+    "EXT_render_snorm",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "NV_shader_noperspective_interpolation",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "OES_texture_float_linear",
+    "WEBGL_blend_func_extended",
+    "WEBGL_clip_cull_distance",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_compressed_texture_pvrtc",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBGL_provoking_vertex",
+    "WEBGL_stencil_texturing",
+];
+
+/// WebGL1 extensions of Chrome on ANGLE OpenGL.
+pub static EXTENSIONS_OPENGL: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_clip_control",
+    "EXT_color_buffer_half_float",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_polygon_offset_clamp",
+    "EXT_shader_texture_lod",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_float_linear",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_blend_func_extended",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+];
+
+/// WebGL2 extensions of Chrome on ANGLE OpenGL.
+pub static EXTENSIONS_WEBGL2_OPENGL: &[&str] = &[
+    "EXT_clip_control",
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_conservative_depth",
+    "EXT_depth_clamp",
+    "EXT_disjoint_timer_query_webgl2",
+    "EXT_float_blend",
+    "EXT_polygon_offset_clamp",
+    "EXT_render_snorm",
+    "EXT_texture_compression_bptc",
+    "EXT_texture_compression_rgtc",
+    "EXT_texture_filter_anisotropic",
+    "EXT_texture_mirror_clamp_to_edge",
+    "EXT_texture_norm16",
+    "KHR_parallel_shader_compile",
+    "NV_shader_noperspective_interpolation",
+    "OES_draw_buffers_indexed",
+    "OES_sample_variables",
+    "OES_shader_multisample_interpolation",
+    "OES_texture_float_linear",
+    "OVR_multiview2",
+    "WEBGL_blend_func_extended",
+    "WEBGL_clip_cull_distance",
+    "WEBGL_compressed_texture_s3tc",
+    "WEBGL_compressed_texture_s3tc_srgb",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+    "WEBGL_polygon_mode",
+    "WEBGL_provoking_vertex",
+    "WEBGL_stencil_texturing",
+];
+
+/// WebGL1 extensions of Chrome on mobile GLES drivers.
+pub static EXTENSIONS_GLES: &[&str] = &[
+    "ANGLE_instanced_arrays",
+    "EXT_blend_minmax",
+    "EXT_color_buffer_half_float",
+    "EXT_disjoint_timer_query",
+    "EXT_float_blend",
+    "EXT_frag_depth",
+    "EXT_shader_texture_lod",
+    "EXT_texture_filter_anisotropic",
+    "EXT_sRGB",
+    "KHR_parallel_shader_compile",
+    "OES_element_index_uint",
+    "OES_fbo_render_mipmap",
+    "OES_standard_derivatives",
+    "OES_texture_float",
+    "OES_texture_half_float",
+    "OES_texture_half_float_linear",
+    "OES_vertex_array_object",
+    "WEBGL_color_buffer_float",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_depth_texture",
+    "WEBGL_draw_buffers",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+];
+
+/// WebGL2 extensions of Chrome on mobile GLES drivers.
+pub static EXTENSIONS_WEBGL2_GLES: &[&str] = &[
+    "EXT_color_buffer_float",
+    "EXT_color_buffer_half_float",
+    "EXT_disjoint_timer_query_webgl2",
+    "EXT_float_blend",
+    "EXT_texture_filter_anisotropic",
+    "KHR_parallel_shader_compile",
+    "OES_draw_buffers_indexed",
+    "OES_texture_float_linear",
+    "OVR_multiview2",
+    "WEBGL_compressed_texture_astc",
+    "WEBGL_compressed_texture_etc",
+    "WEBGL_compressed_texture_etc1",
+    "WEBGL_debug_renderer_info",
+    "WEBGL_debug_shaders",
+    "WEBGL_lose_context",
+    "WEBGL_multi_draw",
+];
+
+/// Every shipped extension list. Deserialized lists matching one are not leaked.
+#[cfg(feature = "serde")]
+pub(crate) static EXTENSION_LISTS: &[&[&str]] = &[
+    EXTENSIONS_D3D11,
+    EXTENSIONS_WEBGL2_D3D11,
+    EXTENSIONS_METAL,
+    EXTENSIONS_WEBGL2_METAL,
+    EXTENSIONS_OPENGL,
+    EXTENSIONS_WEBGL2_OPENGL,
+    EXTENSIONS_GLES,
+    EXTENSIONS_WEBGL2_GLES,
+];
+
+/// ANGLE D3D11 on NVIDIA.
+pub const WEBGL_D3D11_NVIDIA: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 16384,
+    max_cube_map_texture_size: 16384,
+    max_renderbuffer_size: 16384,
+    max_viewport_dims: [32767, 32767],
+    aliased_line_width_range: [1.0, 1.0],
+    aliased_point_size_range: [1.0, 1024.0],
+    max_vertex_attribs: 16,
+    max_vertex_uniform_vectors: 4096,
+    max_fragment_uniform_vectors: 1024,
+    max_varying_vectors: 30,
+    max_texture_image_units: 16,
+    max_vertex_texture_image_units: 16,
+    max_combined_texture_image_units: 32,
+    max_anisotropy: 16,
+    max_samples: 16,
+    max_3d_texture_size: 2048,
+    max_array_texture_layers: 2048,
+    max_draw_buffers: 8,
+    max_color_attachments: 8,
+    shader_precision: PRECISION_DESKTOP,
+    antialias: true,
+    extensions: EXTENSIONS_D3D11,
+    extensions_webgl2: EXTENSIONS_WEBGL2_D3D11,
+};
+
+/// ANGLE D3D11 on AMD.
+pub const WEBGL_D3D11_AMD: WebGlCapabilities = WebGlCapabilities {
+    max_samples: 8,
+    ..WEBGL_D3D11_NVIDIA
+};
+
+/// ANGLE D3D11 on Intel and Qualcomm.
+pub const WEBGL_D3D11_INTEL: WebGlCapabilities = WebGlCapabilities {
+    max_samples: 16,
+    max_vertex_uniform_vectors: 4095,
+    ..WEBGL_D3D11_NVIDIA
+};
+
+/// ANGLE Metal on Apple silicon.
+pub const WEBGL_METAL_APPLE: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 16384,
+    max_cube_map_texture_size: 16384,
+    max_renderbuffer_size: 16384,
+    max_viewport_dims: [16384, 16384],
+    aliased_line_width_range: [1.0, 1.0],
+    aliased_point_size_range: [1.0, 511.0],
+    max_vertex_attribs: 16,
+    max_vertex_uniform_vectors: 1024,
+    max_fragment_uniform_vectors: 1024,
+    max_varying_vectors: 31,
+    max_texture_image_units: 16,
+    max_vertex_texture_image_units: 16,
+    max_combined_texture_image_units: 32,
+    max_anisotropy: 16,
+    max_samples: 4,
+    max_3d_texture_size: 2048,
+    max_array_texture_layers: 2048,
+    max_draw_buffers: 8,
+    max_color_attachments: 8,
+    shader_precision: PRECISION_DESKTOP,
+    antialias: true,
+    extensions: EXTENSIONS_METAL,
+    extensions_webgl2: EXTENSIONS_WEBGL2_METAL,
+};
+
+/// ANGLE OpenGL 4.1 on Intel and AMD Macs.
+pub const WEBGL_OPENGL_MAC: WebGlCapabilities = WebGlCapabilities {
+    max_viewport_dims: [16384, 16384],
+    aliased_point_size_range: [1.0, 2047.0],
+    max_vertex_uniform_vectors: 1024,
+    max_fragment_uniform_vectors: 1024,
+    max_varying_vectors: 15,
+    max_samples: 8,
+    extensions: EXTENSIONS_OPENGL,
+    extensions_webgl2: EXTENSIONS_WEBGL2_OPENGL,
+    ..WEBGL_METAL_APPLE
+};
+
+/// OpenGL with the NVIDIA proprietary driver.
+pub const WEBGL_OPENGL_NVIDIA: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 32768,
+    max_cube_map_texture_size: 32768,
+    max_renderbuffer_size: 32768,
+    max_viewport_dims: [32768, 32768],
+    aliased_line_width_range: [1.0, 10.0],
+    aliased_point_size_range: [1.0, 2047.0],
+    max_vertex_attribs: 16,
+    max_vertex_uniform_vectors: 4096,
+    max_fragment_uniform_vectors: 4096,
+    max_varying_vectors: 31,
+    max_texture_image_units: 32,
+    max_vertex_texture_image_units: 32,
+    max_combined_texture_image_units: 192,
+    max_anisotropy: 16,
+    max_samples: 32,
+    max_3d_texture_size: 16384,
+    max_array_texture_layers: 2048,
+    max_draw_buffers: 8,
+    max_color_attachments: 8,
+    shader_precision: PRECISION_DESKTOP,
+    antialias: true,
+    extensions: EXTENSIONS_OPENGL,
+    extensions_webgl2: EXTENSIONS_WEBGL2_OPENGL,
+};
+
+/// OpenGL with Mesa (Intel, AMD and llvmpipe).
+pub const WEBGL_OPENGL_MESA: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 16384,
+    max_cube_map_texture_size: 16384,
+    max_renderbuffer_size: 16384,
+    max_viewport_dims: [16384, 16384],
+    aliased_line_width_range: [1.0, 7.375],
+    aliased_point_size_range: [1.0, 255.875],
+    max_vertex_uniform_vectors: 4096,
+    max_fragment_uniform_vectors: 4096,
+    max_varying_vectors: 32,
+    max_texture_image_units: 32,
+    max_vertex_texture_image_units: 32,
+    max_combined_texture_image_units: 192,
+    max_samples: 16,
+    max_3d_texture_size: 2048,
+    ..WEBGL_OPENGL_NVIDIA
+};
+
+/// Chrome on Android with Adreno.
+pub const WEBGL_ADRENO: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 16384,
+    max_cube_map_texture_size: 16384,
+    max_renderbuffer_size: 16384,
+    max_viewport_dims: [16384, 16384],
+    aliased_line_width_range: [1.0, 8.0],
+    aliased_point_size_range: [1.0, 1023.0],
+    max_vertex_attribs: 32,
+    max_vertex_uniform_vectors: 256,
+    max_fragment_uniform_vectors: 256,
+    max_varying_vectors: 31,
+    max_texture_image_units: 16,
+    max_vertex_texture_image_units: 16,
+    max_combined_texture_image_units: 96,
+    max_anisotropy: 16,
+    max_samples: 4,
+    max_3d_texture_size: 2048,
+    max_array_texture_layers: 2048,
+    max_draw_buffers: 8,
+    max_color_attachments: 8,
+    shader_precision: PRECISION_MOBILE,
+    antialias: true,
+    extensions: EXTENSIONS_GLES,
+    extensions_webgl2: EXTENSIONS_WEBGL2_GLES,
+};
+
+/// Chrome on Android with Mali.
+pub const WEBGL_MALI: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 8192,
+    max_cube_map_texture_size: 8192,
+    max_renderbuffer_size: 8192,
+    max_viewport_dims: [8192, 8192],
+    aliased_line_width_range: [1.0, 100.0],
+    aliased_point_size_range: [1.0, 1024.0],
+    max_vertex_attribs: 16,
+    max_vertex_uniform_vectors: 1024,
+    max_fragment_uniform_vectors: 1024,
+    max_varying_vectors: 15,
+    max_combined_texture_image_units: 96,
+    max_array_texture_layers: 2048,
+    ..WEBGL_ADRENO
+};
+
+/// Apple GPUs on iOS and iPadOS.
+pub const WEBGL_APPLE_MOBILE: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 16384,
+    max_cube_map_texture_size: 16384,
+    max_renderbuffer_size: 16384,
+    max_viewport_dims: [16384, 16384],
+    aliased_line_width_range: [1.0, 1.0],
+    aliased_point_size_range: [1.0, 511.0],
+    max_vertex_attribs: 16,
+    max_vertex_uniform_vectors: 1024,
+    max_fragment_uniform_vectors: 1024,
+    max_varying_vectors: 31,
+    max_combined_texture_image_units: 32,
+    extensions: EXTENSIONS_GLES,
+    extensions_webgl2: EXTENSIONS_WEBGL2_GLES,
+    ..WEBGL_ADRENO
+};
+
+/// SwiftShader, the software renderer of headless Chrome.
+pub const WEBGL_SWIFTSHADER: WebGlCapabilities = WebGlCapabilities {
+    max_texture_size: 8192,
+    max_cube_map_texture_size: 8192,
+    max_renderbuffer_size: 8192,
+    max_viewport_dims: [8192, 8192],
+    aliased_line_width_range: [1.0, 1.0],
+    aliased_point_size_range: [1.0, 1024.0],
+    max_vertex_uniform_vectors: 256,
+    max_fragment_uniform_vectors: 256,
+    max_varying_vectors: 31,
+    max_texture_image_units: 16,
+    max_vertex_texture_image_units: 16,
+    max_combined_texture_image_units: 32,
+    max_samples: 4,
+    max_3d_texture_size: 2048,
+    ..WEBGL_OPENGL_NVIDIA
+};
+
+impl WebGlCapabilities {
+    /// Pick the capabilities matching an unmasked WebGL renderer string.
+    pub fn for_renderer(webgl_renderer: &str) -> WebGlCapabilities {
+        let renderer = webgl_renderer.to_ascii_lowercase();
+        let has = |needle: &str| renderer.contains(needle);
+
+        if has("swiftshader") {
+            WEBGL_SWIFTSHADER
+        } else if has("d3d") {
+            if has("nvidia") {
+                WEBGL_D3D11_NVIDIA
+            } else if has("amd") || has("radeon") {
+                WEBGL_D3D11_AMD
+            } else {
+                WEBGL_D3D11_INTEL
+            }
+        } else if has("metal") {
+            WEBGL_METAL_APPLE
+        } else if has("opengl 4.1") {
+            WEBGL_OPENGL_MAC
+        } else if has("adreno") {
+            WEBGL_ADRENO
+        } else if has("mali") {
+            WEBGL_MALI
+        } else if has("apple") {
+            WEBGL_APPLE_MOBILE
+        } else if has("nvidia") {
+            WEBGL_OPENGL_NVIDIA
+        } else {
+            WEBGL_OPENGL_MESA
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for WebGlCapabilities {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Owned mirror so the extension lists can be read from any input.
+        #[derive(serde::Deserialize)]
+        struct OwnedWebGlCapabilities {
+            max_texture_size: u32,
+            max_cube_map_texture_size: u32,
+            max_renderbuffer_size: u32,
+            max_viewport_dims: [u32; 2],
+            aliased_line_width_range: [f32; 2],
+            aliased_point_size_range: [f32; 2],
+            max_vertex_attribs: u32,
+            max_vertex_uniform_vectors: u32,
+            max_fragment_uniform_vectors: u32,
+            max_varying_vectors: u32,
+            max_texture_image_units: u32,
+            max_vertex_texture_image_units: u32,
+            max_combined_texture_image_units: u32,
+            max_anisotropy: u32,
+            max_samples: u32,
+            max_3d_texture_size: u32,
+            max_array_texture_layers: u32,
+            max_draw_buffers: u32,
+            max_color_attachments: u32,
+            shader_precision: [[i32; 3]; 6],
+            antialias: bool,
+            extensions: Vec<String>,
+            extensions_webgl2: Vec<String>,
+        }
+
+        let caps = OwnedWebGlCapabilities::deserialize(deserializer)?;
+
+        Ok(WebGlCapabilities {
+            max_texture_size: caps.max_texture_size,
+            max_cube_map_texture_size: caps.max_cube_map_texture_size,
+            max_renderbuffer_size: caps.max_renderbuffer_size,
+            max_viewport_dims: caps.max_viewport_dims,
+            aliased_line_width_range: caps.aliased_line_width_range,
+            aliased_point_size_range: caps.aliased_point_size_range,
+            max_vertex_attribs: caps.max_vertex_attribs,
+            max_vertex_uniform_vectors: caps.max_vertex_uniform_vectors,
+            max_fragment_uniform_vectors: caps.max_fragment_uniform_vectors,
+            max_varying_vectors: caps.max_varying_vectors,
+            max_texture_image_units: caps.max_texture_image_units,
+            max_vertex_texture_image_units: caps.max_vertex_texture_image_units,
+            max_combined_texture_image_units: caps.max_combined_texture_image_units,
+            max_anisotropy: caps.max_anisotropy,
+            max_samples: caps.max_samples,
+            max_3d_texture_size: caps.max_3d_texture_size,
+            max_array_texture_layers: caps.max_array_texture_layers,
+            max_draw_buffers: caps.max_draw_buffers,
+            max_color_attachments: caps.max_color_attachments,
+            shader_precision: caps.shader_precision,
+            antialias: caps.antialias,
+            extensions: super::gpu_profile::intern_list(caps.extensions, EXTENSION_LISTS),
+            extensions_webgl2: super::gpu_profile::intern_list(
+                caps.extensions_webgl2,
+                EXTENSION_LISTS,
+            ),
+        })
+    }
+}
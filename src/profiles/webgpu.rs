@@ -0,0 +1,159 @@
+/// WebGPU features of Chrome on D3D12, Vulkan and Metal with discrete or integrated desktop GPUs.
+pub static WEBGPU_FEATURES_DESKTOP: &[&str] = &[
+    "bgra8unorm-storage",
+    "clip-distances",
+    "core-features-and-limits",
+    "depth-clip-control",
+    "depth32float-stencil8",
+    "dual-source-blending",
+    "float32-blendable",
+    "float32-filterable",
+    "indirect-first-instance",
+    "rg11b10ufloat-renderable",
+    "shader-f16",
+    "subgroups",
+    "texture-compression-bc",
+    "texture-compression-bc-sliced-3d",
+    "timestamp-query",
+];
+
+/// WebGPU features of Chrome on Metal with Apple silicon.
+pub static WEBGPU_FEATURES_APPLE: &[&str] = &[
+    "bgra8unorm-storage",
+    "clip-distances",
+    "core-features-and-limits",
+    "depth-clip-control",
+    "depth32float-stencil8",
+    "dual-source-blending",
+    "float32-blendable",
+    "float32-filterable",
+    "indirect-first-instance",
+    "rg11b10ufloat-renderable",
+    "shader-f16",
+    "subgroups",
+    "texture-compression-astc",
+    "texture-compression-astc-sliced-3d",
+    "texture-compression-bc",
+    "texture-compression-bc-sliced-3d",
+    "texture-compression-etc2",
+    "timestamp-query",
+];
+
+/// WebGPU features of Chrome on Android with Vulkan.
+pub static WEBGPU_FEATURES_MOBILE: &[&str] = &[
+    "core-features-and-limits",
+    "depth32float-stencil8",
+    "float32-blendable",
+    "indirect-first-instance",
+    "rg11b10ufloat-renderable",
+    "shader-f16",
+    "subgroups",
+    "texture-compression-astc",
+    "texture-compression-astc-sliced-3d",
+    "texture-compression-etc2",
+    "timestamp-query",
+];
+
+/// WebGPU features of Chrome on the OpenGL backend.
+pub static WEBGPU_FEATURES_OPENGL: &[&str] = &[
+    "depth32float-stencil8",
+    "float32-filterable",
+    "indirect-first-instance",
+    "rg11b10ufloat-renderable",
+    "texture-compression-bc",
+    "timestamp-query",
+];
+
+/// WebGPU features of Chrome on SwiftShader.
+pub static WEBGPU_FEATURES_SWIFTSHADER: &[&str] = &[
+    "core-features-and-limits",
+    "depth-clip-control",
+    "depth32float-stencil8",
+    "float32-blendable",
+    "float32-filterable",
+    "indirect-first-instance",
+    "rg11b10ufloat-renderable",
+    "texture-compression-astc",
+    "texture-compression-bc",
+    "texture-compression-etc2",
+    "timestamp-query",
+];
+
+/// The shipped feature lists, used to avoid leaking deserialized copies.
+#[cfg(feature = "serde")]
+pub(crate) static WEBGPU_FEATURE_LISTS: &[&[&str]] = &[
+    WEBGPU_FEATURES_DESKTOP,
+    WEBGPU_FEATURES_APPLE,
+    WEBGPU_FEATURES_MOBILE,
+    WEBGPU_FEATURES_OPENGL,
+    WEBGPU_FEATURES_SWIFTSHADER,
+];
+
+/// Get the WebGPU features matching the adapter vendor and architecture.
+pub fn webgpu_features_for(
+    webgpu_vendor: &str,
+    webgpu_architecture: &str,
+) -> &'static [&'static str] {
+    let vendor = webgpu_vendor.to_ascii_lowercase();
+    let architecture = webgpu_architecture.to_ascii_lowercase();
+
+    if vendor == "google" {
+        WEBGPU_FEATURES_SWIFTSHADER
+    } else if vendor == "apple" {
+        WEBGPU_FEATURES_APPLE
+    } else if matches!(vendor.as_str(), "qualcomm" | "arm")
+        || architecture.starts_with("adreno")
+        || architecture.starts_with("mali")
+    {
+        WEBGPU_FEATURES_MOBILE
+    } else if architecture == "opengl" {
+        WEBGPU_FEATURES_OPENGL
+    } else {
+        WEBGPU_FEATURES_DESKTOP
+    }
+}
+
+/// Whether the adapter is a software fallback adapter.
+pub fn is_fallback_adapter(webgpu_vendor: &str) -> bool {
+    webgpu_vendor.eq_ignore_ascii_case("google")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::AgentOs;
+
+    #[test]
+    fn webgpu_adapter_models_the_profile() {
+        use crate::profiles::gpu::{builtin_gpu_profiles_for_os, GPU_PROFILES_LINUX};
+
+        for os in [
+            AgentOs::Windows,
+            AgentOs::Mac,
+            AgentOs::Linux,
+            AgentOs::Android,
+            AgentOs::ChromeOS,
+        ] {
+            for profile in builtin_gpu_profiles_for_os(os) {
+                assert_eq!(
+                    profile.webgpu_features,
+                    webgpu_features_for(profile.webgpu_vendor, profile.webgpu_architecture)
+                );
+            }
+        }
+
+        let swiftshader = GPU_PROFILES_LINUX
+            .iter()
+            .find(|p| p.webgpu_features == WEBGPU_FEATURES_SWIFTSHADER)
+            .expect("swiftshader profile");
+        let script = crate::spoof_gpu::build_gpu_spoof_script_wgsl_from_profile(
+            swiftshader,
+            &crate::profiles::gpu_limits::GpuLimits::for_os(AgentOs::Linux),
+        );
+
+        assert!(script.contains("'texture-compression-etc2'"));
+        assert!(script.contains("B=!0"));
+        assert!(script.contains("requestAdapterInfo"));
+        assert!(!script.contains("requestDevice:()=>Promise.resolve({})"));
+    }
+}
@@ -0,0 +1,119 @@
+/// Spoof the audio fingerprint with a small noise on `AudioBuffer.getChannelData`,
+/// `AudioBuffer.copyFromChannel`, `AnalyserNode.getFloatFrequencyData`,
+/// `AnalyserNode.getByteFrequencyData` and the buffers from `OfflineAudioContext.startRendering`.
+///
+/// The noise is derived from the seed and the sample index so the same seed answers the same
+/// values across calls and pages.
+pub fn spoof_audio_script(seed: u32) -> String {
+    format!(
+        r#"(()=>{{if(typeof AudioBuffer==='undefined')return;const s={seed},h=x=>{{x=Math.imul(x^x>>>16,0x45d9f3b);x=Math.imul(x^x>>>16,0x45d9f3b);return(x^x>>>16)>>>0}},f=i=>h(s^Math.imul(i+1,0x9e3779b1))/4294967296-.5,o=h(s)%100,W=new WeakSet(),n=(P,k,v)=>{{const g=P[k];if(!g)return;const w={{[k](...a){{return v.call(this,g,a)}}}}[k];w.toString=()=>`function ${{k}}() {{ [native code] }}`;Object.defineProperty(P,k,{{value:w,writable:!0,configurable:!0,enumerable:!0}})}},B=AudioBuffer.prototype,A=typeof AnalyserNode!=='undefined'&&AnalyserNode.prototype;n(B,'getChannelData',function(g,a){{const d=g.apply(this,a);if(!W.has(d)){{W.add(d);for(let i=o;i<d.length;i+=100)d[i]+=f(i)*2e-7}}return d}});n(B,'copyFromChannel',function(g,a){{try{{B.getChannelData.call(this,a[1])}}catch(e){{}}return g.apply(this,a)}});if(A){{n(A,'getFloatFrequencyData',function(g,a){{const r=g.apply(this,a),d=a[0];if(d)for(let i=o;i<d.length;i+=100)d[i]+=f(i)*.1;return r}});n(A,'getByteFrequencyData',function(g,a){{const r=g.apply(this,a),d=a[0];if(d)for(let i=o;i<d.length;i+=100)if(d[i]>0&&d[i]<255)d[i]+=f(i)<0?-1:1;return r}})}}if(typeof OfflineAudioContext!=='undefined')n(OfflineAudioContext.prototype,'startRendering',function(g,a){{return g.apply(this,a).then(b=>{{for(let c=0;c<b.numberOfChannels;c++)b.getChannelData(c);return b}})}})}})();"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::Tier;
+    use crate::{emulate, EmulationConfiguration};
+
+    #[test]
+    fn audio_script_is_seeded() {
+        let script = spoof_audio_script(42);
+        assert!(script.contains("const s=42,"));
+        assert!(!script.contains("Math.random"));
+        assert_eq!(script, spoof_audio_script(42));
+        assert_ne!(script, spoof_audio_script(43));
+    }
+
+    /// Run the script over a fake audio stack and read back where and how much each api was
+    /// shifted.
+    fn noise(seed: u32) -> serde_json::Value {
+        let audio = r#"
+            class AudioBuffer {
+                constructor(n) { this.c = [new Float32Array(n), new Float32Array(n)]; this.numberOfChannels = 2; }
+                getChannelData(i) { return this.c[i]; }
+                copyFromChannel(d, i) { d.set(this.c[i].subarray(0, d.length)); }
+            }
+            class AnalyserNode {
+                getFloatFrequencyData(d) { d.fill(-100); }
+                getByteFrequencyData(d) { d.fill(128); d[0] = 0; d[100] = 0; d[200] = 255; }
+            }
+            class OfflineAudioContext { startRendering() { return Promise.resolve(new AudioBuffer(1000)); } }
+            Object.assign(globalThis, { AudioBuffer, AnalyserNode, OfflineAudioContext });"#;
+        let script = format!("{audio}{}", spoof_audio_script(seed));
+        let check = r#"
+            const at = (d, v) => [...d.keys()].filter(i => d[i] !== v);
+            const b = new AudioBuffer(1000), d = b.getChannelData(0), first = [...d];
+            const copy = new Float32Array(1000);
+            b.copyFromChannel(copy, 1);
+            const f = new Float32Array(1000), u = new Uint8Array(1000), a = new AnalyserNode();
+            a.getFloatFrequencyData(f);
+            a.getByteFrequencyData(u);
+            const r = await new OfflineAudioContext().startRendering();
+            return {
+                channel: at(d, 0), max: Math.max(...d.map(Math.abs)), stable: b.getChannelData(0).every((v, i) => v === first[i]),
+                copy: at(copy, 0).length, float: at(f, -100), byte: at(u, 128).map(i => [i, u[i]]),
+                rendered: [at(r.c[0], 0).length, at(r.c[1], 0).length], native: String(AudioBuffer.prototype.getChannelData),
+            };"#;
+
+        crate::test_js::eval_js(&script, check, "UTC")
+    }
+
+    #[test]
+    fn audio_script_shifts_every_hundredth_sample() {
+        let value = noise(42);
+
+        let channel = value["channel"].as_array().expect("indices");
+        let offset = channel[0].as_u64().expect("index");
+        assert!(offset < 100);
+        assert_eq!(channel.len() as u64, (1099 - offset) / 100);
+        assert!(channel
+            .iter()
+            .enumerate()
+            .all(|(i, v)| v.as_u64() == Some(offset + 100 * i as u64)));
+        assert!(value["max"].as_f64().unwrap() < 1e-7);
+        assert_eq!(value["stable"], true);
+        assert_eq!(value["copy"], channel.len());
+        assert_eq!(value["float"], value["channel"]);
+        assert_eq!(
+            value["rendered"],
+            serde_json::json!([channel.len(), channel.len()])
+        );
+        // the silent and saturated bins keep their value
+        let bytes = value["byte"].as_array().expect("bytes");
+        assert_eq!(bytes.len(), channel.len() + if offset == 0 { 0 } else { 3 });
+        for byte in bytes {
+            match (
+                byte[0].as_u64().expect("bin"),
+                byte[1].as_u64().expect("value"),
+            ) {
+                (0 | 100, value) => assert_eq!(value, 0),
+                (200, value) => assert_eq!(value, 255),
+                (bin, value) => assert!(channel.contains(&bin.into()) && value.abs_diff(128) == 1),
+            }
+        }
+        assert_eq!(
+            value["native"],
+            "function getChannelData() { [native code] }"
+        );
+
+        assert_eq!(noise(42), value.clone());
+        assert_ne!(noise(43), value);
+    }
+
+    #[test]
+    fn audio_noise_is_a_standalone_module() {
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.tier = Tier::None;
+        config.fingerprint = crate::Fingerprint::None;
+
+        assert!(emulate(ua, &config, &None, &None).is_none());
+
+        config.enable_audio_noise = true;
+        let persona = crate::Persona::generate(ua, &config, &None, None, false, &None);
+        let script = persona.emulate(&None).unwrap_or_default();
+        assert_eq!(script, spoof_audio_script(persona.noise_seed));
+        assert!(!script.contains("offsetWidth"));
+    }
+}
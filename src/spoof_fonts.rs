@@ -0,0 +1,82 @@
+use crate::configs::AgentOs;
+use crate::profiles::fonts::fonts_for_os;
+
+/// Spoof the font availability checks to answer with the fonts of the os.
+///
+/// Families outside the list are dropped from `font-family` and `font` on inline styles (the
+/// style properties, `cssText`, `setProperty`, the `style` attribute and `element.style = ...`)
+/// and canvas contexts so measuring falls back like a missing font, `document.fonts.check`
+/// returns `false` for them and `queryLocalFonts` lists the os fonts. Web fonts in
+/// `document.fonts` are kept. Style sheets and `setAttributeNS` are not rewritten.
+pub fn spoof_fonts_script(os: AgentOs) -> String {
+    let fonts = fonts_for_os(os)
+        .iter()
+        .map(|f| format!("'{f}'"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"(()=>{{const F=[{fonts}],L=new Set(F.map(f=>f.toLowerCase())),G=new Set(['serif','sans-serif','monospace','cursive','fantasy','system-ui','ui-serif','ui-sans-serif','ui-monospace','ui-rounded','math','emoji','fangsong','-apple-system','blinkmacsystemfont','inherit','initial','unset','revert','revert-layer']),sp=v=>{{const r=[];let c='',q='';for(const h of v){{if(q){{if(h===q)q='';else c+=h}}else if(h==='"'||h==="'")q=h;else if(h===','){{r.push(c.trim());c=''}}else c+=h}}r.push(c.trim());return r.filter(Boolean)}},w=()=>{{const s=new Set();try{{for(const f of document.fonts)s.add(f.family.replace(/^["']|["']$/g,'').toLowerCase())}}catch(e){{}}return s}},ok=(f,W)=>{{const k=f.toLowerCase();return G.has(k)||L.has(k)||W.has(k)}},ff=v=>{{if(typeof v!=='string')return v;const W=w(),a=sp(v),k=a.filter(f=>ok(f,W));return k.length===a.length?v:k.length?k.map(f=>G.has(f.toLowerCase())?f:`"${{f}}"`).join(', '):'initial'}},R=/^((?:.*?\s)?(?:[\d.]+(?:px|pt|pc|em|rem|ex|ch|vw|vh|vmin|vmax|cm|mm|in|q|%)|xx-small|x-small|small|medium|large|x-large|xx-large|xxx-large|smaller|larger)(?:\s*\/\s*\S+)?\s+)(.+)$/i,fs=v=>{{if(typeof v!=='string')return v;const m=v.match(R);return m?m[1]+ff(m[2]):v}},cs=v=>{{if(typeof v!=='string')return v;const o=[];let c='',q='',p=0;for(const h of v){{if(q){{if(h===q)q=''}}else if(h==='"'||h==="'")q=h;else if(h==='(')p++;else if(h===')')p--;else if(h===';'&&!p){{o.push(c);c='';continue}}c+=h}}o.push(c);return o.map(d=>{{const i=d.indexOf(':'),k=d.slice(0,i).trim().toLowerCase();if(i<0||k!=='font'&&k!=='font-family')return d;const m=d.slice(i+1).match(/^(\s*)(.*?)(\s*!\s*important\s*)?$/i);return d.slice(0,i+1)+m[1]+(k==='font'?fs(m[2]):ff(m[2]))+(m[3]||'')}}).join(';')}},n=(f,k)=>{{f.toString=()=>`function ${{k}}() {{ [native code] }}`;return f}},a=(P,k,t)=>{{const d=P&&Object.getOwnPropertyDescriptor(P,k);if(d&&d.set)Object.defineProperty(P,k,{{...d,set:n(function(v){{return d.set.call(this,t(v))}},'set '+k)}})}};if(typeof CSSStyleDeclaration!=='undefined'){{const C=CSSStyleDeclaration.prototype,sp0=C.setProperty;a(C,'fontFamily',ff);a(C,'font',fs);a(C,'cssText',cs);Object.defineProperty(C,'setProperty',{{value:n({{setProperty(p,v,...r){{const k=String(p).toLowerCase();return sp0.call(this,p,k==='font-family'?ff(v):k==='font'?fs(v):v,...r)}}}}.setProperty,'setProperty'),writable:!0,configurable:!0,enumerable:!0}})}}for(const c of['HTMLElement','SVGElement','MathMLElement'])if(typeof self[c]!=='undefined')a(self[c].prototype,'style',cs);if(typeof Element!=='undefined'){{const E=Element.prototype,sa=E.setAttribute,f=n({{setAttribute(k,...r){{if(r.length&&String(k).toLowerCase()==='style')r[0]=cs(String(r[0]));return sa.call(this,k,...r)}}}}.setAttribute,'setAttribute');Object.defineProperty(f,'length',{{value:2}});Object.defineProperty(E,'setAttribute',{{value:f,writable:!0,configurable:!0,enumerable:!0}})}}for(const c of['CanvasRenderingContext2D','OffscreenCanvasRenderingContext2D'])if(typeof self[c]!=='undefined')a(self[c].prototype,'font',fs);if(typeof FontFaceSet!=='undefined'){{const F=FontFaceSet.prototype,ck=F.check;Object.defineProperty(F,'check',{{value:n({{check(f,...r){{const m=typeof f==='string'&&f.match(R);if(m){{const W=w();if(!sp(m[2]).every(x=>ok(x,W)))return!1}}return ck.call(this,f,...r)}}}}.check,'check'),writable:!0,configurable:!0,enumerable:!0}})}}if(typeof self.queryLocalFonts==='function'){{const q=self.queryLocalFonts;Object.defineProperty(self,'queryLocalFonts',{{value:n({{queryLocalFonts(o){{return q.call(this,o).then(()=>{{const p=o&&o.postscriptNames;return F.map(f=>({{family:f,fullName:f,postscriptName:f.replace(/\s+/g,''),style:'Regular',blob:()=>Promise.reject(new DOMException('','NotAllowedError'))}})).filter(d=>!p||p.includes(d.postscriptName))}})}}}}.queryLocalFonts,'queryLocalFonts'),writable:!0,configurable:!0,enumerable:!0}})}}}})();"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fonts_script_per_os() {
+        let windows = spoof_fonts_script(AgentOs::Windows);
+        assert!(windows.contains("'Segoe UI'"));
+        assert!(!windows.contains("'Helvetica Neue'"));
+
+        let mac = spoof_fonts_script(AgentOs::Mac);
+        assert!(mac.contains("'Helvetica Neue'"));
+        assert!(!mac.contains("'Segoe UI'"));
+
+        for os in [
+            AgentOs::Linux,
+            AgentOs::Android,
+            AgentOs::IPhone,
+            AgentOs::ChromeOS,
+        ] {
+            assert!(!fonts_for_os(os).is_empty());
+        }
+    }
+
+    #[test]
+    fn fonts_script_rewrites_inline_style_text() {
+        let dom = r#"
+            class CSSStyleDeclaration {}
+            for (const k of ['fontFamily', 'font', 'cssText'])
+                Object.defineProperty(CSSStyleDeclaration.prototype, k, {
+                    get() { return this['_' + k]; }, set(v) { this['_' + k] = v; }, configurable: true });
+            class Element { setAttribute(k, v) { (this.attrs ||= {})[k] = v; } }
+            class HTMLElement extends Element {}
+            Object.defineProperty(HTMLElement.prototype, 'style', {
+                get() { return this._style; }, set(v) { this._style = v; }, configurable: true });
+            Object.assign(globalThis, { CSSStyleDeclaration, Element, HTMLElement });"#;
+        let script = format!("{dom}{}", spoof_fonts_script(AgentOs::Windows));
+        let check = r#"
+            const s = new CSSStyleDeclaration();
+            s.cssText = 'color: red; font-family: "Segoe UI", Fakefont, serif !important; background: url("data:image/png;base64,AA")';
+            const e = new HTMLElement();
+            e.setAttribute('style', 'font: 12px Fakefont, Arial');
+            e.setAttribute('title', 'Fakefont');
+            e.style = 'font-family: Fakefont';
+            return [s.cssText, e.attrs.style, e.attrs.title, e._style, Element.prototype.setAttribute.length];"#;
+
+        let value = crate::test_js::eval_js(&script, check, "UTC");
+
+        assert_eq!(
+            value,
+            serde_json::json!([
+                r#"color: red; font-family: "Segoe UI", serif !important; background: url("data:image/png;base64,AA")"#,
+                r#"font: 12px "Arial""#,
+                "Fakefont",
+                "font-family: initial",
+                2
+            ])
+        );
+    }
+}
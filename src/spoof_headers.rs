@@ -1,8 +1,13 @@
 use std::collections::HashMap;
 
 use crate::configs::AgentOs;
-use crate::BrowserKind;
-use crate::{detect_browser, detect_browser_kind, get_agent_os};
+use crate::profiles::device::DeviceProfile;
+use crate::spoof_user_agent::{
+    build_high_entropy_data_from_info_rng, chrome_os_platform_version, chromium_brand,
+    chromium_brand_list, chromium_brand_version, format_sec_ch_ua, HighEntropyUaData,
+};
+use crate::{detect_browser, parse_user_agent};
+use crate::{BrowserKind, UserAgentInfo};
 use http::header::{
     HeaderValue, ACCEPT, ACCEPT_ENCODING, ACCEPT_LANGUAGE, CACHE_CONTROL, CONNECTION, HOST, PRAGMA,
     REFERER, UPGRADE_INSECURE_REQUESTS, USER_AGENT,
@@ -11,10 +16,6 @@ use http::{HeaderMap, HeaderName};
 use rand::{rng, Rng};
 
 lazy_static::lazy_static! {
-    /// The brand version of google chrome. Use the env var 'NOT_A_BRAND_VERSION'.
-    static ref NOT_A_BRAND_VERSION: String = {
-       crate::CHROME_NOT_A_BRAND_VERSION.split('.').next().unwrap_or("99".into()).into()
-    };
     /// The chrome platform linux version of google chrome. Use the env var 'NOT_A_BRAND_VERSION'.
     static ref CHROME_PLATFORM_LINUX_VERSION: String = {
         std::env::var("CHROME_PLATFORM_LINUX_VERSION").unwrap_or_else(|_| {
@@ -37,64 +38,107 @@ lazy_static::lazy_static! {
     };
 }
 
-fn parse_user_agent_to_ch_ua(ua: &str, dec: bool, linux: bool) -> String {
-    let mut parts = Vec::with_capacity(3);
-
-    if let Some(version) = ua
-        .split("Chrome/")
-        .nth(1)
-        .and_then(|s| s.split_whitespace().next())
-    {
-        if let Some(major_version) = version.split('.').next() {
-            let chromium_version = format!(
-                r#""Chromium";v="{}{}""#,
-                major_version,
-                if dec {
-                    if linux {
-                        ".0.0.0"
-                    } else {
-                        ".0.0"
-                    }
-                } else {
-                    ""
-                }
+/// The brands of the user agent for the headers sent without high entropy values.
+fn parse_user_agent_to_ch_ua(info: &UserAgentInfo, dec: bool) -> String {
+    let chrome_major = info
+        .engine_version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
+        .filter(|_| info.is_chromium());
+
+    match chrome_major {
+        Some(chrome_major) => {
+            let chromium_version = format!("{chrome_major}.0.0.0");
+            let brands = chromium_brand_list(
+                chrome_major,
+                &chromium_version,
+                chromium_brand(info.browser),
+                &chromium_brand_version(info, &chromium_version),
+                dec,
             );
-            let google_chrome_major = format!(r#""Google Chrome";v="{}""#, major_version);
-            let one_fourty_one = major_version == "141";
 
-            let brand_label = if one_fourty_one {
-                "Not?A_Brand"
-            } else {
-                "Not:A-Brand"
-            };
+            format_sec_ch_ua(&brands)
+        }
+        _ => String::new(),
+    }
+}
 
-            let not_a_brand_label = format!(
-                r#""{brand_label}";v="{}{}""#,
-                *NOT_A_BRAND_VERSION,
-                if dec {
-                    if linux {
-                        ".0.0.0"
-                    } else {
-                        ".0.0"
-                    }
-                } else {
-                    ""
-                }
-            );
+/// The client hint header values.
+struct ClientHints {
+    sec_ch_ua: String,
+    full_version_list: String,
+    mobile: bool,
+    platform: String,
+    platform_version: String,
+    architecture: String,
+    bitness: String,
+    model: String,
+    wow64: bool,
+}
 
-            if one_fourty_one {
-                parts.push(google_chrome_major);
-                parts.push(not_a_brand_label);
-                parts.push(chromium_version);
+impl ClientHints {
+    /// The hints of the high entropy values, matching `navigator.userAgentData`.
+    fn from_high_entropy(data: &HighEntropyUaData) -> Self {
+        let quote = |value: &str| format!("\"{value}\"");
+
+        Self {
+            sec_ch_ua: format_sec_ch_ua(&data.brands()),
+            full_version_list: format_sec_ch_ua(&data.full_version_list),
+            mobile: data.mobile,
+            platform: quote(&data.platform),
+            platform_version: quote(&data.platform_version),
+            architecture: quote(&data.architecture),
+            bitness: quote(&data.bitness),
+            model: quote(&data.model),
+            wow64: data.wow64_ness,
+        }
+    }
+
+    /// The hints read from the user agent and the host.
+    fn from_user_agent(info: &UserAgentInfo, mobile: bool) -> Self {
+        let agent_os = info.os;
+        let linux_agent = agent_os == AgentOs::Linux;
+
+        Self {
+            sec_ch_ua: parse_user_agent_to_ch_ua(info, false),
+            full_version_list: parse_user_agent_to_ch_ua(info, true),
+            mobile,
+            platform: if linux_agent {
+                "\"Linux\""
+            } else if agent_os == AgentOs::Mac {
+                "\"macOS\""
+            } else if agent_os == AgentOs::Windows {
+                "\"Windows\""
+            } else if agent_os == AgentOs::Android {
+                "\"Android\""
+            } else if agent_os == AgentOs::ChromeOS {
+                "\"Chrome OS\""
+            } else {
+                get_sec_ch_ua_platform()
+            }
+            .into(),
+            platform_version: if linux_agent {
+                CHROME_PLATFORM_LINUX_VERSION.to_string()
+            } else if agent_os == AgentOs::ChromeOS {
+                format!(
+                    "\"{}\"",
+                    chrome_os_platform_version(info.major.unwrap_or_default())
+                )
             } else {
-                parts.push(chromium_version);
-                parts.push(not_a_brand_label);
-                parts.push(google_chrome_major);
+                CHROME_PLATFORM_VERSION.to_string()
+            },
+            architecture: if linux_agent {
+                "x86_64"
+            } else {
+                get_sec_ch_ua_arch()
             }
+            .into(),
+            bitness: get_sec_ch_ua_bitness().into(),
+            model: "\"\"".into(),
+            wow64: false,
         }
     }
-
-    parts.join(", ").trim_end().into()
 }
 
 #[cfg(target_os = "macos")]
@@ -210,11 +254,11 @@ impl HeaderKey {
 
 /// Add a spoofed Referer header from Google or a realistic domain,
 /// or sometimes intentionally omit it entirely for privacy realism.
-pub fn maybe_insert_spoofed_referer(
+pub fn maybe_insert_spoofed_referer<R: Rng>(
     domain_parsed: Option<&url::Url>,
-    rng: &mut rand::rngs::ThreadRng,
+    rng: &mut R,
 ) -> Option<HeaderValue> {
-    use crate::spoof_refererer::{spoof_referrer, spoof_referrer_google};
+    use crate::spoof_refererer::{spoof_referrer_google, spoof_referrer_rng};
     let chance: f64 = rng.random();
 
     if chance < 0.50 {
@@ -230,7 +274,7 @@ pub fn maybe_insert_spoofed_referer(
         if rng.random_bool(0.50) {
             HeaderValue::from_static("https://google.com/").into()
         } else {
-            HeaderValue::from_static(spoof_referrer()).into()
+            HeaderValue::from_static(spoof_referrer_rng(rng)).into()
         }
     } else {
         None
@@ -239,15 +283,15 @@ pub fn maybe_insert_spoofed_referer(
 
 /// Add a spoofed Referer header from Google or a realistic domain,
 /// or sometimes intentionally omit it entirely for privacy realism.
-pub fn maybe_insert_spoofed_referer_simple(rng: &mut rand::rngs::ThreadRng) -> Option<HeaderValue> {
-    use crate::spoof_refererer::spoof_referrer;
+pub fn maybe_insert_spoofed_referer_simple<R: Rng>(rng: &mut R) -> Option<HeaderValue> {
+    use crate::spoof_refererer::spoof_referrer_rng;
     let chance: f64 = rng.random();
 
     if chance < 0.50 {
         if rng.random_bool(0.35) {
             HeaderValue::from_static("https://google.com/").into()
         } else {
-            HeaderValue::from_static(spoof_referrer()).into()
+            HeaderValue::from_static(spoof_referrer_rng(rng)).into()
         }
     } else {
         None
@@ -279,6 +323,99 @@ pub enum HeaderDetailLevel {
 const DEFAULT_ACCEPT_HEADER: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8";
 const DEFAULT_ACCEPT_HEADER_SIGNED: &str = "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7";
 
+/// The random choices made while building the http headers.
+///
+/// Rolling the choices once and storing them keeps the headers identical across requests
+/// and process restarts when rendered with [`emulate_headers_with_choices`].
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HeaderChoices {
+    /// The Device-Memory header value.
+    pub device_memory: f64,
+    /// The Sec-CH-DPR header value.
+    pub device_pixel_ratio: f64,
+    /// The Downlink header value in mbps.
+    pub downlink: f64,
+    /// Send the Accept-Language header for chrome.
+    pub accept_language: bool,
+    /// The spoofed referer, if any.
+    pub referer: Option<String>,
+    /// Send the Viewport-Width header.
+    pub viewport_width: bool,
+    /// The Viewport-Width used when the viewport has no width.
+    pub fallback_viewport_width: u32,
+    /// Send the Priority header.
+    pub priority: bool,
+    /// Prefer reduced motion.
+    pub reduced_motion: bool,
+    /// Prefer the dark color scheme.
+    pub dark_color_scheme: bool,
+}
+
+impl HeaderChoices {
+    /// Roll the header choices.
+    pub fn random(user_agent: &str, chrome: bool, domain_parsed: &Option<Box<url::Url>>) -> Self {
+        Self::random_rng(user_agent, chrome, domain_parsed, &mut rng())
+    }
+
+    /// Roll the header choices with the provided rng.
+    pub fn random_rng<R: Rng>(
+        user_agent: &str,
+        chrome: bool,
+        domain_parsed: &Option<Box<url::Url>>,
+        rng: &mut R,
+    ) -> Self {
+        Self::random_from_info_rng(&parse_user_agent(user_agent), chrome, domain_parsed, rng)
+    }
+
+    /// Roll the header choices for the parsed user agent with the provided rng.
+    pub fn random_from_info_rng<R: Rng>(
+        info: &UserAgentInfo,
+        chrome: bool,
+        domain_parsed: &Option<Box<url::Url>>,
+        rng: &mut R,
+    ) -> Self {
+        let device = DeviceProfile::random_rng(info.os, rng);
+        let downlink = rng.random_range(0.1..=10.0);
+
+        let referer = if chrome && info.browser == BrowserKind::Chrome {
+            maybe_insert_spoofed_referer_simple(rng)
+        } else {
+            maybe_insert_spoofed_referer(domain_parsed.as_deref(), rng)
+        };
+
+        Self {
+            device_memory: device.device_memory.into(),
+            device_pixel_ratio: device.device_pixel_ratio,
+            downlink,
+            referer: referer.and_then(|r| r.to_str().ok().map(String::from)),
+            accept_language: rng.random_bool(0.4),
+            viewport_width: rng.random(),
+            fallback_viewport_width: device.screen_width,
+            priority: rng.random(),
+            reduced_motion: !rng.random::<bool>(),
+            dark_color_scheme: !rng.random::<bool>(),
+        }
+    }
+
+    /// The referer header value.
+    fn referer_header(&self) -> Option<HeaderValue> {
+        self.referer
+            .as_deref()
+            .and_then(|r| HeaderValue::from_str(r).ok())
+    }
+
+    /// Use the memory and screen of the device so the headers match the script.
+    pub fn with_device(self, device: &DeviceProfile) -> Self {
+        Self {
+            device_memory: device.device_memory.into(),
+            device_pixel_ratio: device.device_pixel_ratio,
+            fallback_viewport_width: device.screen_width,
+            ..self
+        }
+    }
+}
+
 /// Emulate real HTTP chrome headers.
 pub fn emulate_headers(
     user_agent: &str,
@@ -288,6 +425,122 @@ pub fn emulate_headers(
     viewport: &Option<crate::spoof_viewport::Viewport>,
     domain_parsed: &Option<Box<url::Url>>,
     detail_level: &Option<HeaderDetailLevel>,
+) -> HeaderMap {
+    emulate_headers_with_locale(
+        user_agent,
+        header_map,
+        hostname,
+        chrome,
+        viewport,
+        domain_parsed,
+        detail_level,
+        None,
+    )
+}
+
+/// Emulate real HTTP chrome headers. Pass the `locale` of the emulation configuration so the
+/// `Accept-Language` header matches `navigator.languages`.
+#[allow(clippy::too_many_arguments)]
+pub fn emulate_headers_with_locale(
+    user_agent: &str,
+    header_map: &std::option::Option<&HeaderMap>,
+    hostname: &Option<&str>,
+    chrome: bool, // incase HeaderMap allows case handling and ignoring the referer handling.
+    viewport: &Option<crate::spoof_viewport::Viewport>,
+    domain_parsed: &Option<Box<url::Url>>,
+    detail_level: &Option<HeaderDetailLevel>,
+    locale: Option<crate::spoof_locale::Locale>,
+) -> HeaderMap {
+    emulate_headers_rng(
+        user_agent,
+        header_map,
+        hostname,
+        chrome,
+        viewport,
+        domain_parsed,
+        detail_level,
+        locale,
+        &mut rng(),
+    )
+}
+
+/// Emulate real HTTP chrome headers drawing every random choice from the provided rng.
+/// The client hints come from the high entropy values drawn first from the rng, the same
+/// values the script draws for `navigator.userAgentData` from a rng in the same state.
+#[allow(clippy::too_many_arguments)]
+pub fn emulate_headers_rng<R: Rng>(
+    user_agent: &str,
+    header_map: &std::option::Option<&HeaderMap>,
+    hostname: &Option<&str>,
+    chrome: bool, // incase HeaderMap allows case handling and ignoring the referer handling.
+    viewport: &Option<crate::spoof_viewport::Viewport>,
+    domain_parsed: &Option<Box<url::Url>>,
+    detail_level: &Option<HeaderDetailLevel>,
+    locale: Option<crate::spoof_locale::Locale>,
+    rng: &mut R,
+) -> HeaderMap {
+    let info = parse_user_agent(user_agent);
+    let high_entropy = info
+        .allows_high_entropy()
+        .then(|| build_high_entropy_data_from_info_rng(&info, rng));
+    let choices = HeaderChoices::random_from_info_rng(&info, chrome, domain_parsed, rng);
+
+    emulate_headers_with_info(
+        user_agent,
+        &info,
+        header_map,
+        hostname,
+        chrome,
+        viewport,
+        detail_level,
+        locale,
+        high_entropy.as_ref(),
+        &choices,
+    )
+}
+
+/// Emulate real HTTP chrome headers from pre-rolled choices without drawing any randomness.
+/// The locale always sends a matching `Accept-Language` header. The high entropy values drive
+/// every `sec-ch-ua-*` header so they match `navigator.userAgentData`.
+#[allow(clippy::too_many_arguments)]
+pub fn emulate_headers_with_choices(
+    user_agent: &str,
+    header_map: &std::option::Option<&HeaderMap>,
+    hostname: &Option<&str>,
+    chrome: bool, // incase HeaderMap allows case handling and ignoring the referer handling.
+    viewport: &Option<crate::spoof_viewport::Viewport>,
+    detail_level: &Option<HeaderDetailLevel>,
+    locale: Option<crate::spoof_locale::Locale>,
+    high_entropy: Option<&HighEntropyUaData>,
+    choices: &HeaderChoices,
+) -> HeaderMap {
+    emulate_headers_with_info(
+        user_agent,
+        &parse_user_agent(user_agent),
+        header_map,
+        hostname,
+        chrome,
+        viewport,
+        detail_level,
+        locale,
+        high_entropy,
+        choices,
+    )
+}
+
+/// Emulate real HTTP chrome headers for the parsed user agent from pre-rolled choices.
+#[allow(clippy::too_many_arguments)]
+fn emulate_headers_with_info(
+    user_agent: &str,
+    info: &UserAgentInfo,
+    header_map: &std::option::Option<&HeaderMap>,
+    hostname: &Option<&str>,
+    chrome: bool,
+    viewport: &Option<crate::spoof_viewport::Viewport>,
+    detail_level: &Option<HeaderDetailLevel>,
+    locale: Option<crate::spoof_locale::Locale>,
+    high_entropy: Option<&HighEntropyUaData>,
+    choices: &HeaderChoices,
 ) -> HeaderMap {
     let empty = matches!(detail_level, Some(HeaderDetailLevel::Empty));
 
@@ -295,7 +548,7 @@ pub fn emulate_headers(
         return Default::default();
     }
 
-    let browser = detect_browser_kind(user_agent);
+    let browser = info.browser;
 
     let cap = if browser == BrowserKind::Chrome {
         31
@@ -352,13 +605,11 @@ pub fn emulate_headers(
     }
 
     match browser {
-        BrowserKind::Chrome | BrowserKind::Opera | BrowserKind::Brave => {
-            let agent_os = get_agent_os(user_agent);
+        BrowserKind::Chrome | BrowserKind::Opera | BrowserKind::Brave | BrowserKind::Edge => {
+            let agent_os = info.os;
 
             let linux_agent = agent_os == AgentOs::Linux;
 
-            let mut thread_rng = rng();
-
             // if not a chrome request we should stick to the headers from request to prevent duplications.
             let (
                 host_header,
@@ -399,15 +650,14 @@ pub fn emulate_headers(
                 )
             };
 
-            let memory_levels = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0];
-            let device_memory = memory_levels[thread_rng.random_range(0..memory_levels.len())];
-            let device_memory_str = format!("{}", device_memory);
-            let downlink_mbps = thread_rng.random_range(0.1..=10.0);
-            let downlink_str = format!("{:.1}", downlink_mbps);
-            let is_mobile = if let Some(vp) = viewport {
-                vp.emulating_mobile
-            } else {
-                false
+            let device_memory_str = format!("{}", choices.device_memory);
+            let downlink_str = format!("{:.1}", choices.downlink);
+            let hints = match high_entropy {
+                Some(data) => ClientHints::from_high_entropy(data),
+                _ => ClientHints::from_user_agent(
+                    info,
+                    viewport.is_some_and(|vp| vp.emulating_mobile),
+                ),
             };
 
             // 1. Host
@@ -430,32 +680,17 @@ pub fn emulate_headers(
 
             // 3. sec-ch-ua group
             if !user_agent.is_empty() {
-                if let Ok(sec_ch_ua) = HeaderValue::from_str(&parse_user_agent_to_ch_ua(
-                    user_agent,
-                    false,
-                    linux_agent,
-                )) {
+                if let Ok(sec_ch_ua) = HeaderValue::from_str(&hints.sec_ch_ua) {
                     insert_or_default!("sec-ch-ua", sec_ch_ua);
                 }
             }
             insert_or_default!(
                 "sec-ch-ua-mobile",
-                HeaderValue::from_static(if is_mobile { "?1" } else { "?0" })
-            );
-            insert_or_default!(
-                "sec-ch-ua-platform",
-                HeaderValue::from_static(if linux_agent {
-                    "\"Linux\""
-                } else if agent_os == AgentOs::Mac {
-                    "\"macOS\""
-                } else if agent_os == AgentOs::Windows {
-                    "\"Windows\""
-                } else if agent_os == AgentOs::Android {
-                    "\"Android\""
-                } else {
-                    get_sec_ch_ua_platform()
-                })
+                HeaderValue::from_static(if hints.mobile { "?1" } else { "?0" })
             );
+            if let Ok(platform) = HeaderValue::from_str(&hints.platform) {
+                insert_or_default!("sec-ch-ua-platform", platform);
+            }
             // 4. Upgrade-Insecure-Requests
             if mimic {
                 insert_or_default!(
@@ -489,16 +724,8 @@ pub fn emulate_headers(
             // We want to set the referer with our page navigation or on interception remove the referer headers.
             // For now it is better for chrome not to use the referer unless you can re-order the headers with a http proxy.
             if add_ref {
-                if chrome {
-                    if let Some(ref_header) = maybe_insert_spoofed_referer_simple(&mut thread_rng) {
-                        insert_or_default!(&refererer_header.as_header_name(), ref_header);
-                    }
-                } else {
-                    if let Some(ref_header) =
-                        maybe_insert_spoofed_referer(domain_parsed.as_deref(), &mut thread_rng)
-                    {
-                        insert_or_default!(&refererer_header.as_header_name(), ref_header);
-                    }
+                if let Some(ref_header) = choices.referer_header() {
+                    insert_or_default!(&refererer_header.as_header_name(), ref_header);
                 }
             }
 
@@ -508,7 +735,11 @@ pub fn emulate_headers(
                 HeaderValue::from_static("gzip, deflate, br, zstd")
             );
 
-            if thread_rng.random_bool(0.4) {
+            if let Some(locale) = locale {
+                if let Ok(lang) = HeaderValue::from_str(&locale.accept_language(browser)) {
+                    insert_or_default!(&accept_language.as_header_name(), lang);
+                }
+            } else if choices.accept_language {
                 insert_or_default!(
                     &accept_language.as_header_name(),
                     HeaderValue::from_static(get_accept_language())
@@ -537,21 +768,20 @@ pub fn emulate_headers(
             }
 
             if extensive || linux_agent {
-                insert_or_default!("Sec-CH-DPR", HeaderValue::from_static("2"));
+                let dpr = viewport
+                    .as_ref()
+                    .and_then(|vp| vp.device_scale_factor)
+                    .unwrap_or(choices.device_pixel_ratio);
+                if let Ok(dpr) = HeaderValue::from_str(&format!("{dpr}")) {
+                    insert_or_default!("Sec-CH-DPR", dpr);
+                }
                 // this should be sent internally.
-                if thread_rng.random() {
+                if choices.viewport_width {
                     if let Some(vp) = viewport {
                         let width = if vp.width > 0 {
                             format!("{}", vp.width)
                         } else {
-                            format!(
-                                "{}",
-                                crate::spoof_viewport::randomize_viewport_rng(
-                                    &crate::spoof_viewport::DeviceType::Desktop,
-                                    &mut thread_rng
-                                )
-                                .width
-                            )
+                            format!("{}", choices.fallback_viewport_width)
                         };
 
                         if let Ok(width) = HeaderValue::from_str(&width) {
@@ -564,7 +794,7 @@ pub fn emulate_headers(
             }
 
             // this should be set auto
-            if linux_agent && extensive && thread_rng.random() {
+            if linux_agent && extensive && choices.priority {
                 insert_or_default!("Priority", HeaderValue::from_static("u=0, i"));
             }
 
@@ -578,38 +808,27 @@ pub fn emulate_headers(
 
             if extensive || linux_agent {
                 // 11. Extra client hints (real Chrome includes some of these)
-                if let Ok(ua_full_list) =
-                    HeaderValue::from_str(&parse_user_agent_to_ch_ua(user_agent, true, linux_agent))
-                {
+                if let Ok(ua_full_list) = HeaderValue::from_str(&hints.full_version_list) {
                     insert_or_default!("sec-ch-ua-full-version-list", ua_full_list);
                 }
-                if let Ok(sec_ch_platform) = HeaderValue::from_str(if linux_agent {
-                    &CHROME_PLATFORM_LINUX_VERSION
-                } else {
-                    &CHROME_PLATFORM_VERSION
-                }) {
+                if let Ok(sec_ch_platform) = HeaderValue::from_str(&hints.platform_version) {
                     insert_or_default!("sec-ch-ua-platform-version", sec_ch_platform);
                 }
             }
 
             if mild || linux_agent {
-                insert_or_default!("sec-ch-ua-model", HeaderValue::from_static("\"\""));
-                insert_or_default!(
-                    "sec-ch-ua-arc",
-                    HeaderValue::from_static(if linux_agent {
-                        "x86_64"
-                    } else {
-                        get_sec_ch_ua_arch()
-                    })
-                );
-                insert_or_default!(
-                    "sec-ch-ua-bitness",
-                    HeaderValue::from_static(get_sec_ch_ua_bitness())
-                );
-                // TODO: parse the user-agent for mobile or desktop
+                if let Ok(model) = HeaderValue::from_str(&hints.model) {
+                    insert_or_default!("sec-ch-ua-model", model);
+                }
+                if let Ok(architecture) = HeaderValue::from_str(&hints.architecture) {
+                    insert_or_default!("sec-ch-ua-arch", architecture);
+                }
+                if let Ok(bitness) = HeaderValue::from_str(&hints.bitness) {
+                    insert_or_default!("sec-ch-ua-bitness", bitness);
+                }
                 insert_or_default!(
                     "sec-ch-ua-form-factors",
-                    HeaderValue::from_static(if is_mobile {
+                    HeaderValue::from_static(if hints.mobile {
                         r#""Mobile""#
                     } else {
                         r#""Desktop""#
@@ -618,18 +837,25 @@ pub fn emulate_headers(
             }
 
             if extensive || linux_agent {
-                insert_or_default!("sec-ch-ua-wow64", HeaderValue::from_static("?0"));
+                insert_or_default!(
+                    "sec-ch-ua-wow64",
+                    HeaderValue::from_static(if hints.wow64 { "?1" } else { "?0" })
+                );
                 insert_or_default!(
                     "sec-ch-prefers-reduced-motion",
-                    HeaderValue::from_static(if thread_rng.random() {
-                        "no-preference"
-                    } else {
+                    HeaderValue::from_static(if choices.reduced_motion {
                         "reduced"
+                    } else {
+                        "no-preference"
                     })
                 );
                 insert_or_default!(
                     "sec-ch-prefers-color-scheme",
-                    HeaderValue::from_static(if thread_rng.random() { "light" } else { "dark" })
+                    HeaderValue::from_static(if choices.dark_color_scheme {
+                        "dark"
+                    } else {
+                        "light"
+                    })
                 );
             }
         }
@@ -646,14 +872,17 @@ pub fn emulate_headers(
             );
 
             if add_ref {
-                if let Some(ref_header) =
-                    maybe_insert_spoofed_referer(domain_parsed.as_deref(), &mut rand::rng())
-                {
+                if let Some(ref_header) = choices.referer_header() {
                     insert_or_default!(REFERER, ref_header);
                 }
             }
 
-            insert_or_default!(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.5"));
+            match locale.and_then(|l| HeaderValue::from_str(&l.accept_language(browser)).ok()) {
+                Some(lang) => insert_or_default!(ACCEPT_LANGUAGE, lang),
+                _ => {
+                    insert_or_default!(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US,en;q=0.5"))
+                }
+            }
             insert_or_default!(
                 ACCEPT_ENCODING,
                 HeaderValue::from_static("gzip, deflate, br, zstd")
@@ -675,20 +904,24 @@ pub fn emulate_headers(
             );
 
             if add_ref {
-                if let Some(ref_header) =
-                    maybe_insert_spoofed_referer(domain_parsed.as_deref(), &mut rng())
-                {
+                if let Some(ref_header) = choices.referer_header() {
                     insert_or_default!(REFERER, ref_header);
                 }
             }
 
+            if let Some(lang) =
+                locale.and_then(|l| HeaderValue::from_str(&l.accept_language(browser)).ok())
+            {
+                insert_or_default!(ACCEPT_LANGUAGE, lang);
+            }
+
             insert_or_default!(UPGRADE_INSECURE_REQUESTS, HeaderValue::from_static("1"));
 
             if let Ok(ua) = HeaderValue::from_str(user_agent) {
                 insert_or_default!(USER_AGENT, ua);
             }
         }
-        BrowserKind::Edge | BrowserKind::Other => {
+        BrowserKind::Other => {
             insert_or_default!(
                 ACCEPT,
                 HeaderValue::from_static(
@@ -697,13 +930,17 @@ pub fn emulate_headers(
             );
 
             if add_ref {
-                if let Some(ref_header) =
-                    maybe_insert_spoofed_referer(domain_parsed.as_deref(), &mut rng())
-                {
+                if let Some(ref_header) = choices.referer_header() {
                     insert_or_default!(REFERER, ref_header);
                 }
             }
 
+            if let Some(lang) =
+                locale.and_then(|l| HeaderValue::from_str(&l.accept_language(browser)).ok())
+            {
+                insert_or_default!(ACCEPT_LANGUAGE, lang);
+            }
+
             insert_or_default!(UPGRADE_INSECURE_REQUESTS, HeaderValue::from_static("1"));
 
             if let Ok(ua) = HeaderValue::from_str(user_agent) {
@@ -965,6 +1202,8 @@ mod tests {
     use crate::spoof_viewport::Viewport;
     use http::header::{ACCEPT, HOST, USER_AGENT};
     use http::HeaderMap;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
     use url::Url;
 
     #[test]
@@ -980,7 +1219,7 @@ mod tests {
 
         let domain_parsed = Url::parse("https://example.com").ok().map(Box::new);
 
-        let headers = emulate_headers(
+        let headers = emulate_headers_rng(
             user_agent,
             &None, // No initial headers
             &hostname,
@@ -988,6 +1227,8 @@ mod tests {
             &viewport,
             &domain_parsed,
             &None, // default to extensive headers
+            None,
+            &mut StdRng::seed_from_u64(1), // a roll sending Viewport-Width
         );
 
         // Check host header
@@ -1014,6 +1255,64 @@ mod tests {
         assert_eq!(headers.get("Viewport-Width").unwrap(), "1920");
     }
 
+    #[test]
+    fn headers_take_the_memory_and_dpr_from_the_device() {
+        let user_agent = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut rng = StdRng::seed_from_u64(2);
+        let headers = emulate_headers_rng(
+            user_agent,
+            &None,
+            &Some("example.com"),
+            false,
+            &None,
+            &None,
+            &Some(HeaderDetailLevel::Extensive),
+            None,
+            &mut rng,
+        );
+        let header = |headers: &HeaderMap, name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        assert!(header(&headers, "Device-Memory").is_some());
+        assert!(header(&headers, "Sec-CH-DPR").is_some());
+
+        let device = DeviceProfile {
+            device_pixel_ratio: 1.25,
+            ..DeviceProfile::random_rng(AgentOs::Linux, &mut rng)
+        };
+        let choices =
+            HeaderChoices::random_rng(user_agent, false, &None, &mut rng).with_device(&device);
+        let headers_for = |viewport: &Option<Viewport>| {
+            emulate_headers_with_choices(
+                user_agent,
+                &None,
+                &Some("example.com"),
+                false,
+                viewport,
+                &Some(HeaderDetailLevel::Extensive),
+                None,
+                None,
+                &choices,
+            )
+        };
+        let headers = headers_for(&None);
+        assert_eq!(
+            header(&headers, "Device-Memory"),
+            Some(device.device_memory.to_string())
+        );
+        assert_eq!(header(&headers, "Sec-CH-DPR").as_deref(), Some("1.25"));
+
+        let viewport = Viewport {
+            device_scale_factor: Some(2.0),
+            ..Viewport::new(1280, 800)
+        };
+        let headers = headers_for(&Some(viewport));
+        assert_eq!(header(&headers, "Sec-CH-DPR").as_deref(), Some("2"));
+    }
+
     #[test]
     fn test_emulate_headers_existing_headers_merging() {
         let user_agent = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15";
@@ -1149,4 +1448,100 @@ mod tests {
         let ua = "moZILLa/5.0 (WiNdOwS) CHROME/123.0 SAFARI/537.36";
         assert_eq!(detect_browser(ua), "chrome");
     }
+
+    #[test]
+    fn locale_drives_accept_language() {
+        use crate::spoof_locale::Locale;
+
+        let chrome = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let firefox =
+            "Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:124.0) Gecko/20100101 Firefox/124.0";
+        let choices = HeaderChoices::default();
+
+        for (ua, expected) in [
+            (chrome, "de-DE,de;q=0.9,en-US;q=0.8,en;q=0.7"),
+            (firefox, "de-DE,de;q=0.8,en-US;q=0.5,en;q=0.3"),
+        ] {
+            let headers = emulate_headers_with_choices(
+                ua,
+                &None,
+                &None,
+                false,
+                &None,
+                &None,
+                Some(Locale::DeDE),
+                None,
+                &choices,
+            );
+            assert_eq!(headers.get("Accept-Language").unwrap(), expected);
+
+            let headers = emulate_headers_with_locale(
+                ua,
+                &None,
+                &None,
+                false,
+                &None,
+                &None,
+                &None,
+                Some(Locale::DeDE),
+            );
+            assert_eq!(headers.get("Accept-Language").unwrap(), expected);
+
+            let config = crate::EmulationConfiguration {
+                locale: Some(Locale::DeDE),
+                ..crate::EmulationConfiguration::setup_defaults(ua)
+            };
+            let headers = crate::Identity::new(1)
+                .emulate_headers(ua, &config, &None, &None, false, &None, &None, &None);
+            assert_eq!(headers.get("Accept-Language").unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_emulate_headers_chrome_os() {
+        let user_agent = "Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let headers = emulate_headers(
+            user_agent,
+            &None,
+            &Some("example.com"),
+            true,
+            &None,
+            &None,
+            &Some(HeaderDetailLevel::Extensive),
+        );
+
+        assert_eq!(headers.get("sec-ch-ua-platform").unwrap(), "\"Chrome OS\"");
+        assert_eq!(
+            headers.get("sec-ch-ua-platform-version").unwrap(),
+            "\"16295.54.0\""
+        );
+    }
+
+    #[test]
+    fn test_emulate_headers_fork_brands() {
+        let base = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+
+        for (suffix, brand) in [
+            (" Edg/138.0.3351.95", r#""Microsoft Edge";v="138""#),
+            (" OPR/122.0.0.0", r#""Opera";v="122""#),
+            (" Brave/138", r#""Brave";v="138""#),
+            ("", r#""Google Chrome";v="138""#),
+        ] {
+            let user_agent = format!("{base}{suffix}");
+            let headers = emulate_headers(
+                &user_agent,
+                &None,
+                &Some("example.com"),
+                true,
+                &None,
+                &None,
+                &Some(HeaderDetailLevel::Extensive),
+            );
+
+            let sec_ch_ua = headers.get("sec-ch-ua").unwrap().to_str().unwrap();
+            assert!(sec_ch_ua.contains(brand), "{sec_ch_ua}");
+            assert!(sec_ch_ua.contains(r#""Chromium";v="138""#), "{sec_ch_ua}");
+            assert!(headers.contains_key("sec-ch-ua-full-version-list"));
+        }
+    }
 }
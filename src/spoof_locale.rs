@@ -0,0 +1,315 @@
+use crate::BrowserKind;
+
+/// The locale of the browser. Drives `navigator.language(s)`, the `Intl` defaults and the
+/// `Accept-Language` header.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Locale {
+    #[default]
+    /// English (United States).
+    #[cfg_attr(feature = "serde", serde(rename = "en-US"))]
+    EnUS,
+    /// English (United Kingdom).
+    #[cfg_attr(feature = "serde", serde(rename = "en-GB"))]
+    EnGB,
+    /// English (Canada).
+    #[cfg_attr(feature = "serde", serde(rename = "en-CA"))]
+    EnCA,
+    /// English (Australia).
+    #[cfg_attr(feature = "serde", serde(rename = "en-AU"))]
+    EnAU,
+    /// English (India).
+    #[cfg_attr(feature = "serde", serde(rename = "en-IN"))]
+    EnIN,
+    /// German (Germany).
+    #[cfg_attr(feature = "serde", serde(rename = "de-DE"))]
+    DeDE,
+    /// French (France).
+    #[cfg_attr(feature = "serde", serde(rename = "fr-FR"))]
+    FrFR,
+    /// French (Canada).
+    #[cfg_attr(feature = "serde", serde(rename = "fr-CA"))]
+    FrCA,
+    /// Spanish (Spain).
+    #[cfg_attr(feature = "serde", serde(rename = "es-ES"))]
+    EsES,
+    /// Spanish (Mexico).
+    #[cfg_attr(feature = "serde", serde(rename = "es-MX"))]
+    EsMX,
+    /// Italian (Italy).
+    #[cfg_attr(feature = "serde", serde(rename = "it-IT"))]
+    ItIT,
+    /// Portuguese (Brazil).
+    #[cfg_attr(feature = "serde", serde(rename = "pt-BR"))]
+    PtBR,
+    /// Portuguese (Portugal).
+    #[cfg_attr(feature = "serde", serde(rename = "pt-PT"))]
+    PtPT,
+    /// Dutch (Netherlands).
+    #[cfg_attr(feature = "serde", serde(rename = "nl-NL"))]
+    NlNL,
+    /// Polish (Poland).
+    #[cfg_attr(feature = "serde", serde(rename = "pl-PL"))]
+    PlPL,
+    /// Swedish (Sweden).
+    #[cfg_attr(feature = "serde", serde(rename = "sv-SE"))]
+    SvSE,
+    /// Turkish (Turkey).
+    #[cfg_attr(feature = "serde", serde(rename = "tr-TR"))]
+    TrTR,
+    /// Russian (Russia).
+    #[cfg_attr(feature = "serde", serde(rename = "ru-RU"))]
+    RuRU,
+    /// Japanese (Japan).
+    #[cfg_attr(feature = "serde", serde(rename = "ja-JP"))]
+    JaJP,
+    /// Korean (South Korea).
+    #[cfg_attr(feature = "serde", serde(rename = "ko-KR"))]
+    KoKR,
+    /// Chinese (China).
+    #[cfg_attr(feature = "serde", serde(rename = "zh-CN"))]
+    ZhCN,
+    /// Chinese (Taiwan).
+    #[cfg_attr(feature = "serde", serde(rename = "zh-TW"))]
+    ZhTW,
+}
+
+/// Every supported locale.
+pub const LOCALES: &[Locale] = &[
+    Locale::EnUS,
+    Locale::EnGB,
+    Locale::EnCA,
+    Locale::EnAU,
+    Locale::EnIN,
+    Locale::DeDE,
+    Locale::FrFR,
+    Locale::FrCA,
+    Locale::EsES,
+    Locale::EsMX,
+    Locale::ItIT,
+    Locale::PtBR,
+    Locale::PtPT,
+    Locale::NlNL,
+    Locale::PlPL,
+    Locale::SvSE,
+    Locale::TrTR,
+    Locale::RuRU,
+    Locale::JaJP,
+    Locale::KoKR,
+    Locale::ZhCN,
+    Locale::ZhTW,
+];
+
+impl Locale {
+    /// The BCP 47 language tag e.g. `en-US`.
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::EnUS => "en-US",
+            Locale::EnGB => "en-GB",
+            Locale::EnCA => "en-CA",
+            Locale::EnAU => "en-AU",
+            Locale::EnIN => "en-IN",
+            Locale::DeDE => "de-DE",
+            Locale::FrFR => "fr-FR",
+            Locale::FrCA => "fr-CA",
+            Locale::EsES => "es-ES",
+            Locale::EsMX => "es-MX",
+            Locale::ItIT => "it-IT",
+            Locale::PtBR => "pt-BR",
+            Locale::PtPT => "pt-PT",
+            Locale::NlNL => "nl-NL",
+            Locale::PlPL => "pl-PL",
+            Locale::SvSE => "sv-SE",
+            Locale::TrTR => "tr-TR",
+            Locale::RuRU => "ru-RU",
+            Locale::JaJP => "ja-JP",
+            Locale::KoKR => "ko-KR",
+            Locale::ZhCN => "zh-CN",
+            Locale::ZhTW => "zh-TW",
+        }
+    }
+
+    /// The ISO 3166-1 alpha-2 region of the locale e.g. `US`.
+    pub fn region(&self) -> &'static str {
+        let tag = self.tag();
+        &tag[tag.len() - 2..]
+    }
+
+    /// Get the locale from a language tag. Matching is case-insensitive and accepts `_`.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let tag = tag.trim().replace('_', "-");
+        LOCALES
+            .iter()
+            .find(|locale| locale.tag().eq_ignore_ascii_case(&tag))
+            .copied()
+    }
+
+    /// The value of `navigator.languages`. Non-English locales keep English as a fallback
+    /// like a default browser install.
+    pub fn languages(&self) -> &'static [&'static str] {
+        match self {
+            Locale::EnUS => &["en-US", "en"],
+            Locale::EnGB => &["en-GB", "en"],
+            Locale::EnCA => &["en-CA", "en-US", "en"],
+            Locale::EnAU => &["en-AU", "en"],
+            Locale::EnIN => &["en-IN", "en-GB", "en-US", "en"],
+            Locale::DeDE => &["de-DE", "de", "en-US", "en"],
+            Locale::FrFR => &["fr-FR", "fr", "en-US", "en"],
+            Locale::FrCA => &["fr-CA", "fr", "en-CA", "en"],
+            Locale::EsES => &["es-ES", "es", "en-US", "en"],
+            Locale::EsMX => &["es-MX", "es", "en-US", "en"],
+            Locale::ItIT => &["it-IT", "it", "en-US", "en"],
+            Locale::PtBR => &["pt-BR", "pt", "en-US", "en"],
+            Locale::PtPT => &["pt-PT", "pt", "en-US", "en"],
+            Locale::NlNL => &["nl-NL", "nl", "en-US", "en"],
+            Locale::PlPL => &["pl-PL", "pl", "en-US", "en"],
+            Locale::SvSE => &["sv-SE", "sv", "en-US", "en"],
+            Locale::TrTR => &["tr-TR", "tr", "en-US", "en"],
+            Locale::RuRU => &["ru-RU", "ru", "en-US", "en"],
+            Locale::JaJP => &["ja-JP", "ja", "en-US", "en"],
+            Locale::KoKR => &["ko-KR", "ko", "en-US", "en"],
+            Locale::ZhCN => &["zh-CN", "zh", "en-US", "en"],
+            Locale::ZhTW => &["zh-TW", "zh", "en-US", "en"],
+        }
+    }
+
+    /// The `Accept-Language` header with the q-values the browser sends.
+    ///
+    /// Chromium lowers the weight by `0.1` per language while Firefox spreads the weights
+    /// evenly e.g. `en-US,en;q=0.5`.
+    pub fn accept_language(&self, browser: BrowserKind) -> String {
+        let languages = self.languages();
+        let mut header = String::with_capacity(64);
+
+        for (i, language) in languages.iter().enumerate() {
+            if i > 0 {
+                header.push(',');
+            }
+            header.push_str(language);
+
+            if i > 0 {
+                let q = if browser == BrowserKind::Firefox {
+                    ((1.0 - i as f32 / languages.len() as f32) * 10.0).round() / 10.0
+                } else {
+                    (1.0 - i as f32 * 0.1).max(0.1)
+                };
+                header.push_str(&format!(";q={:.1}", q));
+            }
+        }
+
+        header
+    }
+}
+
+/// Spoof `navigator.language`, `navigator.languages` and the default locale of the `Intl`
+/// constructors (`DateTimeFormat`, `NumberFormat`, `Collator`, `PluralRules`, `RelativeTimeFormat`,
+/// `ListFormat`, `Segmenter` and `DisplayNames`), `localeCompare` and the `toLocale*String` methods.
+pub fn spoof_locale_script(locale: Locale) -> String {
+    let languages = locale
+        .languages()
+        .iter()
+        .map(|l| format!("'{l}'"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"(()=>{{const l='{tag}',v=Object.freeze([{languages}]),gl=function(){{return v}},g1=function(){{return l}};gl.toString=()=>`function get languages() {{ [native code] }}`;g1.toString=()=>`function get language() {{ [native code] }}`;const d=o=>{{try{{Object.defineProperty(o,'languages',{{get:gl,enumerable:!0,configurable:!0}});Object.defineProperty(o,'language',{{get:g1,enumerable:!0,configurable:!0}})}}catch(e){{}}}};d(Navigator.prototype);if(typeof WorkerNavigator!=='undefined')d(WorkerNavigator.prototype);const w=(C,n)=>{{if(!C)return;const f=function(...a){{if(a[0]===undefined)a[0]=l;return new.target?Reflect.construct(C,a,new.target):C(...a)}};f.prototype=C.prototype;Object.defineProperty(C.prototype,'constructor',{{value:f,writable:!0,configurable:!0}});Object.defineProperty(f,'name',{{value:n}});Object.defineProperty(f,'supportedLocalesOf',{{value:C.supportedLocalesOf,writable:!0,configurable:!0}});f.toString=()=>`function ${{n}}() {{ [native code] }}`;Object.defineProperty(Intl,n,{{value:f,writable:!0,configurable:!0}})}};for(const n of['DateTimeFormat','NumberFormat','Collator','PluralRules','RelativeTimeFormat','ListFormat','Segmenter','DisplayNames'])w(Intl[n],n);const t=(P,k)=>{{const o=P[k],f={{[k](a,...r){{return o.call(this,a===undefined?l:a,...r)}}}}[k];f.toString=()=>`function ${{k}}() {{ [native code] }}`;Object.defineProperty(P,k,{{value:f,writable:!0,configurable:!0}})}};t(Date.prototype,'toLocaleString');t(Date.prototype,'toLocaleDateString');t(Date.prototype,'toLocaleTimeString');t(Number.prototype,'toLocaleString');const c=String.prototype.localeCompare,lc={{localeCompare(s,a,...r){{return c.call(this,s,a===undefined?l:a,...r)}}}}.localeCompare;Object.defineProperty(lc,'length',{{value:1}});lc.toString=()=>`function localeCompare() {{ [native code] }}`;Object.defineProperty(String.prototype,'localeCompare',{{value:lc,writable:!0,configurable:!0}})}})();"#,
+        tag = locale.tag(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{emulate, EmulationConfiguration};
+
+    #[test]
+    fn accept_language_q_values() {
+        assert_eq!(
+            Locale::EnUS.accept_language(BrowserKind::Chrome),
+            "en-US,en;q=0.9"
+        );
+        assert_eq!(
+            Locale::DeDE.accept_language(BrowserKind::Chrome),
+            "de-DE,de;q=0.9,en-US;q=0.8,en;q=0.7"
+        );
+        assert_eq!(
+            Locale::EnUS.accept_language(BrowserKind::Firefox),
+            "en-US,en;q=0.5"
+        );
+        assert_eq!(
+            Locale::DeDE.accept_language(BrowserKind::Firefox),
+            "de-DE,de;q=0.8,en-US;q=0.5,en;q=0.3"
+        );
+    }
+
+    #[test]
+    fn locale_from_tag() {
+        assert_eq!(Locale::from_tag("de_de"), Some(Locale::DeDE));
+        assert_eq!(Locale::from_tag("ja-JP"), Some(Locale::JaJP));
+        assert_eq!(Locale::from_tag("xx-YY"), None);
+        for locale in LOCALES {
+            assert_eq!(Locale::from_tag(locale.tag()), Some(*locale));
+            assert_eq!(locale.languages()[0], locale.tag());
+        }
+    }
+
+    #[test]
+    fn locale_script_uses_one_source() {
+        let script = spoof_locale_script(Locale::FrFR);
+        assert!(script.contains("const l='fr-FR'"));
+        assert!(script.contains("['fr-FR','fr','en-US','en']"));
+        assert!(script.contains("DateTimeFormat"));
+        assert!(script.contains("NumberFormat"));
+    }
+
+    #[test]
+    fn locale_script_sets_the_intl_defaults() {
+        let script = format!(
+            "globalThis.Navigator=class{{}};{}",
+            spoof_locale_script(Locale::DeDE)
+        );
+        let check = r#"
+            const names = ['DateTimeFormat', 'NumberFormat', 'Collator', 'PluralRules',
+                'RelativeTimeFormat', 'ListFormat', 'Segmenter', 'DisplayNames'];
+            return [
+                names.map(n => new Intl[n](undefined, n === 'DisplayNames' ? { type: 'region' } : {})
+                    .resolvedOptions().locale),
+                Intl.Collator().resolvedOptions().locale,
+                new Intl.RelativeTimeFormat().format(-1, 'day'),
+                new Intl.ListFormat().format(['a', 'b']),
+                'a'.localeCompare('b'),
+                (1234.5).toLocaleString(),
+                [Intl.PluralRules.name, String(Intl.ListFormat), String.prototype.localeCompare.length],
+            ];"#;
+
+        let value = crate::test_js::eval_js(&script, check, "UTC");
+
+        assert_eq!(
+            value,
+            serde_json::json!([
+                // the collation and plural rules resolve to the language like Chrome
+                ["de-DE", "de-DE", "de", "de", "de-DE", "de-DE", "de-DE", "de-DE"],
+                "de",
+                "vor 1 Tag",
+                "a und b",
+                -1,
+                "1.234,5",
+                ["PluralRules", "function ListFormat() { [native code] }", 1]
+            ])
+        );
+    }
+
+    #[test]
+    fn locale_is_spoofed_when_configured() {
+        let ua = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        assert!(!script.contains("Intl,n"));
+
+        config.locale = Some(Locale::JaJP);
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        assert!(script.contains(&spoof_locale_script(Locale::JaJP)));
+    }
+}
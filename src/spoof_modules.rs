@@ -0,0 +1,868 @@
+use crate::configs::{AgentOs, Tier, TIERS};
+use crate::profiles::gpu::GpuProfile;
+use crate::profiles::gpu_limits::{build_gpu_request_adapter_script_from_profile, GpuLimits};
+use crate::spoof_gpu::build_gpu_spoof_script_wgsl_from_profile;
+use crate::spoofs::{
+    spoof_device_memory, spoof_hardware_concurrency, spoof_history_length_script,
+    spoof_media_codecs_script, spoof_media_labels_script, spoof_screen_script_profile,
+    spoof_touch_points_script, spoof_touch_screen, unified_worker_override,
+    unified_worker_override_with_capabilities, worker_override_with_capabilities,
+    CLEANUP_CDP_MARKERS, DISABLE_DIALOGS, HIDE_CHROME, HIDE_CONSOLE, HIDE_SELENIUM_MARKERS,
+    HIDE_WEBDRIVER, NAVIGATOR_SCRIPT, PATCH_SPEECH_SYNTHESIS, PLUGIN_AND_MIMETYPE_SPOOF,
+    PLUGIN_AND_MIMETYPE_SPOOF_CHROME, REMOVE_CHROME, SPOOF_NOTIFICATIONS, SPOOF_PERMISSIONS_QUERY,
+};
+use crate::{is_ipad_desktop_mode, BrowserKind, EmulationConfiguration, Fingerprint, Persona};
+use std::borrow::Cow;
+use std::sync::{Arc, RwLock};
+
+/// The names of the built-in modules.
+pub mod names {
+    /// Seeded audio fingerprint noise.
+    pub const AUDIO: &str = "audio";
+    /// Seeded WebGL `readPixels` and export noise.
+    pub const WEBGL_NOISE: &str = "webgl_noise";
+    /// `speechSynthesis` voices.
+    pub const SPEECH_SYNTHESIS: &str = "speech_synthesis";
+    /// `navigator.userAgentData` high entropy values.
+    pub const USER_AGENT_DATA: &str = "user_agent_data";
+    /// Auto-dismiss `alert`, `confirm` and `prompt`.
+    pub const DIALOGS: &str = "dialogs";
+    /// Window and screen metrics.
+    pub const SCREEN: &str = "screen";
+    /// `Notification.permission`.
+    pub const NOTIFICATIONS: &str = "notifications";
+    /// `navigator.permissions.query`.
+    pub const PERMISSIONS: &str = "permissions";
+    /// Media codecs support.
+    pub const MEDIA_CODECS: &str = "media_codecs";
+    /// Touch support.
+    pub const TOUCH_SCREEN: &str = "touch_screen";
+    /// Media device labels.
+    pub const MEDIA_LABELS: &str = "media_labels";
+    /// `history.length`.
+    pub const HISTORY_LENGTH: &str = "history_length";
+    /// `navigator.plugins` and `navigator.mimeTypes`.
+    pub const PLUGINS: &str = "plugins";
+    /// Font availability checks.
+    pub const FONTS: &str = "fonts";
+    /// `navigator.deviceMemory`.
+    pub const DEVICE_MEMORY: &str = "device_memory";
+    /// `navigator.language(s)` and the `Intl` default locale.
+    pub const LOCALE: &str = "locale";
+    /// The `Date` offsets and the `Intl` default time zone.
+    pub const TIMEZONE: &str = "timezone";
+    /// WebRTC ICE candidate masking.
+    pub const WEBRTC: &str = "webrtc";
+    /// CDP and ChromeDriver marker cleanup.
+    pub const CDP_MARKERS: &str = "cdp_markers";
+    /// Selenium marker cleanup.
+    pub const SELENIUM_MARKERS: &str = "selenium_markers";
+    /// `window.chrome` hiding or removal.
+    pub const CHROME: &str = "chrome";
+    /// Console hiding.
+    pub const CONSOLE: &str = "console";
+    /// Worker overrides.
+    pub const WORKER: &str = "worker";
+    /// `navigator.hardwareConcurrency`.
+    pub const CONCURRENCY: &str = "concurrency";
+    /// `navigator.gpu.requestAdapter` info and limits.
+    pub const GPU_ADAPTER: &str = "gpu_adapter";
+    /// `navigator.webdriver`.
+    pub const WEBDRIVER: &str = "webdriver";
+    /// Navigator properties.
+    pub const NAVIGATOR: &str = "navigator";
+    /// WGSL language features and the preferred canvas format.
+    pub const WGSL: &str = "wgsl";
+}
+
+use names::*;
+
+/// The noise rendered with the fingerprint while emulating.
+pub const FINGERPRINT_MODULES: &[&str] = &[AUDIO, WEBGL_NOISE];
+
+/// The extras rendered around the core tier patches while emulating.
+pub const EXTRA_MODULES: &[&str] = &[
+    SPEECH_SYNTHESIS,
+    USER_AGENT_DATA,
+    DIALOGS,
+    SCREEN,
+    NOTIFICATIONS,
+    PERMISSIONS,
+    MEDIA_CODECS,
+    TOUCH_SCREEN,
+    MEDIA_LABELS,
+    HISTORY_LENGTH,
+    PLUGINS,
+    FONTS,
+    DEVICE_MEMORY,
+    LOCALE,
+    TIMEZONE,
+    WEBRTC,
+    CDP_MARKERS,
+    SELENIUM_MARKERS,
+];
+
+/// The values available to a module while rendering.
+#[derive(Debug, Clone, Copy)]
+pub struct ModuleContext<'a> {
+    /// The tier being rendered.
+    pub tier: Tier,
+    /// The agent os.
+    pub agent_os: AgentOs,
+    /// The browser kind.
+    pub browser: BrowserKind,
+    /// Spoof the hardware concurrency.
+    pub concurrency: bool,
+    /// The gpu profile.
+    pub gpu: &'a GpuProfile,
+    /// The WebGPU adapter limits.
+    pub gpu_limits: &'a GpuLimits,
+    /// The persona when emulating a user agent. Modules that depend on the user agent or
+    /// configuration render nothing without it.
+    pub persona: Option<&'a Persona>,
+}
+
+impl<'a> ModuleContext<'a> {
+    /// The emulation configuration when emulating a user agent.
+    pub fn config(&self) -> Option<&'a EmulationConfiguration> {
+        self.persona.map(|persona| &persona.config)
+    }
+
+    /// The tier skips the extras.
+    pub fn no_extra(&self) -> bool {
+        matches!(
+            self.tier,
+            Tier::BasicNoExtra | Tier::BasicNoWebglWithGPUNoExtra
+        )
+    }
+
+    /// The extra is not skipped by the tier or disabled in the configuration.
+    fn extra(&self, disabled: fn(&EmulationConfiguration) -> bool) -> bool {
+        self.config()
+            .is_none_or(|config| !(self.no_extra() || disabled(config)))
+    }
+
+    /// The browser renders with WebKit: Safari and every iOS browser.
+    pub fn webkit(&self) -> bool {
+        self.browser == BrowserKind::Safari
+            || matches!(self.agent_os, AgentOs::IPhone | AgentOs::IPad)
+    }
+
+    /// The browser renders with Blink and gets the Chrome only patches. The script builders
+    /// without a user agent use [`BrowserKind::Other`] and keep them.
+    pub fn chromium(&self) -> bool {
+        match self.browser {
+            BrowserKind::Other => self.persona.is_none(),
+            browser => browser.is_chromium() && !self.webkit(),
+        }
+    }
+
+    /// The `UNMASKED_VENDOR_WEBGL` and `UNMASKED_RENDERER_WEBGL` values. WebKit masks the
+    /// gpu behind `Apple GPU`.
+    pub fn webgl_strings(&self) -> (&'a str, &'a str) {
+        if self.webkit() {
+            ("Apple Inc.", "Apple GPU")
+        } else {
+            (self.gpu.webgl_vendor, self.gpu.webgl_renderer)
+        }
+    }
+
+    /// The core stealth patches are not disabled in the configuration.
+    fn core(&self) -> bool {
+        !self.config().is_some_and(|config| config.disable_stealth)
+    }
+}
+
+/// A piece of the stealth script.
+///
+/// Modules are registered in a [`ModuleRegistry`] which renders the selected modules in selection
+/// order followed by the custom modules in registration order.
+pub trait SpoofModule: Send + Sync {
+    /// The unique name of the module.
+    fn name(&self) -> &'static str;
+    /// The modules that must be rendered before this one.
+    fn dependencies(&self) -> &'static [&'static str] {
+        &[]
+    }
+    /// Render the custom module for the tier. Every stealth tier by default.
+    fn tier(&self, tier: Tier) -> bool {
+        tier.stealth()
+    }
+    /// Render the module for the context.
+    fn enabled(&self, _ctx: &ModuleContext) -> bool {
+        true
+    }
+    /// The script for the module.
+    fn render(&self, ctx: &ModuleContext) -> Cow<'static, str>;
+}
+
+/// A module defined from plain functions.
+#[derive(Clone, Copy)]
+struct BuiltinModule {
+    name: &'static str,
+    dependencies: &'static [&'static str],
+    enabled: fn(&ModuleContext) -> bool,
+    render: fn(&ModuleContext) -> Cow<'static, str>,
+}
+
+impl SpoofModule for BuiltinModule {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn dependencies(&self) -> &'static [&'static str] {
+        self.dependencies
+    }
+    fn enabled(&self, ctx: &ModuleContext) -> bool {
+        (self.enabled)(ctx)
+    }
+    fn render(&self, ctx: &ModuleContext) -> Cow<'static, str> {
+        (self.render)(ctx)
+    }
+}
+
+/// The built-in modules in render order.
+static BUILTIN_MODULES: &[BuiltinModule] = &[
+    BuiltinModule {
+        name: AUDIO,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.config()
+                .is_some_and(|c| c.fingerprint == Fingerprint::Basic || c.enable_audio_noise)
+        },
+        render: |ctx| match ctx.persona {
+            Some(persona) => crate::spoof_audio::spoof_audio_script(persona.noise_seed).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: WEBGL_NOISE,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.config()
+                .is_some_and(|c| c.fingerprint == Fingerprint::Basic)
+        },
+        render: |ctx| match ctx.persona {
+            Some(persona) => {
+                crate::spoof_webgl::spoof_webgl_noise_script(persona.noise_seed).into()
+            }
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: SPEECH_SYNTHESIS,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.agent_os != AgentOs::Unknown
+                && ctx.chromium()
+                && ctx.extra(|c| c.disable_speech_syntheses)
+        },
+        render: |_| PATCH_SPEECH_SYNTHESIS.into(),
+    },
+    BuiltinModule {
+        name: USER_AGENT_DATA,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.persona.is_some_and(|p| p.high_entropy.is_some())
+                && ctx.extra(|c| c.disable_user_agent_data)
+        },
+        render: |ctx| match ctx.persona.and_then(|p| p.high_entropy.as_ref()) {
+            Some(data) => {
+                crate::spoof_user_agent::spoof_user_agent_data_high_entropy_values(data).into()
+            }
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: DIALOGS,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.config()
+                .is_some_and(|c| ctx.no_extra() || c.dismiss_dialogs)
+        },
+        render: |_| DISABLE_DIALOGS.into(),
+    },
+    BuiltinModule {
+        name: SCREEN,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.persona.is_some_and(|p| p.screen.is_some()) && ctx.extra(|c| c.disable_screen)
+        },
+        render: |ctx| match ctx.persona.and_then(|p| p.screen.as_ref()) {
+            Some(screen) => spoof_screen_script_profile(screen).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: NOTIFICATIONS,
+        dependencies: &[],
+        enabled: |ctx| ctx.persona.is_some() && ctx.extra(|c| c.disable_notifications),
+        render: |_| SPOOF_NOTIFICATIONS.into(),
+    },
+    BuiltinModule {
+        name: PERMISSIONS,
+        dependencies: &[],
+        enabled: |ctx| ctx.extra(|c| c.disable_permissions),
+        render: |_| SPOOF_PERMISSIONS_QUERY.into(),
+    },
+    BuiltinModule {
+        name: MEDIA_CODECS,
+        dependencies: &[],
+        enabled: |ctx| ctx.extra(|c| c.disable_media_codecs),
+        render: |_| spoof_media_codecs_script().into(),
+    },
+    BuiltinModule {
+        name: TOUCH_SCREEN,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.config().is_some_and(|c| c.touch_screen) && ctx.extra(|c| c.disable_touch_screen)
+        },
+        render: |ctx| {
+            if ctx
+                .persona
+                .is_some_and(|p| is_ipad_desktop_mode(&p.user_agent, ctx.agent_os))
+            {
+                return spoof_touch_points_script(5).into();
+            }
+
+            spoof_touch_screen(
+                ctx.persona
+                    .and_then(|p| p.viewport)
+                    .is_some_and(|viewport| viewport.emulating_mobile),
+            )
+            .into()
+        },
+    },
+    BuiltinModule {
+        name: MEDIA_LABELS,
+        dependencies: &[],
+        enabled: |ctx| ctx.extra(|c| c.disable_media_labels),
+        render: |ctx| spoof_media_labels_script(ctx.agent_os).into(),
+    },
+    BuiltinModule {
+        name: HISTORY_LENGTH,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.persona.is_some_and(|p| p.history_length.is_some())
+                && ctx.extra(|c| c.disable_history_length)
+        },
+        render: |ctx| match ctx.persona.and_then(|p| p.history_length) {
+            Some(length) => spoof_history_length_script(length).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: PLUGINS,
+        dependencies: &[],
+        enabled: |ctx| ctx.extra(|c| c.disable_plugins && c.tier != Tier::LowWithPlugins),
+        render: |ctx| {
+            if ctx.browser == BrowserKind::Chrome {
+                PLUGIN_AND_MIMETYPE_SPOOF_CHROME.into()
+            } else {
+                PLUGIN_AND_MIMETYPE_SPOOF.into()
+            }
+        },
+    },
+    BuiltinModule {
+        name: FONTS,
+        dependencies: &[],
+        enabled: |ctx| ctx.agent_os != AgentOs::Unknown && ctx.extra(|c| c.disable_fonts),
+        render: |ctx| crate::spoof_fonts::spoof_fonts_script(ctx.agent_os).into(),
+    },
+    BuiltinModule {
+        name: DEVICE_MEMORY,
+        dependencies: &[],
+        enabled: |ctx| ctx.persona.is_some_and(|p| p.device_memory.is_some()),
+        render: |ctx| match ctx.persona.and_then(|p| p.device_memory) {
+            Some(memory) => spoof_device_memory(memory).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: LOCALE,
+        dependencies: &[],
+        enabled: |ctx| ctx.config().is_some_and(|c| c.locale.is_some()),
+        render: |ctx| match ctx.config().and_then(|c| c.locale) {
+            Some(locale) => crate::spoof_locale::spoof_locale_script(locale).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: TIMEZONE,
+        dependencies: &[],
+        enabled: |ctx| ctx.config().is_some_and(|c| c.timezone.is_some()),
+        render: |ctx| match ctx.config().and_then(|c| c.timezone) {
+            Some(timezone) => crate::spoof_timezone::spoof_timezone_script(timezone).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: WEBRTC,
+        dependencies: &[],
+        enabled: |ctx| ctx.config().is_some_and(|c| c.webrtc.is_some()),
+        render: |ctx| match ctx.config().and_then(|c| c.webrtc) {
+            Some(mode) => crate::spoof_webrtc::spoof_webrtc_script(mode).into(),
+            _ => Cow::Borrowed(""),
+        },
+    },
+    BuiltinModule {
+        name: CDP_MARKERS,
+        dependencies: &[],
+        enabled: |ctx| ctx.config().is_some_and(|c| c.enable_cdp_marker_cleanup),
+        render: |_| CLEANUP_CDP_MARKERS.into(),
+    },
+    BuiltinModule {
+        name: SELENIUM_MARKERS,
+        dependencies: &[],
+        enabled: |ctx| {
+            ctx.config()
+                .is_some_and(|c| c.enable_selenium_marker_cleanup)
+        },
+        render: |_| HIDE_SELENIUM_MARKERS.into(),
+    },
+    BuiltinModule {
+        name: CHROME,
+        dependencies: &[],
+        enabled: |ctx| ctx.core(),
+        render: |ctx| {
+            // Only spoof window.chrome for Chromium-based browsers.
+            // Non-Chromium browsers (Safari, Firefox) should never get a fake window.chrome
+            // even when agent_os is manually overridden, as it creates a detectable mismatch.
+            if ctx.browser.is_chromium() {
+                HIDE_CHROME.into()
+            } else {
+                REMOVE_CHROME.into()
+            }
+        },
+    },
+    BuiltinModule {
+        name: CONSOLE,
+        dependencies: &[],
+        enabled: |ctx| ctx.core(),
+        render: |_| HIDE_CONSOLE.into(),
+    },
+    BuiltinModule {
+        name: WORKER,
+        dependencies: &[],
+        enabled: |ctx| (ctx.chromium() || ctx.webkit() || ctx.concurrency) && ctx.core(),
+        render: |ctx| {
+            // the other engines keep their gpu and only get the worker concurrency.
+            if !(ctx.chromium() || ctx.webkit()) {
+                return unified_worker_override(ctx.gpu.hardware_concurrency, "", "", false).into();
+            }
+            let (vendor, renderer) = ctx.webgl_strings();
+            // the capabilities of the profile are the ANGLE values of Chrome.
+            let capabilities = (!ctx.webkit()).then_some(&ctx.gpu.webgl);
+
+            if ctx.concurrency {
+                unified_worker_override_with_capabilities(
+                    ctx.gpu.hardware_concurrency,
+                    vendor,
+                    renderer,
+                    !matches!(
+                        ctx.tier,
+                        Tier::BasicNoWebglWithGPU
+                            | Tier::BasicNoWebglWithGPUNoExtra
+                            | Tier::BasicNoWebglWithGPUcWithConsole
+                    ),
+                    capabilities,
+                )
+                .into()
+            } else {
+                worker_override_with_capabilities(vendor, renderer, capabilities).into()
+            }
+        },
+    },
+    BuiltinModule {
+        name: CONCURRENCY,
+        dependencies: &[],
+        enabled: |ctx| ctx.concurrency && ctx.core(),
+        render: |ctx| spoof_hardware_concurrency(ctx.gpu.hardware_concurrency).into(),
+    },
+    BuiltinModule {
+        name: GPU_ADAPTER,
+        dependencies: &[],
+        enabled: |ctx| ctx.chromium() && ctx.core(),
+        render: |ctx| build_gpu_request_adapter_script_from_profile(ctx.gpu, ctx.gpu_limits).into(),
+    },
+    BuiltinModule {
+        name: WEBDRIVER,
+        dependencies: &[],
+        enabled: |ctx| ctx.core(),
+        render: |_| HIDE_WEBDRIVER.into(),
+    },
+    BuiltinModule {
+        name: NAVIGATOR,
+        dependencies: &[],
+        enabled: |ctx| ctx.core(),
+        render: |_| NAVIGATOR_SCRIPT.into(),
+    },
+    BuiltinModule {
+        name: WGSL,
+        dependencies: &[GPU_ADAPTER],
+        enabled: |ctx| ctx.chromium() && ctx.core(),
+        render: |ctx| build_gpu_spoof_script_wgsl_from_profile(ctx.gpu, ctx.gpu_limits).into(),
+    },
+];
+
+/// An error registering a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleError {
+    /// A module with the name is already registered.
+    Duplicate(&'static str),
+    /// A dependency of the module is not registered yet.
+    MissingDependency {
+        /// The module being registered.
+        module: &'static str,
+        /// The missing dependency.
+        dependency: &'static str,
+    },
+    /// A tier the module renders for does not render the dependency.
+    TierMissingDependency {
+        /// The module being registered.
+        module: &'static str,
+        /// The dependency left out by the tier.
+        dependency: &'static str,
+        /// The tier.
+        tier: Tier,
+    },
+}
+
+impl std::fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModuleError::Duplicate(name) => write!(f, "the module {name} is already registered"),
+            ModuleError::MissingDependency { module, dependency } => write!(
+                f,
+                "the module {module} depends on {dependency} which is not registered"
+            ),
+            ModuleError::TierMissingDependency {
+                module,
+                dependency,
+                tier,
+            } => write!(
+                f,
+                "the module {module} depends on {dependency} which the tier {tier:?} does not render"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ModuleError {}
+
+/// A registered module.
+#[derive(Clone)]
+struct Entry {
+    module: Arc<dyn SpoofModule>,
+    /// Custom modules render for the tiers they select.
+    custom: bool,
+}
+
+/// An ordered set of spoof modules.
+///
+/// A module can only be registered after its dependencies and a custom module only for tiers
+/// rendering them, so a tier is never widened to satisfy a custom module.
+#[derive(Clone)]
+pub struct ModuleRegistry {
+    entries: Vec<Entry>,
+}
+
+impl std::fmt::Debug for ModuleRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl Default for ModuleRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        for module in BUILTIN_MODULES {
+            registry.entries.push(Entry {
+                module: Arc::new(*module),
+                custom: false,
+            });
+        }
+        registry
+    }
+}
+
+impl ModuleRegistry {
+    /// A registry without any modules.
+    pub fn empty() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Register a custom module after the existing ones. The module renders for the tiers it
+    /// selects, which must render its dependencies.
+    pub fn register<M: SpoofModule + 'static>(&mut self, module: M) -> Result<(), ModuleError> {
+        let name = module.name();
+
+        if self.contains(name) {
+            return Err(ModuleError::Duplicate(name));
+        }
+
+        if let Some(dependency) = module
+            .dependencies()
+            .iter()
+            .find(|dependency| !self.contains(dependency))
+        {
+            return Err(ModuleError::MissingDependency {
+                module: name,
+                dependency,
+            });
+        }
+
+        for tier in TIERS.iter().filter(|tier| module.tier(**tier)) {
+            let builtin = tier_modules(*tier);
+
+            if let Some(dependency) = module.dependencies().iter().find(|dependency| {
+                !builtin.contains(dependency)
+                    && !self.entries.iter().any(|e| {
+                        e.custom && e.module.name() == **dependency && e.module.tier(*tier)
+                    })
+            }) {
+                return Err(ModuleError::TierMissingDependency {
+                    module: name,
+                    dependency,
+                    tier: *tier,
+                });
+            }
+        }
+
+        self.entries.push(Entry {
+            module: Arc::new(module),
+            custom: true,
+        });
+
+        Ok(())
+    }
+
+    /// The module is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|e| e.module.name() == name)
+    }
+
+    /// The registered module names in render order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|e| e.module.name())
+    }
+
+    /// Add the module after its dependencies.
+    fn include(&self, index: usize, order: &mut Vec<usize>) {
+        if order.contains(&index) {
+            return;
+        }
+
+        for dependency in self.entries[index].module.dependencies() {
+            if let Some(j) = self.position(dependency) {
+                self.include(j, order);
+            }
+        }
+
+        order.push(index);
+    }
+
+    /// The index of the module.
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|e| e.module.name() == name)
+    }
+
+    /// Render the selected modules after their dependencies in selection order, then the custom
+    /// modules of the tier in registration order joined by `;`. A custom module whose
+    /// dependencies are not selected is left out.
+    pub fn render(&self, selected: &[&str], ctx: &ModuleContext) -> String {
+        let mut order = Vec::with_capacity(self.entries.len());
+
+        for index in selected.iter().filter_map(|name| self.position(name)) {
+            self.include(index, &mut order);
+        }
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.custom
+                && entry.module.tier(ctx.tier)
+                && entry.module.dependencies().iter().all(|dependency| {
+                    self.position(dependency)
+                        .is_some_and(|j| order.contains(&j))
+                })
+            {
+                self.include(index, &mut order);
+            }
+        }
+
+        let mut script = String::with_capacity(4096);
+
+        for entry in order.into_iter().map(|index| &self.entries[index]) {
+            if entry.module.enabled(ctx) {
+                let part = entry.module.render(ctx);
+                if !part.is_empty() {
+                    if !script.is_empty() {
+                        script.push(';');
+                    }
+                    script.push_str(&part);
+                }
+            }
+        }
+
+        script
+    }
+}
+
+/// The built-in modules the emulation renders for the tier: the fingerprint noise, then for the
+/// stealth tiers the extras and the core patches of the tier.
+pub fn tier_modules(tier: Tier) -> Vec<&'static str> {
+    let mut modules = FINGERPRINT_MODULES.to_vec();
+
+    if tier.stealth() {
+        for module in EXTRA_MODULES.iter().chain(tier.modules()) {
+            if !modules.contains(module) {
+                modules.push(module);
+            }
+        }
+    }
+
+    modules
+}
+
+lazy_static::lazy_static! {
+    /// The registry used by the emulation.
+    static ref REGISTRY: RwLock<ModuleRegistry> = RwLock::new(ModuleRegistry::default());
+}
+
+/// Register a custom module used by every emulation.
+pub fn register_module<M: SpoofModule + 'static>(module: M) -> Result<(), ModuleError> {
+    REGISTRY
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(module)
+}
+
+/// Render the selected modules with the global registry.
+pub fn render_modules(selected: &[&str], ctx: &ModuleContext) -> String {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .render(selected, ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::gpu::GPU_PROFILES_MAC;
+
+    struct Marker;
+
+    impl SpoofModule for Marker {
+        fn name(&self) -> &'static str {
+            "marker"
+        }
+        fn dependencies(&self) -> &'static [&'static str] {
+            &[WEBDRIVER]
+        }
+        fn render(&self, _ctx: &ModuleContext) -> Cow<'static, str> {
+            "window.__marker=1".into()
+        }
+    }
+
+    /// The marker for the tiers hiding the webdriver.
+    struct WebdriverMarker;
+
+    impl SpoofModule for WebdriverMarker {
+        fn name(&self) -> &'static str {
+            "webdriver_marker"
+        }
+        fn dependencies(&self) -> &'static [&'static str] {
+            &[WEBDRIVER]
+        }
+        fn tier(&self, tier: Tier) -> bool {
+            tier.modules().contains(&WEBDRIVER)
+        }
+        fn render(&self, _ctx: &ModuleContext) -> Cow<'static, str> {
+            "window.__marker=2".into()
+        }
+    }
+
+    fn render(registry: &ModuleRegistry, tier: Tier) -> String {
+        render_selected(registry, tier, tier.modules())
+    }
+
+    fn render_selected(registry: &ModuleRegistry, tier: Tier, selected: &[&str]) -> String {
+        let limits = GpuLimits::for_os(AgentOs::Mac);
+        let ctx = ModuleContext {
+            tier,
+            agent_os: AgentOs::Mac,
+            browser: BrowserKind::Chrome,
+            concurrency: true,
+            gpu: &GPU_PROFILES_MAC[0],
+            gpu_limits: &limits,
+            persona: None,
+        };
+        registry.render(selected, &ctx)
+    }
+
+    #[test]
+    fn tiers_render_their_modules() {
+        let registry = ModuleRegistry::default();
+
+        assert_eq!(render(&registry, Tier::HideOnlyChrome), HIDE_CHROME);
+        assert_eq!(
+            render(&registry, Tier::HideOnly),
+            format!("{HIDE_CHROME};{HIDE_CONSOLE};{HIDE_WEBDRIVER}")
+        );
+        assert!(render(&registry, Tier::Full).ends_with(
+            &build_gpu_spoof_script_wgsl_from_profile(
+                &GPU_PROFILES_MAC[0],
+                &GpuLimits::for_os(AgentOs::Mac)
+            )
+        ));
+        assert!(render(&registry, Tier::None).is_empty());
+
+        // the modules render in the order of the tier.
+        let mid = render(&registry, Tier::Mid);
+        let low = render(&registry, Tier::LowWithNavigator);
+        assert!(mid.find(NAVIGATOR_SCRIPT) < mid.find(HIDE_WEBDRIVER));
+        assert!(low.find(HIDE_WEBDRIVER) < low.find(NAVIGATOR_SCRIPT));
+    }
+
+    #[test]
+    fn registry_enforces_dependencies() {
+        let mut registry = ModuleRegistry::empty();
+
+        assert_eq!(
+            registry.register(Marker),
+            Err(ModuleError::MissingDependency {
+                module: "marker",
+                dependency: WEBDRIVER,
+            })
+        );
+
+        // the default tiers include tiers without the webdriver patch.
+        let mut registry = ModuleRegistry::default();
+        assert_eq!(
+            registry.register(Marker),
+            Err(ModuleError::TierMissingDependency {
+                module: "marker",
+                dependency: WEBDRIVER,
+                tier: Tier::Basic,
+            })
+        );
+
+        assert_eq!(registry.register(WebdriverMarker), Ok(()));
+        assert_eq!(
+            registry.register(WebdriverMarker),
+            Err(ModuleError::Duplicate("webdriver_marker"))
+        );
+
+        // the custom module renders for its tiers without widening the others.
+        assert_eq!(render(&registry, Tier::HideOnlyChrome), HIDE_CHROME);
+        assert_eq!(
+            render(&registry, Tier::HideOnly),
+            format!("{HIDE_CHROME};{HIDE_CONSOLE};{HIDE_WEBDRIVER};window.__marker=2")
+        );
+        assert_eq!(
+            render_selected(&registry, Tier::HideOnly, &[CHROME]),
+            HIDE_CHROME
+        );
+        assert!(render(&registry, Tier::None).is_empty());
+    }
+}
@@ -0,0 +1,658 @@
+use crate::spoof_locale::Locale;
+use rand::distr::{weighted::WeightedIndex, Distribution};
+use rand::{rng, Rng};
+
+/// The IANA time zone of the browser. Drives `Date.prototype.getTimezoneOffset`, the local
+/// `Date` getters, `Date.toString()` and the `Intl` default time zone.
+///
+/// The common zones have a variant, every other IANA zone is kept by name. Serialized as the
+/// IANA name e.g. `"Europe/Berlin"`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Timezone {
+    /// America/New_York.
+    #[default]
+    AmericaNewYork,
+    /// America/Chicago.
+    AmericaChicago,
+    /// America/Denver.
+    AmericaDenver,
+    /// America/Phoenix.
+    AmericaPhoenix,
+    /// America/Los_Angeles.
+    AmericaLosAngeles,
+    /// America/Anchorage.
+    AmericaAnchorage,
+    /// Pacific/Honolulu.
+    PacificHonolulu,
+    /// America/Toronto.
+    AmericaToronto,
+    /// America/Vancouver.
+    AmericaVancouver,
+    /// America/Mexico_City.
+    AmericaMexicoCity,
+    /// America/Sao_Paulo.
+    AmericaSaoPaulo,
+    /// America/Argentina/Buenos_Aires.
+    AmericaBuenosAires,
+    /// America/Bogota.
+    AmericaBogota,
+    /// Europe/London.
+    EuropeLondon,
+    /// Europe/Dublin.
+    EuropeDublin,
+    /// Europe/Lisbon.
+    EuropeLisbon,
+    /// Europe/Madrid.
+    EuropeMadrid,
+    /// Europe/Paris.
+    EuropeParis,
+    /// Europe/Berlin.
+    EuropeBerlin,
+    /// Europe/Rome.
+    EuropeRome,
+    /// Europe/Amsterdam.
+    EuropeAmsterdam,
+    /// Europe/Brussels.
+    EuropeBrussels,
+    /// Europe/Zurich.
+    EuropeZurich,
+    /// Europe/Stockholm.
+    EuropeStockholm,
+    /// Europe/Warsaw.
+    EuropeWarsaw,
+    /// Europe/Athens.
+    EuropeAthens,
+    /// Europe/Istanbul.
+    EuropeIstanbul,
+    /// Europe/Moscow.
+    EuropeMoscow,
+    /// Africa/Johannesburg.
+    AfricaJohannesburg,
+    /// Africa/Lagos.
+    AfricaLagos,
+    /// Africa/Cairo.
+    AfricaCairo,
+    /// Asia/Dubai.
+    AsiaDubai,
+    /// Asia/Kolkata.
+    AsiaKolkata,
+    /// Asia/Bangkok.
+    AsiaBangkok,
+    /// Asia/Jakarta.
+    AsiaJakarta,
+    /// Asia/Singapore.
+    AsiaSingapore,
+    /// Asia/Shanghai.
+    AsiaShanghai,
+    /// Asia/Hong_Kong.
+    AsiaHongKong,
+    /// Asia/Taipei.
+    AsiaTaipei,
+    /// Asia/Manila.
+    AsiaManila,
+    /// Asia/Seoul.
+    AsiaSeoul,
+    /// Asia/Tokyo.
+    AsiaTokyo,
+    /// Australia/Perth.
+    AustraliaPerth,
+    /// Australia/Brisbane.
+    AustraliaBrisbane,
+    /// Australia/Sydney.
+    AustraliaSydney,
+    /// Australia/Melbourne.
+    AustraliaMelbourne,
+    /// Pacific/Auckland.
+    PacificAuckland,
+    /// UTC.
+    Utc,
+    /// Any other IANA zone e.g. `Europe/Vienna`. Built by [`Timezone::from_iana`].
+    Iana(&'static str),
+}
+
+/// The time zones with a variant.
+pub const TIMEZONES: &[Timezone] = &[
+    Timezone::AmericaNewYork,
+    Timezone::AmericaChicago,
+    Timezone::AmericaDenver,
+    Timezone::AmericaPhoenix,
+    Timezone::AmericaLosAngeles,
+    Timezone::AmericaAnchorage,
+    Timezone::PacificHonolulu,
+    Timezone::AmericaToronto,
+    Timezone::AmericaVancouver,
+    Timezone::AmericaMexicoCity,
+    Timezone::AmericaSaoPaulo,
+    Timezone::AmericaBuenosAires,
+    Timezone::AmericaBogota,
+    Timezone::EuropeLondon,
+    Timezone::EuropeDublin,
+    Timezone::EuropeLisbon,
+    Timezone::EuropeMadrid,
+    Timezone::EuropeParis,
+    Timezone::EuropeBerlin,
+    Timezone::EuropeRome,
+    Timezone::EuropeAmsterdam,
+    Timezone::EuropeBrussels,
+    Timezone::EuropeZurich,
+    Timezone::EuropeStockholm,
+    Timezone::EuropeWarsaw,
+    Timezone::EuropeAthens,
+    Timezone::EuropeIstanbul,
+    Timezone::EuropeMoscow,
+    Timezone::AfricaJohannesburg,
+    Timezone::AfricaLagos,
+    Timezone::AfricaCairo,
+    Timezone::AsiaDubai,
+    Timezone::AsiaKolkata,
+    Timezone::AsiaBangkok,
+    Timezone::AsiaJakarta,
+    Timezone::AsiaSingapore,
+    Timezone::AsiaShanghai,
+    Timezone::AsiaHongKong,
+    Timezone::AsiaTaipei,
+    Timezone::AsiaManila,
+    Timezone::AsiaSeoul,
+    Timezone::AsiaTokyo,
+    Timezone::AustraliaPerth,
+    Timezone::AustraliaBrisbane,
+    Timezone::AustraliaSydney,
+    Timezone::AustraliaMelbourne,
+    Timezone::PacificAuckland,
+    Timezone::Utc,
+];
+
+/// The areas of the IANA zone names.
+const IANA_AREAS: &[&str] = &[
+    "Africa",
+    "America",
+    "Antarctica",
+    "Arctic",
+    "Asia",
+    "Atlantic",
+    "Australia",
+    "Etc",
+    "Europe",
+    "Indian",
+    "Pacific",
+];
+
+/// The time zones per ISO 3166-1 alpha-2 country code weighted by population.
+const COUNTRY_TIMEZONES: &[(&str, &[(Timezone, u32)])] = &[
+    (
+        "US",
+        &[
+            (Timezone::AmericaNewYork, 47),
+            (Timezone::AmericaChicago, 29),
+            (Timezone::AmericaDenver, 5),
+            (Timezone::AmericaPhoenix, 2),
+            (Timezone::AmericaLosAngeles, 16),
+            (Timezone::AmericaAnchorage, 1),
+        ],
+    ),
+    (
+        "CA",
+        &[
+            (Timezone::AmericaToronto, 75),
+            (Timezone::AmericaVancouver, 25),
+        ],
+    ),
+    ("MX", &[(Timezone::AmericaMexicoCity, 1)]),
+    ("BR", &[(Timezone::AmericaSaoPaulo, 1)]),
+    ("AR", &[(Timezone::AmericaBuenosAires, 1)]),
+    ("CO", &[(Timezone::AmericaBogota, 1)]),
+    ("GB", &[(Timezone::EuropeLondon, 1)]),
+    ("IE", &[(Timezone::EuropeDublin, 1)]),
+    ("PT", &[(Timezone::EuropeLisbon, 1)]),
+    ("ES", &[(Timezone::EuropeMadrid, 1)]),
+    ("FR", &[(Timezone::EuropeParis, 1)]),
+    ("DE", &[(Timezone::EuropeBerlin, 1)]),
+    ("IT", &[(Timezone::EuropeRome, 1)]),
+    ("NL", &[(Timezone::EuropeAmsterdam, 1)]),
+    ("BE", &[(Timezone::EuropeBrussels, 1)]),
+    ("CH", &[(Timezone::EuropeZurich, 1)]),
+    ("SE", &[(Timezone::EuropeStockholm, 1)]),
+    ("PL", &[(Timezone::EuropeWarsaw, 1)]),
+    ("GR", &[(Timezone::EuropeAthens, 1)]),
+    ("TR", &[(Timezone::EuropeIstanbul, 1)]),
+    ("RU", &[(Timezone::EuropeMoscow, 1)]),
+    ("ZA", &[(Timezone::AfricaJohannesburg, 1)]),
+    ("NG", &[(Timezone::AfricaLagos, 1)]),
+    ("EG", &[(Timezone::AfricaCairo, 1)]),
+    ("AE", &[(Timezone::AsiaDubai, 1)]),
+    ("IN", &[(Timezone::AsiaKolkata, 1)]),
+    ("TH", &[(Timezone::AsiaBangkok, 1)]),
+    ("ID", &[(Timezone::AsiaJakarta, 1)]),
+    ("SG", &[(Timezone::AsiaSingapore, 1)]),
+    ("CN", &[(Timezone::AsiaShanghai, 1)]),
+    ("HK", &[(Timezone::AsiaHongKong, 1)]),
+    ("TW", &[(Timezone::AsiaTaipei, 1)]),
+    ("PH", &[(Timezone::AsiaManila, 1)]),
+    ("KR", &[(Timezone::AsiaSeoul, 1)]),
+    ("JP", &[(Timezone::AsiaTokyo, 1)]),
+    (
+        "AU",
+        &[
+            (Timezone::AustraliaSydney, 40),
+            (Timezone::AustraliaMelbourne, 37),
+            (Timezone::AustraliaBrisbane, 12),
+            (Timezone::AustraliaPerth, 11),
+        ],
+    ),
+    ("NZ", &[(Timezone::PacificAuckland, 1)]),
+];
+
+impl Timezone {
+    /// The IANA time zone name e.g. `Europe/Berlin`.
+    pub fn iana(&self) -> &'static str {
+        match self {
+            Timezone::AmericaNewYork => "America/New_York",
+            Timezone::AmericaChicago => "America/Chicago",
+            Timezone::AmericaDenver => "America/Denver",
+            Timezone::AmericaPhoenix => "America/Phoenix",
+            Timezone::AmericaLosAngeles => "America/Los_Angeles",
+            Timezone::AmericaAnchorage => "America/Anchorage",
+            Timezone::PacificHonolulu => "Pacific/Honolulu",
+            Timezone::AmericaToronto => "America/Toronto",
+            Timezone::AmericaVancouver => "America/Vancouver",
+            Timezone::AmericaMexicoCity => "America/Mexico_City",
+            Timezone::AmericaSaoPaulo => "America/Sao_Paulo",
+            Timezone::AmericaBuenosAires => "America/Argentina/Buenos_Aires",
+            Timezone::AmericaBogota => "America/Bogota",
+            Timezone::EuropeLondon => "Europe/London",
+            Timezone::EuropeDublin => "Europe/Dublin",
+            Timezone::EuropeLisbon => "Europe/Lisbon",
+            Timezone::EuropeMadrid => "Europe/Madrid",
+            Timezone::EuropeParis => "Europe/Paris",
+            Timezone::EuropeBerlin => "Europe/Berlin",
+            Timezone::EuropeRome => "Europe/Rome",
+            Timezone::EuropeAmsterdam => "Europe/Amsterdam",
+            Timezone::EuropeBrussels => "Europe/Brussels",
+            Timezone::EuropeZurich => "Europe/Zurich",
+            Timezone::EuropeStockholm => "Europe/Stockholm",
+            Timezone::EuropeWarsaw => "Europe/Warsaw",
+            Timezone::EuropeAthens => "Europe/Athens",
+            Timezone::EuropeIstanbul => "Europe/Istanbul",
+            Timezone::EuropeMoscow => "Europe/Moscow",
+            Timezone::AfricaJohannesburg => "Africa/Johannesburg",
+            Timezone::AfricaLagos => "Africa/Lagos",
+            Timezone::AfricaCairo => "Africa/Cairo",
+            Timezone::AsiaDubai => "Asia/Dubai",
+            Timezone::AsiaKolkata => "Asia/Kolkata",
+            Timezone::AsiaBangkok => "Asia/Bangkok",
+            Timezone::AsiaJakarta => "Asia/Jakarta",
+            Timezone::AsiaSingapore => "Asia/Singapore",
+            Timezone::AsiaShanghai => "Asia/Shanghai",
+            Timezone::AsiaHongKong => "Asia/Hong_Kong",
+            Timezone::AsiaTaipei => "Asia/Taipei",
+            Timezone::AsiaManila => "Asia/Manila",
+            Timezone::AsiaSeoul => "Asia/Seoul",
+            Timezone::AsiaTokyo => "Asia/Tokyo",
+            Timezone::AustraliaPerth => "Australia/Perth",
+            Timezone::AustraliaBrisbane => "Australia/Brisbane",
+            Timezone::AustraliaSydney => "Australia/Sydney",
+            Timezone::AustraliaMelbourne => "Australia/Melbourne",
+            Timezone::PacificAuckland => "Pacific/Auckland",
+            Timezone::Utc => "UTC",
+            Timezone::Iana(name) => name,
+        }
+    }
+
+    /// Get the time zone from an IANA name. The zones with a variant match case-insensitive,
+    /// any other `Area/Location` name of an IANA area is kept as given.
+    pub fn from_iana(name: &str) -> Option<Timezone> {
+        let name = name.trim();
+
+        if let Some(tz) = TIMEZONES
+            .iter()
+            .find(|tz| tz.iana().eq_ignore_ascii_case(name))
+        {
+            return Some(*tz);
+        }
+
+        let area = name.split('/').next().unwrap_or_default();
+
+        let valid = name.len() <= 64
+            && IANA_AREAS.iter().any(|a| a.eq_ignore_ascii_case(area))
+            && name.contains('/')
+            && name.split('/').all(|part| {
+                !part.is_empty()
+                    && part
+                        .bytes()
+                        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b'+'))
+            });
+
+        valid.then(|| Timezone::Iana(crate::profiles::gpu_profile::intern_str(name)))
+    }
+
+    /// The standard (non-DST) offset from UTC in minutes, east positive. `None` for the zones
+    /// kept by name, the browser resolves their offsets.
+    pub fn standard_offset_minutes(&self) -> Option<i32> {
+        let minutes = match self {
+            Timezone::PacificHonolulu => -600,
+            Timezone::AmericaAnchorage => -540,
+            Timezone::AmericaLosAngeles | Timezone::AmericaVancouver => -480,
+            Timezone::AmericaDenver | Timezone::AmericaPhoenix => -420,
+            Timezone::AmericaChicago | Timezone::AmericaMexicoCity => -360,
+            Timezone::AmericaNewYork | Timezone::AmericaToronto | Timezone::AmericaBogota => -300,
+            Timezone::AmericaSaoPaulo | Timezone::AmericaBuenosAires => -180,
+            Timezone::EuropeLondon
+            | Timezone::EuropeDublin
+            | Timezone::EuropeLisbon
+            | Timezone::Utc => 0,
+            Timezone::EuropeMadrid
+            | Timezone::EuropeParis
+            | Timezone::EuropeBerlin
+            | Timezone::EuropeRome
+            | Timezone::EuropeAmsterdam
+            | Timezone::EuropeBrussels
+            | Timezone::EuropeZurich
+            | Timezone::EuropeStockholm
+            | Timezone::EuropeWarsaw
+            | Timezone::AfricaLagos => 60,
+            Timezone::EuropeAthens | Timezone::AfricaJohannesburg | Timezone::AfricaCairo => 120,
+            Timezone::EuropeIstanbul | Timezone::EuropeMoscow => 180,
+            Timezone::AsiaDubai => 240,
+            Timezone::AsiaKolkata => 330,
+            Timezone::AsiaBangkok | Timezone::AsiaJakarta => 420,
+            Timezone::AsiaSingapore
+            | Timezone::AsiaShanghai
+            | Timezone::AsiaHongKong
+            | Timezone::AsiaTaipei
+            | Timezone::AsiaManila
+            | Timezone::AustraliaPerth => 480,
+            Timezone::AsiaSeoul | Timezone::AsiaTokyo => 540,
+            Timezone::AustraliaBrisbane
+            | Timezone::AustraliaSydney
+            | Timezone::AustraliaMelbourne => 600,
+            Timezone::PacificAuckland => 720,
+            Timezone::Iana(_) => return None,
+        };
+
+        Some(minutes)
+    }
+
+    /// Does the zone observe daylight saving time. `None` for the zones kept by name.
+    pub fn observes_dst(&self) -> Option<bool> {
+        if let Timezone::Iana(_) = self {
+            return None;
+        }
+
+        Some(matches!(
+            self,
+            Timezone::AmericaNewYork
+                | Timezone::AmericaChicago
+                | Timezone::AmericaDenver
+                | Timezone::AmericaLosAngeles
+                | Timezone::AmericaAnchorage
+                | Timezone::AmericaToronto
+                | Timezone::AmericaVancouver
+                | Timezone::EuropeLondon
+                | Timezone::EuropeDublin
+                | Timezone::EuropeLisbon
+                | Timezone::EuropeMadrid
+                | Timezone::EuropeParis
+                | Timezone::EuropeBerlin
+                | Timezone::EuropeRome
+                | Timezone::EuropeAmsterdam
+                | Timezone::EuropeBrussels
+                | Timezone::EuropeZurich
+                | Timezone::EuropeStockholm
+                | Timezone::EuropeWarsaw
+                | Timezone::EuropeAthens
+                | Timezone::AfricaCairo
+                | Timezone::AustraliaSydney
+                | Timezone::AustraliaMelbourne
+                | Timezone::PacificAuckland
+        ))
+    }
+
+    /// The most common time zone of a country by its ISO 3166-1 alpha-2 code.
+    pub fn for_country(country: &str) -> Option<Timezone> {
+        country_timezones(country).and_then(|zones| {
+            zones
+                .iter()
+                .max_by_key(|(_, weight)| *weight)
+                .map(|(tz, _)| *tz)
+        })
+    }
+
+    /// A random time zone of a country weighted by population.
+    pub fn for_country_random(country: &str) -> Option<Timezone> {
+        Self::for_country_rng(country, &mut rng())
+    }
+
+    /// A random time zone of a country weighted by population using the provided rng.
+    pub fn for_country_rng<R: Rng>(country: &str, rng: &mut R) -> Option<Timezone> {
+        let zones = country_timezones(country)?;
+        let dist = WeightedIndex::new(zones.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(zones[dist.sample(rng)].0)
+    }
+
+    /// The most common time zone for the region of a locale.
+    pub fn for_locale(locale: Locale) -> Timezone {
+        Self::for_country(locale.region()).unwrap_or_default()
+    }
+
+    /// A random time zone for the region of a locale using the provided rng.
+    pub fn for_locale_rng<R: Rng>(locale: Locale, rng: &mut R) -> Timezone {
+        Self::for_country_rng(locale.region(), rng).unwrap_or_default()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timezone {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.iana())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Timezone {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Timezone::from_iana(&name)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown IANA time zone `{name}`")))
+    }
+}
+
+/// The weighted zones of a country.
+fn country_timezones(country: &str) -> Option<&'static [(Timezone, u32)]> {
+    let country = country.trim();
+    COUNTRY_TIMEZONES
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(country))
+        .map(|(_, zones)| *zones)
+}
+
+/// Spoof `Date.prototype.getTimezoneOffset`, the local `Date` getters and setters, `Date.toString()`,
+/// `toDateString()`, `toTimeString()` and the default time zone of `Intl.DateTimeFormat` and
+/// the `toLocale*String` methods. The `Date` constructor, bare `Date()` and `Date.parse` read
+/// local date parts and strings in the zone too.
+///
+/// The offsets are resolved by the browser's own tz database so DST transitions match the zone.
+pub fn spoof_timezone_script(timezone: Timezone) -> String {
+    format!(
+        r#"(()=>{{const z='{zone}',D=Date,P=D.prototype,N=Intl.DateTimeFormat,gt=P.getTime,st=P.setTime,go=P.getTimezoneOffset,dp=D.parse,f=new N('en-US',{{timeZone:z,hourCycle:'h23',year:'numeric',month:'numeric',day:'numeric',hour:'numeric',minute:'numeric',second:'numeric',timeZoneName:'long'}}),W=['Sun','Mon','Tue','Wed','Thu','Fri','Sat'],M=['Jan','Feb','Mar','Apr','May','Jun','Jul','Aug','Sep','Oct','Nov','Dec'],q=v=>String(v).padStart(2,'0'),r=t=>{{const p={{}};for(const x of f.formatToParts(new D(t)))p[x.type]=x.value;return p}},o=t=>{{const p=r(t),s=t-((t%1e3)+1e3)%1e3;return(D.UTC(+p.year,+p.month-1,+p.day,+p.hour%24,+p.minute,+p.second)-s)/6e4}},u=d=>{{const t=gt.call(d);return isNaN(t)?null:new D(t+o(t)*6e4)}},L=w=>{{if(!isFinite(w)||Math.abs(w)>8.639e15)return NaN;const t=w-o(w)*6e4;return w-o(t)*6e4}},zr=/GMT|UTC|\b[ECMP][SD]T\b|\dZ$|:\d\d(\.\d+)?\s*[+-]\d\d:?\d\d/i,dr=/^[+-]?\d{{4,6}}(-\d\d(-\d\d)?)?$/,pa=s=>{{const t=dp(s),v=s.trim();return isNaN(t)||zr.test(v)||dr.test(v)?t:L(t-go.call(new D(t))*6e4)}},g=t=>{{const m=o(t),a=Math.abs(m);return`GMT${{m<0?'-':'+'}}${{q(Math.floor(a/60))}}${{q(a%60)}} (${{r(t).timeZoneName}})`}},n=(k,v)=>{{Object.defineProperty(v,'name',{{value:k}});Object.defineProperty(v,'length',{{value:P[k].length}});v.toString=()=>`function ${{k}}() {{ [native code] }}`;Object.defineProperty(P,k,{{value:v,writable:!0,configurable:!0}})}},ds=s=>`${{W[s.getUTCDay()]}} ${{M[s.getUTCMonth()]}} ${{q(s.getUTCDate())}} ${{String(s.getUTCFullYear()).padStart(4,'0')}}`,ts=s=>`${{q(s.getUTCHours())}}:${{q(s.getUTCMinutes())}}:${{q(s.getUTCSeconds())}}`;n('getTimezoneOffset',function(){{const t=gt.call(this);return isNaN(t)?NaN:-o(t)}});for(const[k,c]of[['getFullYear','getUTCFullYear'],['getMonth','getUTCMonth'],['getDate','getUTCDate'],['getDay','getUTCDay'],['getHours','getUTCHours'],['getMinutes','getUTCMinutes']]){{const h=P[c];n(k,function(){{const s=u(this);return s?h.call(s):NaN}})}}for(const[k,c]of[['setMilliseconds','setUTCMilliseconds'],['setSeconds','setUTCSeconds'],['setMinutes','setUTCMinutes'],['setHours','setUTCHours'],['setDate','setUTCDate'],['setMonth','setUTCMonth'],['setFullYear','setUTCFullYear']]){{const h=P[c];n(k,function(...a){{let t=gt.call(this);if(isNaN(t)){{if(k!=='setFullYear')return NaN;t=0}}else t+=o(t)*6e4;const s=new D(t);h.apply(s,a);return st.call(this,L(gt.call(s)))}})}}n('toString',function(){{const s=u(this);return s?`${{ds(s)}} ${{ts(s)}} ${{g(gt.call(this))}}`:'Invalid Date'}});n('toDateString',function(){{const s=u(this);return s?ds(s):'Invalid Date'}});n('toTimeString',function(){{const s=u(this);return s?`${{ts(s)}} ${{g(gt.call(this))}}`:'Invalid Date'}});const y=v=>v===undefined?{{timeZone:z}}:v&&v.timeZone===undefined?Object.assign({{}},v,{{timeZone:z}}):v;for(const k of['toLocaleString','toLocaleDateString','toLocaleTimeString']){{const h=P[k];n(k,function(l,v){{return h.call(this,l,y(v))}})}}const F=function(l,v){{return new.target?Reflect.construct(N,[l,y(v)],new.target):N(l,y(v))}};F.prototype=N.prototype;Object.defineProperty(N.prototype,'constructor',{{value:F,writable:!0,configurable:!0}});Object.defineProperty(F,'name',{{value:'DateTimeFormat'}});Object.defineProperty(F,'supportedLocalesOf',{{value:N.supportedLocalesOf,writable:!0,configurable:!0}});F.toString=()=>`function DateTimeFormat() {{ [native code] }}`;Object.defineProperty(Intl,'DateTimeFormat',{{value:F,writable:!0,configurable:!0}});const X=function Date(...a){{if(!new.target)return P.toString.call(new D());const v=a.length>1?L(D.UTC(...a)):a.length===1&&typeof a[0]==='string'?pa(a[0]):void 0;return Reflect.construct(D,v===void 0?a:[v],new.target)}},ps=function parse(s){{return pa(String(s))}};Object.defineProperty(X,'length',{{value:7}});X.toString=()=>'function Date() {{ [native code] }}';ps.toString=()=>'function parse() {{ [native code] }}';Object.defineProperty(X,'prototype',{{value:P,writable:!1}});for(const[k,v]of[['now',D.now],['UTC',D.UTC],['parse',ps]])Object.defineProperty(X,k,{{value:v,writable:!0,configurable:!0}});Object.defineProperty(P,'constructor',{{value:X,writable:!0,configurable:!0}});Object.defineProperty(globalThis,'Date',{{value:X,writable:!0,configurable:!0}})}})();"#,
+        zone = timezone.iana(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{emulate, EmulationConfiguration};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn timezone_from_iana() {
+        assert_eq!(
+            Timezone::from_iana("europe/berlin"),
+            Some(Timezone::EuropeBerlin)
+        );
+        assert_eq!(Timezone::from_iana("Mars/Olympus"), None);
+        for tz in TIMEZONES {
+            assert_eq!(Timezone::from_iana(tz.iana()), Some(*tz));
+        }
+
+        for name in [
+            "Europe/Vienna",
+            "America/Detroit",
+            "America/Indiana/Knox",
+            "Etc/GMT+5",
+        ] {
+            let tz = Timezone::from_iana(name).expect("zone");
+            assert_eq!(tz.iana(), name);
+            assert_eq!(tz.standard_offset_minutes(), None);
+            assert_eq!(Timezone::from_iana(&format!(" {name} ")), Some(tz));
+        }
+        for name in [
+            "Europe",
+            "Europe/",
+            "Europe//Vienna",
+            "Europe/Vi'enna",
+            "Vienna",
+        ] {
+            assert_eq!(Timezone::from_iana(name), None, "{name}");
+        }
+        assert_eq!(Timezone::EuropeBerlin.standard_offset_minutes(), Some(60));
+        assert_eq!(Timezone::AsiaTokyo.observes_dst(), Some(false));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn timezone_serializes_as_the_iana_name() {
+        let zones = [
+            Timezone::AmericaBuenosAires,
+            Timezone::from_iana("Europe/Vienna").unwrap(),
+        ];
+        let json = serde_json::to_string(&zones).expect("json");
+
+        assert_eq!(
+            json,
+            r#"["America/Argentina/Buenos_Aires","Europe/Vienna"]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<Timezone>>(&json).expect("zones"),
+            zones
+        );
+        assert!(serde_json::from_str::<Timezone>(r#""Mars/Olympus""#).is_err());
+    }
+
+    #[test]
+    fn timezone_for_locale_and_country() {
+        assert_eq!(Timezone::for_locale(Locale::DeDE), Timezone::EuropeBerlin);
+        assert_eq!(Timezone::for_locale(Locale::EnUS), Timezone::AmericaNewYork);
+        assert_eq!(Timezone::for_country("jp"), Some(Timezone::AsiaTokyo));
+        assert_eq!(Timezone::for_country("XX"), None);
+
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..32 {
+            let tz = Timezone::for_locale_rng(Locale::EnAU, &mut rng);
+            assert!(tz.iana().starts_with("Australia/"));
+        }
+        for locale in crate::spoof_locale::LOCALES {
+            assert!(Timezone::for_country(locale.region()).is_some());
+        }
+    }
+
+    #[test]
+    fn timezone_script_uses_zone() {
+        let script = spoof_timezone_script(Timezone::AsiaKolkata);
+        assert!(script.contains("const z='Asia/Kolkata'"));
+        assert!(script.contains("getTimezoneOffset"));
+        assert!(script.contains("DateTimeFormat"));
+    }
+
+    #[test]
+    fn timezone_script_reads_local_dates_in_the_zone() {
+        let script = spoof_timezone_script(Timezone::EuropeBerlin);
+        let check = r#"
+            const d = new Date(Date.UTC(2024, 0, 15, 12));
+            const iso = [d.setHours(23), d.toISOString()];
+            d.setMonth(6); iso.push(d.toISOString(), d.getHours());
+            d.setDate(31); d.setMinutes(5); iso.push(d.toISOString());
+            d.setFullYear(2023, 11); iso.push(d.toISOString());
+            return [
+                new Date(2024, 0, 15, 10, 30).toISOString(),
+                new Date(2024, 6, 15, 10, 30).toISOString(),
+                new Date(2024, 6).getMonth(),
+                Date.parse('2024-07-15T10:30:00') === Date.UTC(2024, 6, 15, 8, 30),
+                Date.parse('Jul 15 2024 10:30:00') === Date.UTC(2024, 6, 15, 8, 30),
+                Date.parse('2024-07-15') === Date.UTC(2024, 6, 15),
+                Date.parse('2024-07-15T10:30:00Z') === Date.UTC(2024, 6, 15, 10, 30),
+                Date.parse('Mon Jul 15 2024 10:30:00 GMT+0200') === Date.UTC(2024, 6, 15, 8, 30),
+                new Date('2024-07-15T10:30:00').getHours(),
+                new Date(new Date(1)).getTime(),
+                /GMT\+0[12]00 \(Central European (Standard|Summer) Time\)$/.test(Date()),
+                new Date(NaN).setHours(1),
+                new Date(0) instanceof Date && Date.prototype.constructor === Date,
+                [Date.name, Date.length, String(Date), typeof Date.now()],
+                iso,
+            ];"#;
+
+        let value = crate::test_js::eval_js(&script, check, "America/New_York");
+
+        assert_eq!(
+            value,
+            serde_json::json!([
+                "2024-01-15T09:30:00.000Z",
+                "2024-07-15T08:30:00.000Z",
+                6,
+                true,
+                true,
+                true,
+                true,
+                true,
+                10,
+                1,
+                true,
+                null,
+                true,
+                ["Date", 7, "function Date() { [native code] }", "number"],
+                [
+                    1705356000000_i64,
+                    "2024-01-15T22:00:00.000Z",
+                    "2024-07-15T21:00:00.000Z",
+                    23,
+                    "2024-07-31T21:05:00.000Z",
+                    "2023-12-31T22:05:00.000Z"
+                ]
+            ])
+        );
+    }
+
+    #[test]
+    fn timezone_script_resolves_named_zones() {
+        let script = spoof_timezone_script(Timezone::from_iana("America/Detroit").unwrap());
+        let check = r#"
+            return [
+                new Date(Date.UTC(2024, 0, 15, 12)).getTimezoneOffset(),
+                new Date(Date.UTC(2024, 6, 15, 12)).getTimezoneOffset(),
+                new Intl.DateTimeFormat().resolvedOptions().timeZone,
+            ];"#;
+
+        let value = crate::test_js::eval_js(&script, check, "Asia/Tokyo");
+
+        assert_eq!(value, serde_json::json!([300, 240, "America/Detroit"]));
+    }
+
+    #[test]
+    fn timezone_is_spoofed_when_configured() {
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        assert!(!script.contains("getTimezoneOffset"));
+
+        config.locale = Some(Locale::DeDE);
+        config.timezone = Some(Timezone::for_locale(Locale::DeDE));
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        let timezone = spoof_timezone_script(Timezone::EuropeBerlin);
+        assert!(script.contains(&timezone));
+        // the zone wraps the locale defaults so both apply
+        let locale = crate::spoof_locale::spoof_locale_script(Locale::DeDE);
+        assert!(script.find(&locale) < script.find(&timezone));
+    }
+}
@@ -1,5 +1,9 @@
-use crate::versions::BASE_VERSION;
-use crate::{mobile_model_from_user_agent, BASE_CHROME_VERSION, CHROME_VERSIONS_BY_MAJOR};
+use crate::configs::AgentOs;
+use crate::spoof_viewport::DeviceType;
+use crate::versions::{BASE_VERSION, CHROME_NOT_A_BRAND_VERSION_OVERRIDE};
+use crate::{
+    parse_user_agent, BrowserKind, UserAgentInfo, BASE_CHROME_VERSION, CHROME_VERSIONS_BY_MAJOR,
+};
 use rand::prelude::IndexedRandom;
 use rand::{rng, Rng};
 
@@ -94,8 +98,14 @@ pub fn random_spoofed_version_rng(latest: &str, rng: &mut impl Rng) -> String {
 /// Generate a real spoof for chrome full version.
 pub fn smart_spoof_chrome_full_version(ua_major: &str, // e.g. "136"
 ) -> String {
-    let mut rng = rng();
+    smart_spoof_chrome_full_version_rng(ua_major, &mut rng())
+}
 
+/// Generate a real spoof for chrome full version using the provided rng.
+pub fn smart_spoof_chrome_full_version_rng<R: Rng>(
+    ua_major: &str, // e.g. "136"
+    rng: &mut R,
+) -> String {
     // Try the latest full version from "latest" key in PHF
     let latest_versions = CHROME_VERSIONS_BY_MAJOR
         .get("latest")
@@ -108,13 +118,13 @@ pub fn smart_spoof_chrome_full_version(ua_major: &str, // e.g. "136"
     let same_major = latest_versions.starts_with(ua_major);
 
     if same_major && rng.random_bool(0.75) {
-        return crate::versions::random_version_based_on_default_version_base(&mut rng);
+        return crate::versions::random_version_based_on_default_version_base(rng);
     }
 
     // Otherwise, pick a random known-good version in the given major
     if let Some(versions) = CHROME_VERSIONS_BY_MAJOR.get(ua_major) {
         if !versions.is_empty() {
-            if let Some(v) = versions.choose(&mut rng) {
+            if let Some(v) = versions.choose(rng) {
                 return v.to_string();
             }
         }
@@ -123,7 +133,7 @@ pub fn smart_spoof_chrome_full_version(ua_major: &str, // e.g. "136"
     if same_major {
         latest_versions.to_string()
     } else {
-        random_spoofed_version_rng(ua_major, &mut rng)
+        random_spoofed_version_rng(ua_major, rng)
     }
 }
 
@@ -161,6 +171,19 @@ pub struct HighEntropyUaData {
     pub wow64_ness: bool,
 }
 
+impl HighEntropyUaData {
+    /// The low entropy `brands`: the full version list reduced to the majors.
+    pub fn brands(&self) -> Vec<BrandEntry> {
+        self.full_version_list
+            .iter()
+            .map(|b| BrandEntry {
+                brand: b.brand.clone(),
+                version: b.version.split('.').next().unwrap_or("99").into(),
+            })
+            .collect()
+    }
+}
+
 /// Get the default chrome version.
 pub fn get_default_version() -> &'static str {
     if !&crate::CHROME_VERSION_FULL.is_empty() {
@@ -170,16 +193,265 @@ pub fn get_default_version() -> &'static str {
     }
 }
 
+/// The ChromeOS platform versions of the stable releases by chrome major.
+static CHROME_OS_PLATFORM_VERSIONS: &[(u32, &str)] = &[
+    (120, "15662.76.0"),
+    (122, "15753.50.0"),
+    (124, "15823.51.0"),
+    (126, "15886.44.0"),
+    (128, "15964.59.0"),
+    (130, "16033.48.0"),
+    (132, "16093.68.0"),
+    (134, "16181.47.0"),
+    (136, "16238.64.0"),
+    (138, "16295.54.0"),
+    (140, "16371.41.0"),
+    (142, "16428.38.0"),
+];
+
+/// Get the ChromeOS platform version shipped with the chrome major.
+pub fn chrome_os_platform_version(chrome_major: u32) -> &'static str {
+    CHROME_OS_PLATFORM_VERSIONS
+        .iter()
+        .rev()
+        .find(|(major, _)| *major <= chrome_major)
+        .unwrap_or(&CHROME_OS_PLATFORM_VERSIONS[0])
+        .1
+}
+
+/// The Windows `platformVersion` values: `10.0.0` for Windows 10 and `13.0.0` and up for the
+/// Windows 11 releases.
+static WINDOWS_PLATFORM_VERSIONS: &[&str] = &["10.0.0", "13.0.0", "14.0.0", "15.0.0", "19.0.0"];
+
+/// The separators Chromium picks from for the GREASE brand.
+const GREASE_CHARS: [char; 11] = [' ', '(', ':', '-', '.', '/', ')', ';', '=', '?', '_'];
+
+/// The versions Chromium picks from for the GREASE brand.
+const GREASE_VERSIONS: [&str; 3] = ["8", "99", "24"];
+
+/// The permutations of the GREASE, `Chromium` and brand entries.
+const GREASE_ORDERS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// The GREASE brand and major version Chromium sends for the major e.g. `("Not;A=Brand", "99")`
+/// for 139. The chromium major is the seed, so forks get the same value for the same chromium.
+/// The `CHROME_NOT_A_BRAND_VERSION` env variable pins the version.
+pub fn grease_brand(chrome_major: u32) -> (String, &'static str) {
+    let seed = chrome_major as usize;
+    let brand = format!(
+        "Not{}A{}Brand",
+        GREASE_CHARS[seed % GREASE_CHARS.len()],
+        GREASE_CHARS[(seed + 1) % GREASE_CHARS.len()]
+    );
+
+    let version = match CHROME_NOT_A_BRAND_VERSION_OVERRIDE.as_deref() {
+        Some(version) => version.split('.').next().unwrap_or(version),
+        _ => GREASE_VERSIONS[seed % GREASE_VERSIONS.len()],
+    };
+
+    (brand, version)
+}
+
+/// The brand list Chromium builds for the major: the GREASE brand, `Chromium` and the brand
+/// permuted by the major. `full` uses the full versions of `fullVersionList` instead of the majors.
+///
+/// ```
+/// use spider_fingerprint::spoof_user_agent::{chromium_brand_list, format_sec_ch_ua};
+///
+/// let brands = chromium_brand_list(139, "139.0.7258.155", "Google Chrome", "139.0.7258.155", false);
+///
+/// assert_eq!(
+///     format_sec_ch_ua(&brands),
+///     r#""Not;A=Brand";v="99", "Google Chrome";v="139", "Chromium";v="139""#
+/// );
+/// ```
+pub fn chromium_brand_list(
+    chrome_major: u32,
+    chromium_version: &str,
+    brand: &str,
+    brand_version: &str,
+    full: bool,
+) -> Vec<BrandEntry> {
+    let version = |version: &str| {
+        if full {
+            version.to_string()
+        } else {
+            version.split('.').next().unwrap_or(version).to_string()
+        }
+    };
+    let (grease_brand, grease_version) = grease_brand(chrome_major);
+    let order = GREASE_ORDERS[chrome_major as usize % GREASE_ORDERS.len()];
+
+    let entries = [
+        BrandEntry {
+            brand: grease_brand,
+            version: if full {
+                format!("{grease_version}.0.0.0")
+            } else {
+                grease_version.into()
+            },
+        },
+        BrandEntry {
+            brand: "Chromium".into(),
+            version: version(chromium_version),
+        },
+        BrandEntry {
+            brand: brand.into(),
+            version: version(brand_version),
+        },
+    ];
+
+    let mut list: Vec<(usize, BrandEntry)> = order.into_iter().zip(entries).collect();
+    list.sort_by_key(|(position, _)| *position);
+    list.into_iter().map(|(_, entry)| entry).collect()
+}
+
+/// Format the brands as the `sec-ch-ua` header value.
+pub fn format_sec_ch_ua(brands: &[BrandEntry]) -> String {
+    brands
+        .iter()
+        .map(|b| format!(r#""{}";v="{}""#, b.brand, b.version))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The brand of the chromium browser e.g. `Microsoft Edge` for Edge.
+pub fn chromium_brand(browser: BrowserKind) -> &'static str {
+    match browser {
+        BrowserKind::Edge => "Microsoft Edge",
+        BrowserKind::Opera => "Opera",
+        BrowserKind::Brave => "Brave",
+        _ => "Google Chrome",
+    }
+}
+
+/// The stable releases of a chromium fork as `(major, chromium major, full version)`.
+type ForkReleases = [(u32, u32, &'static str)];
+
+/// The Edge stable releases.
+static EDGE_RELEASES: &ForkReleases = &[
+    (130, 130, "130.0.2849.80"),
+    (131, 131, "131.0.2903.112"),
+    (132, 132, "132.0.2957.140"),
+    (133, 133, "133.0.3065.92"),
+    (134, 134, "134.0.3124.93"),
+    (135, 135, "135.0.3179.98"),
+    (136, 136, "136.0.3240.92"),
+    (137, 137, "137.0.3296.93"),
+    (138, 138, "138.0.3351.121"),
+    (139, 139, "139.0.3405.125"),
+    (140, 140, "140.0.3485.94"),
+    (141, 141, "141.0.3537.99"),
+    (142, 142, "142.0.3595.94"),
+];
+
+/// The Opera stable releases.
+static OPERA_RELEASES: &ForkReleases = &[
+    (115, 130, "115.0.5322.119"),
+    (116, 131, "116.0.5366.127"),
+    (117, 132, "117.0.5408.197"),
+    (118, 133, "118.0.5461.104"),
+    (119, 134, "119.0.5497.141"),
+    (120, 135, "120.0.5543.201"),
+    (121, 137, "121.0.5600.164"),
+    (122, 138, "122.0.5643.150"),
+    (123, 139, "123.0.5669.113"),
+    (124, 140, "124.0.5705.65"),
+];
+
+/// The full version of the fork release on the chromium major. Majors outside the table continue
+/// the release cadence of the closest release, `build_step` builds per major.
+fn fork_release(releases: &ForkReleases, chrome_major: u32, build_step: u32) -> String {
+    let Some(&(major, chromium, full)) = releases
+        .iter()
+        .min_by_key(|(_, chromium, _)| chromium.abs_diff(chrome_major))
+    else {
+        return format!("{chrome_major}.0.0.0");
+    };
+
+    if chromium == chrome_major {
+        return full.into();
+    }
+
+    let version = ChromeVersion::from_str(full);
+    let delta = i64::from(chrome_major) - i64::from(chromium);
+    let shift = |value: u32, step: u32| (i64::from(value) + delta * i64::from(step)).max(0);
+
+    format!(
+        "{}.0.{}.{}",
+        shift(major, 1),
+        shift(version.build, build_step),
+        version.patch
+    )
+}
+
+/// The Edge full version released on the chromium major e.g. `139.0.3405.125` for 139.
+pub fn edge_full_version(chrome_major: u32) -> String {
+    fork_release(EDGE_RELEASES, chrome_major, 55)
+}
+
+/// The Opera full version released on the chromium major e.g. `123.0.5669.113` for 139.
+pub fn opera_full_version(chrome_major: u32) -> String {
+    fork_release(OPERA_RELEASES, chrome_major, 45)
+}
+
+/// The full version of the brand. Edge and Opera report their own build, read from the user
+/// agent or the release on the chromium major when the user agent is reduced. Brave reduces it
+/// to the major.
+pub fn chromium_brand_version(info: &UserAgentInfo, chromium_full_version: &str) -> String {
+    let reduced = info.version.is_empty() || info.version.ends_with(".0.0.0");
+    let chrome_major = chromium_full_version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok())
+        .unwrap_or(*BASE_CHROME_VERSION);
+
+    match info.browser {
+        BrowserKind::Brave => format!("{}.0.0.0", info.major.unwrap_or(*BASE_CHROME_VERSION)),
+        BrowserKind::Edge | BrowserKind::Opera if !reduced => info.version.clone(),
+        BrowserKind::Edge => edge_full_version(chrome_major),
+        BrowserKind::Opera => opera_full_version(chrome_major),
+        _ => chromium_full_version.into(),
+    }
+}
+
 /// Build the entropy data.
 pub fn build_high_entropy_data(user_agent: &Option<&str>) -> HighEntropyUaData {
+    build_high_entropy_data_rng(user_agent, &mut rng())
+}
+
+/// Build the entropy data using the provided rng.
+pub fn build_high_entropy_data_rng<R: Rng>(
+    user_agent: &Option<&str>,
+    rng: &mut R,
+) -> HighEntropyUaData {
     let user_agent: &str = user_agent.as_deref().map_or("", |v| v);
-    let full_version = user_agent
-        .split_whitespace()
-        .find_map(|s| s.strip_prefix("Chrome/"))
-        .unwrap_or(&get_default_version());
+    build_high_entropy_data_from_info_rng(&parse_user_agent(user_agent), rng)
+}
+
+/// Build the entropy data from the parsed user agent using the provided rng. The browser of the
+/// info picks the brand, so Brave which sends the Chrome user agent can be set by the caller.
+pub fn build_high_entropy_data_from_info_rng<R: Rng>(
+    info: &UserAgentInfo,
+    rng: &mut R,
+) -> HighEntropyUaData {
+    let full_version = if info.is_chromium() && !info.engine_version.is_empty() {
+        info.engine_version.as_str()
+    } else {
+        get_default_version()
+    };
 
-    let mut older_brand = true;
-    let mut chrome_major = 0;
+    let chrome_major = full_version
+        .split('.')
+        .next()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(*BASE_CHROME_VERSION);
 
     let (architecture, model, platform, platform_version, bitness): (
         &str,
@@ -187,42 +459,29 @@ pub fn build_high_entropy_data(user_agent: &Option<&str>) -> HighEntropyUaData {
         &str,
         String,
         &str,
-    ) = if user_agent.contains("Android") {
-        let version = user_agent
-            .split(';')
-            .find_map(|s| s.trim().strip_prefix("Android "))
-            .unwrap_or("13");
-        chrome_major = version.parse::<u32>().ok().unwrap_or_default();
-
-        let model = user_agent
-            .split(';')
-            .nth(2)
-            .map(|s| s.trim().to_string())
-            .unwrap_or_default();
-
-        let bitness = if user_agent.contains("arm64") || user_agent.contains("aarch64") {
-            "64"
+    ) = if info.os == AgentOs::Android {
+        let version = if info.os_version.is_empty() {
+            "13"
         } else {
-            "32"
+            &info.os_version
         };
+        let bitness = if info.bitness == "64" { "64" } else { "32" };
 
-        ("arm", model, "Android", version.to_string(), bitness)
-    } else if user_agent.contains("Windows NT") {
-        let version = user_agent
-            .split("Windows NT ")
-            .nth(1)
-            .and_then(|s| s.split(';').next())
-            .unwrap_or("10.0");
-        chrome_major = version.parse::<u32>().ok().unwrap_or_default();
-
-        let bitness = if user_agent.contains("Win64")
-            || user_agent.contains("x64")
-            || user_agent.contains("WOW64")
-        {
-            "64"
+        (
+            "arm",
+            info.model.clone(),
+            "Android",
+            version.to_string(),
+            bitness,
+        )
+    } else if info.os == AgentOs::Windows {
+        // Windows 10 and 11 both freeze the user agent at NT 10.0, older releases send 0.0.0.
+        let version = if info.os_version.is_empty() || info.os_version == "10.0" {
+            WINDOWS_PLATFORM_VERSIONS.choose(rng).unwrap_or(&"10.0.0")
         } else {
-            "32"
+            "0.0.0"
         };
+        let bitness = if info.bitness == "64" { "64" } else { "32" };
 
         (
             "x86",
@@ -231,13 +490,7 @@ pub fn build_high_entropy_data(user_agent: &Option<&str>) -> HighEntropyUaData {
             version.to_string(),
             bitness,
         )
-    } else if user_agent.contains("Mac OS X") {
-        chrome_major = full_version
-            .split('.')
-            .next()
-            .and_then(|s| s.parse::<u32>().ok())
-            .unwrap_or(*BASE_CHROME_VERSION);
-
+    } else if matches!(info.os, AgentOs::Mac | AgentOs::IPhone | AgentOs::IPad) {
         // 139 plus
         let base_mac = 15.5;
 
@@ -270,10 +523,6 @@ pub fn build_high_entropy_data(user_agent: &Option<&str>) -> HighEntropyUaData {
                 base_mac + delta
             };
 
-            if mac_major >= 136.0 {
-                older_brand = false;
-            }
-
             if mac_major < 15.0 {
                 format!("{:.1}.1", mac_major)
             } else {
@@ -282,23 +531,28 @@ pub fn build_high_entropy_data(user_agent: &Option<&str>) -> HighEntropyUaData {
         };
 
         ("arm", "".to_string(), "macOS", platform_version, "64")
-    } else if user_agent.contains("Linux") {
+    } else if info.os == AgentOs::ChromeOS {
+        let (architecture, bitness) = if info.architecture.is_empty() {
+            ("x86", "64")
+        } else {
+            (info.architecture.as_str(), info.bitness.as_str())
+        };
+
+        (
+            architecture,
+            "".to_string(),
+            "Chrome OS",
+            chrome_os_platform_version(chrome_major).to_string(),
+            bitness,
+        )
+    } else if info.os == AgentOs::Linux {
         let platform_version = full_version
             .split('.')
             .take(3)
             .collect::<Vec<_>>()
             .join(".");
 
-        chrome_major = platform_version.parse::<u32>().ok().unwrap_or_default();
-
-        let bitness = if user_agent.contains("x86_64")
-            || user_agent.contains("amd64")
-            || user_agent.contains("arm64")
-        {
-            "64"
-        } else {
-            "32"
-        };
+        let bitness = if info.bitness == "64" { "64" } else { "32" };
 
         ("x86", "".to_string(), "Linux", platform_version, bitness)
     } else {
@@ -306,110 +560,220 @@ pub fn build_high_entropy_data(user_agent: &Option<&str>) -> HighEntropyUaData {
     };
 
     // generator a new full version.
-    let full_version = smart_spoof_chrome_full_version(full_version);
-
-    // chrome canary order - Not, Chromium, and "Google Chrome ( use a flag for it. )
-    // base canary is released 2 versions ahead of chrome.
-    // canary not a brand starts at 8.0 while normal chrome "99"
-    // we need to spoof this for firefox.
-    let full_version_list = if chrome_major == 143 {
-        vec![
-            BrandEntry {
-                brand: "Google Chrome".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Chromium".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Not A(Brand".into(),
-                version: "24.0.0.0".into(),
-            },
-        ]
-    } else if chrome_major == 142 {
-        vec![
-            BrandEntry {
-                brand: "Chromium".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Google Chrome".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Not_A Brand".into(),
-                version: "99.0.0.0".into(),
-            },
-        ]
-    } else if chrome_major == 141 {
-        vec![
-            BrandEntry {
-                brand: "Google Chrome".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Chromium".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Not?A_Brand".into(),
-                version: crate::CHROME_NOT_A_BRAND_VERSION.clone(),
-            },
-        ]
-    } else {
-        vec![
-            BrandEntry {
-                brand: "Chromium".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: "Google Chrome".into(),
-                version: full_version.clone(),
-            },
-            BrandEntry {
-                brand: if older_brand {
-                    "Not-A.Brand"
-                } else if platform_version == "15.5.0" {
-                    "Not;A=Brand"
-                } else {
-                    "Not.A/Brand"
-                }
-                .into(),
-                version: crate::CHROME_NOT_A_BRAND_VERSION.clone(),
-            },
-        ]
-    };
+    let full_version = smart_spoof_chrome_full_version_rng(full_version, rng);
+    let brand = chromium_brand(info.browser);
+    let brand_version = chromium_brand_version(info, &full_version);
 
-    let mobile = mobile_model_from_user_agent(user_agent);
-    let mobile_device = mobile.is_some();
+    let full_version_list =
+        chromium_brand_list(chrome_major, &full_version, brand, &brand_version, true);
 
     HighEntropyUaData {
         architecture: architecture.to_string(),
         bitness: bitness.to_string(),
-        model: if let Some(mobile_model) = mobile {
-            mobile_model.to_string()
-        } else {
-            model
-        },
+        model,
         platform: platform.to_string(),
         platform_version,
         full_version_list,
-        ua_full_version: full_version,
-        mobile: mobile_device,
+        ua_full_version: brand_version,
+        mobile: info.is_mobile(),
         wow64_ness: false,
     }
 }
 
+/// iOS versions as `(ua token, safari version)`.
+static IOS_VERSIONS: &[(&str, &str)] = &[
+    ("17_5", "17.5"),
+    ("17_6", "17.6"),
+    ("18_1", "18.1"),
+    ("18_3", "18.3"),
+    ("18_5", "18.5"),
+    ("18_6", "18.6"),
+];
+
+/// Android phones as `(model, android version)`.
+static ANDROID_PHONES: &[(&str, &str)] = &[
+    ("Pixel 9", "15"),
+    ("Pixel 8", "14"),
+    ("Pixel 7", "14"),
+    ("SM-S928B", "14"),
+    ("SM-S918B", "14"),
+    ("SM-A546B", "14"),
+    ("CPH2581", "14"),
+    ("2201116SG", "13"),
+];
+
+/// Android tablets as `(model, android version)`.
+static ANDROID_TABLETS: &[(&str, &str)] = &[
+    ("SM-X710", "14"),
+    ("SM-X200", "14"),
+    ("Pixel Tablet", "15"),
+    ("Lenovo TB-X606F", "12"),
+];
+
+/// A generated user agent with its `navigator.userAgentData` values.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GeneratedUserAgent {
+    /// The user agent string.
+    pub user_agent: String,
+    /// The high entropy values. `None` for browsers without `navigator.userAgentData`.
+    pub high_entropy: Option<HighEntropyUaData>,
+}
+
+/// Generate a user agent for the os, browser and form factor.
+pub fn generate_user_agent(
+    os: AgentOs,
+    browser: BrowserKind,
+    form_factor: DeviceType,
+    chrome_major: Option<u32>,
+) -> GeneratedUserAgent {
+    generate_user_agent_rng(os, browser, form_factor, chrome_major, &mut rng())
+}
+
+/// Generate a user agent using the provided rng.
+///
+/// Chromium browsers get the reduced user agent with a frozen platform, `Chrome/<major>.0.0.0`
+/// and the `K` Android model, the real values only show up in the high entropy data.
+/// Edge keeps its full build in the user agent, Opera and Brave only report theirs in the
+/// high entropy data.
+/// The form factor picks the phone or tablet on Android and the desktop mode on iPad, which
+/// sends the Mac user agent without high entropy data.
+/// Safari falls back to the Mac on non Apple os, `Other` is generated as Chrome.
+/// The Chrome major defaults to the latest and picks the matching Firefox major.
+pub fn generate_user_agent_rng<R: Rng>(
+    os: AgentOs,
+    browser: BrowserKind,
+    form_factor: DeviceType,
+    chrome_major: Option<u32>,
+    rng: &mut R,
+) -> GeneratedUserAgent {
+    let chrome_major = chrome_major.unwrap_or(*BASE_CHROME_VERSION);
+    let browser = match browser {
+        BrowserKind::Other => BrowserKind::Chrome,
+        browser => browser,
+    };
+    // iOS browsers are WebKit and never expose `navigator.userAgentData`, even on the desktop site.
+    let ipad_desktop = os == AgentOs::IPad && form_factor == DeviceType::Desktop;
+    let os = match (browser, os, form_factor) {
+        (BrowserKind::Safari, AgentOs::IPhone | AgentOs::IPad, _) => os,
+        (BrowserKind::Safari, _, _) => AgentOs::Mac,
+        // iPadOS requests the desktop site with the Mac user agent.
+        (_, AgentOs::IPad, DeviceType::Desktop) => AgentOs::Mac,
+        (_, AgentOs::Unknown, _) => AgentOs::Windows,
+        _ => os,
+    };
+
+    let tablet = matches!(form_factor, DeviceType::Tablet | DeviceType::Desktop);
+    let (model, android_version) = if tablet {
+        ANDROID_TABLETS.choose(rng)
+    } else {
+        ANDROID_PHONES.choose(rng)
+    }
+    .copied()
+    .unwrap_or(("Pixel 8", "14"));
+    let (ios_version, safari_version) = IOS_VERSIONS
+        .choose(rng)
+        .copied()
+        .unwrap_or(("18_5", "18.5"));
+
+    let ios_device = match os {
+        AgentOs::IPad => "iPad; CPU OS",
+        _ => "iPhone; CPU iPhone OS",
+    };
+
+    let user_agent = match (browser, os) {
+        (BrowserKind::Firefox, _) => {
+            let firefox = chrome_major + 3;
+
+            match os {
+                AgentOs::IPhone | AgentOs::IPad => format!("Mozilla/5.0 ({ios_device} {ios_version} like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) FxiOS/{firefox}.0 Mobile/15E148 Safari/605.1.15"),
+                AgentOs::Android => format!(
+                    "Mozilla/5.0 (Android {android_version}; {}; rv:{firefox}.0) Gecko/{firefox}.0 Firefox/{firefox}.0",
+                    if tablet { "Tablet" } else { "Mobile" }
+                ),
+                _ => format!(
+                    "Mozilla/5.0 ({}; rv:{firefox}.0) Gecko/20100101 Firefox/{firefox}.0",
+                    match os {
+                        AgentOs::Mac => "Macintosh; Intel Mac OS X 10.15",
+                        AgentOs::Linux => "X11; Linux x86_64",
+                        AgentOs::ChromeOS => "X11; CrOS x86_64 14541.0.0",
+                        _ => "Windows NT 10.0; Win64; x64",
+                    }
+                ),
+            }
+        }
+        (BrowserKind::Safari, AgentOs::IPhone | AgentOs::IPad) => format!("Mozilla/5.0 ({ios_device} {ios_version} like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/{safari_version} Mobile/15E148 Safari/604.1"),
+        (BrowserKind::Safari, _) => format!("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/{safari_version} Safari/605.1.15"),
+        (_, AgentOs::IPhone | AgentOs::IPad) => {
+            let token = match browser {
+                BrowserKind::Edge => format!("EdgiOS/{}", edge_full_version(chrome_major)),
+                BrowserKind::Opera => format!("OPiOS/{}", opera_full_version(chrome_major)),
+                _ => format!(
+                    "CriOS/{}",
+                    smart_spoof_chrome_full_version_rng(&chrome_major.to_string(), rng)
+                ),
+            };
+
+            format!("Mozilla/5.0 ({ios_device} {ios_version} like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) {token} Mobile/15E148 Safari/604.1")
+        }
+        _ => {
+            let platform = match os {
+                AgentOs::Mac => "Macintosh; Intel Mac OS X 10_15_7",
+                AgentOs::Linux => "X11; Linux x86_64",
+                AgentOs::ChromeOS => "X11; CrOS x86_64 14541.0.0",
+                AgentOs::Android => "Linux; Android 10; K",
+                _ => "Windows NT 10.0; Win64; x64",
+            };
+            let mobile = if os == AgentOs::Android && !tablet {
+                "Mobile "
+            } else {
+                ""
+            };
+            // Edge keeps its full build in the user agent, Opera reduces it.
+            let fork = match (browser, os) {
+                (BrowserKind::Edge, AgentOs::Android) => format!(" EdgA/{}", edge_full_version(chrome_major)),
+                (BrowserKind::Edge, _) => format!(" Edg/{}", edge_full_version(chrome_major)),
+                (BrowserKind::Opera, _) => {
+                    let opera = opera_full_version(chrome_major);
+                    format!(" OPR/{}.0.0.0", opera.split('.').next().unwrap_or(&opera))
+                }
+                _ => String::new(),
+            };
+
+            format!("Mozilla/5.0 ({platform}) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/{chrome_major}.0.0.0 {mobile}Safari/537.36{fork}")
+        }
+    };
+
+    let info = parse_user_agent(&user_agent);
+
+    let high_entropy = if !ipad_desktop && info.allows_high_entropy() {
+        let info = UserAgentInfo { browser, ..info };
+        let mut data = build_high_entropy_data_from_info_rng(&info, rng);
+
+        if os == AgentOs::Android {
+            data.model = model.into();
+            data.platform_version = android_version.into();
+            data.bitness = "64".into();
+        }
+        data.mobile = os == AgentOs::Android && !tablet;
+
+        Some(data)
+    } else {
+        None
+    };
+
+    GeneratedUserAgent {
+        user_agent,
+        high_entropy,
+    }
+}
+
 /// Spoof navigator.userAgentData.
 pub fn spoof_user_agent_data_high_entropy_values(data: &HighEntropyUaData) -> String {
     let brands = data
-        .full_version_list
+        .brands()
         .iter()
-        .map(|b| {
-            let major = b.version.split('.').next().unwrap_or("99");
-            format!("{{brand:'{}',version:'{}'}}", b.brand, major)
-        })
+        .map(|b| format!("{{brand:'{}',version:'{}'}}", b.brand, b.version))
         .collect::<Vec<_>>()
         .join(",");
     let full_versions = data
@@ -420,13 +784,14 @@ pub fn spoof_user_agent_data_high_entropy_values(data: &HighEntropyUaData) -> St
         .join(",");
 
     format!(
-        r###"(()=>{{if(typeof NavigatorUAData==='undefined')window.NavigatorUAData=function NavigatorUAData(){{}};const p=NavigatorUAData.prototype,v=Object.create(p),d={{architecture:'{}',bitness:'{}',model:'{}',platformVersion:'{}',fullVersionList:[{}],brands:[{}],mobile:!1,platform:'{}'}};Object.defineProperties(v,{{brands:{{value:d.brands,enumerable:true}},mobile:{{value:d.mobile,enumerable:true}},platform:{{value:d.platform,enumerable:true}}}});Object.defineProperties(p,{{brands:{{get:function brands(){{return this.brands}}}},mobile:{{get:function mobile(){{return this.mobile}}}},platform:{{get:function platform(){{return this.platform}}}}}});function getHighEntropyValues(keys){{keys=Array.isArray(keys)?keys:[];var out={{}};for(var i=0;i<keys.length;i++){{var k=keys[i];if(k==='architecture'||k==='bitness'||k==='model'||k==='platformVersion'||k==='uaFullVersion'||k==='fullVersionList'){{out[k]=(k==='uaFullVersion'?'{}':d[k]);}}}}return Promise.resolve(Object.assign({{brands:d.brands,mobile:d.mobile,platform:d.platform}},out))}};Object.defineProperty(p,'getHighEntropyValues',{{value:getHighEntropyValues}});function toJSON(){{return{{brands:this.brands,mobile:this.mobile,platform:this.platform}}}}Object.defineProperty(p,'toJSON',{{value:toJSON}});const f=()=>v;Object.defineProperty(f,'toString',{{value:()=>`function get userAgentData() {{ [native code] }}`}});Object.defineProperty(Navigator.prototype,'userAgentData',{{get:f,configurable:!0}});}})();"###,
+        r###"(()=>{{if(typeof NavigatorUAData==='undefined')window.NavigatorUAData=function NavigatorUAData(){{}};const p=NavigatorUAData.prototype,v=Object.create(p),d={{architecture:'{}',bitness:'{}',model:'{}',platformVersion:'{}',fullVersionList:[{}],brands:[{}],mobile:{},platform:'{}'}};Object.defineProperties(v,{{brands:{{value:d.brands,enumerable:true}},mobile:{{value:d.mobile,enumerable:true}},platform:{{value:d.platform,enumerable:true}}}});Object.defineProperties(p,{{brands:{{get:function brands(){{return this.brands}}}},mobile:{{get:function mobile(){{return this.mobile}}}},platform:{{get:function platform(){{return this.platform}}}}}});function getHighEntropyValues(keys){{keys=Array.isArray(keys)?keys:[];var out={{}};for(var i=0;i<keys.length;i++){{var k=keys[i];if(k==='architecture'||k==='bitness'||k==='model'||k==='platformVersion'||k==='uaFullVersion'||k==='fullVersionList'){{out[k]=(k==='uaFullVersion'?'{}':d[k]);}}}}return Promise.resolve(Object.assign({{brands:d.brands,mobile:d.mobile,platform:d.platform}},out))}};Object.defineProperty(p,'getHighEntropyValues',{{value:getHighEntropyValues}});function toJSON(){{return{{brands:this.brands,mobile:this.mobile,platform:this.platform}}}}Object.defineProperty(p,'toJSON',{{value:toJSON}});const f=()=>v;Object.defineProperty(f,'toString',{{value:()=>`function get userAgentData() {{ [native code] }}`}});Object.defineProperty(Navigator.prototype,'userAgentData',{{get:f,configurable:!0}});}})();"###,
         data.architecture,
         data.bitness,
         data.model,
         data.platform_version,
         full_versions,
         brands,
+        if data.mobile { "!0" } else { "!1" },
         data.platform,
         data.ua_full_version
     )
@@ -563,4 +928,199 @@ mod tests {
         assert!(data.platform == "macOS");
         assert!(data.platform_version == "15.5.0");
     }
+
+    #[test]
+    fn grease_matches_real_chrome() {
+        for (major, sec_ch_ua) in [
+            (
+                138,
+                r#""Not)A;Brand";v="8", "Chromium";v="138", "Google Chrome";v="138""#,
+            ),
+            (
+                139,
+                r#""Not;A=Brand";v="99", "Google Chrome";v="139", "Chromium";v="139""#,
+            ),
+            (
+                141,
+                r#""Google Chrome";v="141", "Not?A_Brand";v="8", "Chromium";v="141""#,
+            ),
+            (
+                142,
+                r#""Chromium";v="142", "Google Chrome";v="142", "Not_A Brand";v="99""#,
+            ),
+            (
+                143,
+                r#""Google Chrome";v="143", "Chromium";v="143", "Not A(Brand";v="24""#,
+            ),
+        ] {
+            let version = format!("{major}.0.7000.10");
+            let brands = chromium_brand_list(major, &version, "Google Chrome", &version, false);
+            assert_eq!(format_sec_ch_ua(&brands), sec_ch_ua);
+
+            let full = chromium_brand_list(major, &version, "Google Chrome", &version, true);
+            assert!(full.iter().any(|b| b.version == version));
+            assert!(full
+                .iter()
+                .any(|b| b.brand.starts_with("Not") && b.version.ends_with(".0.0.0")));
+        }
+
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36 Edg/138.0.0.0";
+        let data = build_high_entropy_data(&Some(ua));
+        let brands: Vec<_> = data
+            .full_version_list
+            .iter()
+            .map(|b| b.brand.as_str())
+            .collect();
+        assert_eq!(brands, ["Not)A;Brand", "Chromium", "Microsoft Edge"]);
+    }
+
+    #[test]
+    fn fork_brands_in_high_entropy_data() {
+        let base = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+
+        for (suffix, brand, version) in [
+            (" Edg/138.0.3351.95", "Microsoft Edge", "138.0.3351.95"),
+            (" OPR/122.0.5643.71", "Opera", "122.0.5643.71"),
+            (" Brave/138", "Brave", "138.0.0.0"),
+        ] {
+            let ua = format!("{base}{suffix}");
+            let data = build_high_entropy_data(&Some(&ua));
+
+            assert!(data
+                .full_version_list
+                .iter()
+                .any(|b| b.brand == brand && b.version == version));
+            assert!(!data
+                .full_version_list
+                .iter()
+                .any(|b| b.brand == "Google Chrome"));
+            assert_eq!(data.ua_full_version, version);
+            assert!(spoof_user_agent_data_high_entropy_values(&data).contains(brand));
+        }
+    }
+
+    #[test]
+    fn generated_user_agents_parse_back() {
+        use crate::spoof_viewport::DeviceType;
+        use crate::user_agent::BrowserEngine;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(11);
+
+        for os in [
+            AgentOs::Windows,
+            AgentOs::Mac,
+            AgentOs::Linux,
+            AgentOs::ChromeOS,
+            AgentOs::Android,
+            AgentOs::IPhone,
+            AgentOs::IPad,
+        ] {
+            for browser in [
+                BrowserKind::Chrome,
+                BrowserKind::Brave,
+                BrowserKind::Edge,
+                BrowserKind::Opera,
+                BrowserKind::Firefox,
+                BrowserKind::Safari,
+            ] {
+                for form_factor in [DeviceType::Mobile, DeviceType::Tablet, DeviceType::Desktop] {
+                    let generated =
+                        generate_user_agent_rng(os, browser, form_factor, Some(138), &mut rng);
+                    let ua = generated.user_agent.as_str();
+                    let info = parse_user_agent(ua);
+
+                    // Brave sends the Chrome user agent.
+                    if browser == BrowserKind::Brave {
+                        assert_eq!(info.browser, BrowserKind::Chrome, "{ua}");
+                    } else {
+                        assert_eq!(info.browser, browser, "{ua}");
+                    }
+                    if browser != BrowserKind::Safari && form_factor != DeviceType::Desktop {
+                        assert_eq!(info.os, os, "{ua}");
+                    }
+                    if info.engine == BrowserEngine::Blink {
+                        assert!(ua.contains("Chrome/138.0.0.0"), "{ua}");
+                    }
+
+                    match generated.high_entropy {
+                        Some(data) => {
+                            assert!(info.is_chromium(), "{ua}");
+                            assert!(data
+                                .full_version_list
+                                .iter()
+                                .any(|b| b.brand == "Chromium" && b.version.starts_with("138.")));
+                            assert!(data
+                                .full_version_list
+                                .iter()
+                                .any(|b| b.brand == chromium_brand(browser)));
+                            assert_eq!(data.platform, info.os.agent_os_string(), "{ua}");
+                            assert_eq!(data.mobile, info.form_factor == DeviceType::Mobile);
+                            if info.os == AgentOs::Android {
+                                assert_ne!(data.model, "K");
+                            }
+                            if info.os == AgentOs::Windows {
+                                assert!(data.platform_version.ends_with(".0.0"), "{ua}");
+                            }
+                            match browser {
+                                BrowserKind::Edge => {
+                                    assert_eq!(data.ua_full_version, edge_full_version(138));
+                                    assert!(ua.contains(&data.ua_full_version), "{ua}");
+                                }
+                                BrowserKind::Opera => {
+                                    assert_eq!(data.ua_full_version, opera_full_version(138));
+                                    assert!(ua.contains("OPR/122.0.0.0"), "{ua}");
+                                }
+                                BrowserKind::Brave => {
+                                    assert_eq!(data.ua_full_version, "138.0.0.0")
+                                }
+                                _ => (),
+                            }
+                            let script = spoof_user_agent_data_high_entropy_values(&data);
+                            assert!(script.contains(if data.mobile {
+                                "mobile:!0"
+                            } else {
+                                "mobile:!1"
+                            }));
+                        }
+                        _ => assert!(
+                            os == AgentOs::IPad && form_factor == DeviceType::Desktop
+                                || !crate::ua_allows_gethighentropy(ua),
+                            "{ua}"
+                        ),
+                    }
+                }
+            }
+        }
+
+        let ua = generate_user_agent_rng(
+            AgentOs::Android,
+            BrowserKind::Chrome,
+            DeviceType::Mobile,
+            Some(139),
+            &mut rng,
+        )
+        .user_agent;
+        assert_eq!(ua, "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Mobile Safari/537.36");
+
+        let ipad = generate_user_agent_rng(
+            AgentOs::IPad,
+            BrowserKind::Chrome,
+            DeviceType::Desktop,
+            None,
+            &mut rng,
+        );
+        assert!(ipad.user_agent.contains("Macintosh"));
+        assert_eq!(ipad.high_entropy, None);
+    }
+
+    #[test]
+    fn fork_releases_follow_the_chromium_major() {
+        assert_eq!(edge_full_version(139), "139.0.3405.125");
+        assert_eq!(opera_full_version(138), "122.0.5643.150");
+        assert!(edge_full_version(150).starts_with("150.0."));
+        assert!(opera_full_version(150).starts_with("134.0."));
+        assert!(edge_full_version(120).starts_with("120.0."));
+    }
 }
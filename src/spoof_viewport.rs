@@ -1,6 +1,5 @@
 use rand::distr::{weighted::WeightedIndex, Distribution};
 use rand::prelude::IndexedRandom;
-use rand::rngs::ThreadRng;
 use rand::Rng;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -68,7 +67,7 @@ impl Viewport {
 /// to simulate and test varying screen resolutions and viewports in
 /// web development, ensuring that web applications render correctly
 /// across different platforms.
-#[derive(Default, Clone, Copy, Debug)]
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeviceType {
     /// Represents a mobile device.
@@ -103,7 +102,7 @@ pub fn randomize_viewport(device: &DeviceType) -> Viewport {
 }
 
 /// Randomize viewport dimensions based on device type.
-pub fn randomize_viewport_rng(device: &DeviceType, rng: &mut ThreadRng) -> Viewport {
+pub fn randomize_viewport_rng<R: Rng>(device: &DeviceType, rng: &mut R) -> Viewport {
     match device {
         DeviceType::Mobile => {
             let width = rng.random_range(320..=480);
@@ -123,18 +122,49 @@ pub fn randomize_viewport_rng(device: &DeviceType, rng: &mut ThreadRng) -> Viewp
     }
 }
 
+/// The screen sizes of the iPad models in CSS pixels (portrait).
+pub static IPAD_SCREEN_SIZES: &[(u32, u32)] = &[
+    (744, 1133),  // iPad mini 6th and 7th gen
+    (810, 1080),  // iPad 9th gen
+    (820, 1180),  // iPad 10th and 11th gen, iPad Air 11-inch
+    (834, 1194),  // iPad Pro 11-inch
+    (834, 1210),  // iPad Pro 11-inch (M4)
+    (1024, 1366), // iPad Pro 12.9-inch, iPad Air 13-inch
+    (1032, 1376), // iPad Pro 13-inch (M4)
+];
+
+/// Randomize an iPad viewport with touch and the retina scale factor.
+pub fn randomize_ipad_viewport() -> Viewport {
+    randomize_ipad_viewport_rng(&mut rand::rng())
+}
+
+/// Randomize an iPad viewport with touch and the retina scale factor using the provided rng.
+pub fn randomize_ipad_viewport_rng<R: Rng>(rng: &mut R) -> Viewport {
+    let (width, height) = *IPAD_SCREEN_SIZES.choose(rng).unwrap_or(&(820, 1180));
+
+    Viewport {
+        width,
+        height,
+        device_scale_factor: Some(2.0),
+        has_touch: true,
+        ..Default::default()
+    }
+}
+
 /// Get a random viewport by selecting a random device type first. The weights are aligned in favor of desktop.
 pub fn get_random_viewport() -> Viewport {
     get_random_viewport_rng(&mut rand::rng())
 }
 
 /// Get a random viewport by selecting a random device type first. The weights are aligned in favor of desktop.
-pub fn get_random_viewport_rng(rng: &mut ThreadRng) -> Viewport {
+pub fn get_random_viewport_rng<R: Rng>(rng: &mut R) -> Viewport {
     let device_types = [DeviceType::Mobile, DeviceType::Tablet, DeviceType::Desktop];
 
-    randomize_viewport(if let Ok(dist) = WeightedIndex::new(&[1, 1, 3]) {
-        &device_types[dist.sample(rng)]
+    let device = if let Ok(dist) = WeightedIndex::new([1, 1, 3]) {
+        device_types[dist.sample(rng)]
     } else {
-        device_types.choose(rng).unwrap_or(&DeviceType::Desktop)
-    })
+        *device_types.choose(rng).unwrap_or(&DeviceType::Desktop)
+    };
+
+    randomize_viewport_rng(&device, rng)
 }
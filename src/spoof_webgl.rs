@@ -1,3 +1,5 @@
+use crate::profiles::webgl::WebGlCapabilities;
+
 pub const HIDE_WEBGL: &str = r#"(()=>{const v='Google Inc. (NVIDIA)',r='ANGLE (NVIDIA, NVIDIA GeForce GTX 1050 Direct3D11 vs_5_0 ps_5_0, D3D11-27.21.14.5671)',f=p=>p===37445?v:p===37446?r:null;for(const k of['WebGLRenderingContext','WebGL2RenderingContext']){const o=globalThis[k]?.prototype?.getParameter;if(o){Object.defineProperty(globalThis[k].prototype,'getParameter',{value:function(p){const spoof=f(p);return spoof??o.call(this,p);},configurable:true});}}const wrap=W=>function(u,...a){const abs=new URL(u,location.href).toString(),b=`(()=>{const v='${v}',r='${r}',f=p=>p===37445?v:p===37446?r:null;for(const k of['WebGLRenderingContext','WebGL2RenderingContext']){const o=self[k]?.prototype?.getParameter;if(o){Object.defineProperty(self[k].prototype,'getParameter',{value:function(p){const s=f(p);return s??o.call(this,p);},configurable:true});}}fetch("${abs}").then(r=>r.text()).then(t=>(0,eval)(t));})();`;return new W(URL.createObjectURL(new Blob([b],{type:'application/javascript'})),...a)};window.Worker=wrap(window.Worker);window.SharedWorker=wrap(window.SharedWorker);})();"#;
 pub const HIDE_WEBGL_MAC: &str = r#"(()=>{const v='Google Inc. (Apple)',r='ANGLE (Apple, ANGLE Metal Renderer: Apple M1 Max, Unspecified Version)',f=p=>p===37445?v:p===37446?r:null;for(const k of['WebGLRenderingContext','WebGL2RenderingContext']){const o=globalThis[k]?.prototype?.getParameter;if(o){Object.defineProperty(globalThis[k].prototype,'getParameter',{value:function(p){const spoof=f(p);return spoof??o.call(this,p);},configurable:true});}}const wrap=W=>function(u,...a){const abs=new URL(u,location.href).toString(),b=`(()=>{const v='${v}',r='${r}',f=p=>p===37445?v:p===37446?r:null;for(const k of['WebGLRenderingContext','WebGL2RenderingContext']){const o=self[k]?.prototype?.getParameter;if(o){Object.defineProperty(self[k].prototype,'getParameter',{value:function(p){const s=f(p);return s??o.call(this,p);},configurable:true});}}fetch("${abs}").then(r=>r.text()).then(t=>(0,eval)(t));})();`;return new W(URL.createObjectURL(new Blob([b],{type:'application/javascript'})),...a)};window.Worker=wrap(window.Worker);window.SharedWorker=wrap(window.SharedWorker);})();"#;
 
@@ -22,3 +24,277 @@ pub fn hide_webgl_gpu_spoof(vendor: &str, renderer: &str) -> String {
         worker_script = worker_script
     )
 }
+
+/// Serve the WebGL capabilities of the gpu on WebGL1 and WebGL2 contexts. Works in the window
+/// and in workers.
+///
+/// Covers the limits from `getParameter`, the version strings, `getShaderPrecisionFormat`,
+/// `getSupportedExtensions`, `getExtension` and the `antialias` context attribute. Values are
+/// fixed so two reads always agree.
+pub fn spoof_webgl_capabilities_script(caps: &WebGlCapabilities) -> String {
+    let list = |values: &[&str]| {
+        let quoted: Vec<String> = values.iter().map(|v| format!("'{v}'")).collect();
+        format!("[{}]", quoted.join(","))
+    };
+    let num = [
+        (3379, caps.max_texture_size),
+        (34076, caps.max_cube_map_texture_size),
+        (34024, caps.max_renderbuffer_size),
+        (34921, caps.max_vertex_attribs),
+        (36347, caps.max_vertex_uniform_vectors),
+        (36349, caps.max_fragment_uniform_vectors),
+        (36348, caps.max_varying_vectors),
+        (34930, caps.max_texture_image_units),
+        (35660, caps.max_vertex_texture_image_units),
+        (35661, caps.max_combined_texture_image_units),
+        (34047, caps.max_anisotropy),
+        (36183, caps.max_samples),
+        (32883, caps.max_3d_texture_size),
+        (35071, caps.max_array_texture_layers),
+        (34852, caps.max_draw_buffers),
+        (36063, caps.max_color_attachments),
+    ]
+    .iter()
+    .map(|(k, v)| format!("{k}:{v}"))
+    .collect::<Vec<_>>()
+    .join(",");
+    let precision = caps
+        .shader_precision
+        .iter()
+        .map(|[min, max, precision]| format!("[{min},{max},{precision}]"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        r#"(()=>{{try{{const n={{{num}}},t={{3386:[Int32Array,[{viewport_w},{viewport_h}]],33902:[Float32Array,[{line_min},{line_max}]],33901:[Float32Array,[{point_min},{point_max}]]}},q=[{precision}],e1={extensions},e2={extensions_webgl2},aa={antialias},v=[['WebGL 1.0 (OpenGL ES 2.0 Chromium)','WebGL GLSL ES 1.0 (OpenGL ES GLSL ES 1.0 Chromium)'],['WebGL 2.0 (OpenGL ES 3.0 Chromium)','WebGL GLSL ES 3.00 (OpenGL ES GLSL ES 3.0 Chromium)']],G2=self.WebGL2RenderingContext,w2=g=>!!G2&&g instanceof G2,M=new WeakMap(),A=new WeakMap(),m=(f,k)=>{{Object.defineProperty(f,'name',{{value:k}});f.toString=()=>`function ${{k}}() {{ [native code] }}`;return f}},d=(P,k,f)=>{{if(P&&P[k])Object.defineProperty(P,k,{{value:m(f,k),writable:!0,configurable:!0,enumerable:!0}})}},SP=self.WebGLShaderPrecisionFormat&&self.WebGLShaderPrecisionFormat.prototype;if(SP)['rangeMin','rangeMax','precision'].forEach((k,i)=>{{const o=Object.getOwnPropertyDescriptor(SP,k);if(o&&o.get)Object.defineProperty(SP,k,{{...o,get:m(function(){{const r=M.get(this);return r?r[i]:o.get.call(this)}},'get '+k)}})}});for(const k of['HTMLCanvasElement','OffscreenCanvas']){{const P=self[k]&&self[k].prototype,g=P&&P.getContext;d(P,'getContext',function(y,o){{const c=g.call(this,y,o);if(c&&/webgl/.test(y))A.set(c,!(o&&o.antialias===!1));return c}})}}for(const k of['WebGLRenderingContext','WebGL2RenderingContext']){{const C=self[k];if(!C)continue;const P=C.prototype,g=P.getParameter,sp=P.getShaderPrecisionFormat,se=P.getSupportedExtensions,ge=P.getExtension,ca=P.getContextAttributes;d(P,'getParameter',function(p){{const r=g.call(this,p);if(r==null)return r;const b=w2(this)?1:0;if(p===7936)return'WebKit';if(p===7937)return'WebKit WebGL';if(p===7938)return v[b][0];if(p===35724)return v[b][1];if(p in n)return n[p];if(p in t)return new t[p][0](t[p][1]);return r}});d(P,'getShaderPrecisionFormat',function(a,b){{const r=sp.call(this,a,b),i=b-36336;if(r&&i>=0&&i<6)M.set(r,q[i]);return r}});d(P,'getSupportedExtensions',function(){{const r=se.call(this);return r?[...(w2(this)?e2:e1)]:r}});d(P,'getExtension',function(a){{return(w2(this)?e2:e1).includes(a)?ge.call(this,a):null}});d(P,'getContextAttributes',function(){{const r=ca.call(this);if(r)r.antialias=aa&&(A.has(this)?A.get(this):r.antialias);return r}})}}}}catch(_){{}}}})();"#,
+        viewport_w = caps.max_viewport_dims[0],
+        viewport_h = caps.max_viewport_dims[1],
+        line_min = caps.aliased_line_width_range[0],
+        line_max = caps.aliased_line_width_range[1],
+        point_min = caps.aliased_point_size_range[0],
+        point_max = caps.aliased_point_size_range[1],
+        extensions = list(caps.extensions),
+        extensions_webgl2 = list(caps.extensions_webgl2),
+        antialias = if caps.antialias { "!0" } else { "!1" },
+    )
+}
+
+/// Spoof the WebGL image with a small per channel shift on `readPixels`, the `toDataURL` and
+/// `toBlob` exports of WebGL canvases and `OffscreenCanvas.convertToBlob`.
+///
+/// The shift is derived from the seed so the same seed answers the same pixels across calls and
+/// pages. WebGL canvases export through a 2d canvas holding the noised pixels. The 2d canvas
+/// noise skips that canvas so repeated exports stay the same.
+pub fn spoof_webgl_noise_script(seed: u32) -> String {
+    format!(
+        r#"(()=>{{if(typeof WebGLRenderingContext==='undefined'&&typeof OffscreenCanvas==='undefined')return;const s={seed},h=x=>{{x=Math.imul(x^x>>>16,0x45d9f3b);x=Math.imul(x^x>>>16,0x45d9f3b);return(x^x>>>16)>>>0}},m=[-2,-1,1,2],k=h(s^0x5bd1e995),o=[m[k&3],m[k>>>2&3],m[k>>>4&3]],G=new WeakMap(),O=new WeakSet(),n=(P,k,v)=>{{const g=P&&P[k];if(!g)return;const w={{[k](...a){{return v.call(this,g,a)}}}}[k];w.toString=()=>`function ${{k}}() {{ [native code] }}`;Object.defineProperty(P,k,{{value:w,writable:!0,configurable:!0,enumerable:!0}})}},N=(d,i,l)=>{{for(l=Math.min(d.length,i+l);i+3<l;i+=4)if(d[i+3])for(let c=0;c<3;c++){{const v=d[i+c]+o[c];d[i+c]=v<0?0:v>255?255:v}}}},X=g=>{{const W=g.drawingBufferWidth,H=g.drawingBufferHeight,p=new Uint8Array(W*H*4),q=new Uint8ClampedArray(W*H*4),l=W*4;g.readPixels(0,0,W,H,6408,5121,p);for(let y=0;y<H;y++)q.set(p.subarray((H-1-y)*l,(H-y)*l),y*l);return new ImageData(q,W,H)}};for(const C of['WebGLRenderingContext','WebGL2RenderingContext'])n(self[C]&&self[C].prototype,'readPixels',function(g,a){{const r=g.apply(this,a),p=a[6];if(a[4]===6408&&a[5]===5121&&p instanceof Uint8Array)N(p,a[7]|0,a[2]*a[3]*4);return r}});for(const C of['HTMLCanvasElement','OffscreenCanvas'])n(self[C]&&self[C].prototype,'getContext',function(g,a){{if(O.has(this))return null;const c=g.apply(this,a);if(c)G.set(this,c);return c}});if(typeof HTMLCanvasElement!=='undefined')for(const k of['toDataURL','toBlob'])n(HTMLCanvasElement.prototype,k,function(g,a){{const c=G.get(this);if(c&&c.readPixels)try{{const t=document.createElement('canvas');t.width=this.width;t.height=this.height;t.getContext('2d').putImageData(X(c),0,0);O.add(t);return t[k](...a)}}catch(e){{}}return g.apply(this,a)}});if(typeof OffscreenCanvas!=='undefined')n(OffscreenCanvas.prototype,'convertToBlob',function(g,a){{const c=G.get(this);let d=null;try{{if(c&&c.readPixels)d=X(c);else if(c&&c.getImageData){{d=c.getImageData(0,0,this.width,this.height);N(d.data,0,d.data.length)}}}}catch(e){{}}if(!d)return g.apply(this,a);const t=new OffscreenCanvas(this.width,this.height);t.getContext('2d').putImageData(d,0,0);return g.apply(t,a)}})}})();"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::webgl::{WEBGL_ADRENO, WEBGL_D3D11_NVIDIA, WEBGL_METAL_APPLE};
+    use crate::EmulationConfiguration;
+
+    #[test]
+    fn capabilities_follow_the_renderer() {
+        assert_eq!(
+            WebGlCapabilities::for_renderer(
+                "ANGLE (NVIDIA, NVIDIA GeForce RTX 3060 (0x00002504) Direct3D11 vs_5_0 ps_5_0, D3D11)"
+            ),
+            WEBGL_D3D11_NVIDIA
+        );
+        assert_eq!(
+            WebGlCapabilities::for_renderer(
+                "ANGLE (Apple, ANGLE Metal Renderer: Apple M2, Unspecified Version)"
+            ),
+            WEBGL_METAL_APPLE
+        );
+        assert_eq!(
+            WebGlCapabilities::for_renderer("Adreno (TM) 740"),
+            WEBGL_ADRENO
+        );
+
+        let script = spoof_webgl_capabilities_script(&WEBGL_D3D11_NVIDIA);
+        assert!(script.contains(&format!("3379:{}", WEBGL_D3D11_NVIDIA.max_texture_size)));
+        assert!(script.contains("'WEBGL_debug_renderer_info'"));
+        assert!(script.contains("getShaderPrecisionFormat"));
+    }
+
+    #[test]
+    fn capabilities_fall_back_to_the_driver() {
+        let webgl = r#"
+            class WebGLRenderingContext {
+                bufferData() {}
+                getParameter(p) { return { 3379: 4096, 3410: 8, 3411: 8, 3412: 8, 3413: 8 }[p] ?? 7; }
+            }
+            class WebGL2RenderingContext extends WebGLRenderingContext {}
+            Object.assign(globalThis, { window: globalThis, HTMLElement: class {}, WebGLRenderingContext, WebGL2RenderingContext });"#;
+        let script = format!(
+            "{webgl}{}{}",
+            crate::spoof_gpu::SPOOF_FINGERPRINT,
+            spoof_webgl_capabilities_script(&WEBGL_D3D11_NVIDIA)
+        );
+        let check = r#"
+            return [new WebGLRenderingContext(), new WebGL2RenderingContext()]
+                .map(g => [3379, 3410, 3411, 3412, 3413, 1234].map(p => g.getParameter(p)));"#;
+
+        let value = crate::test_js::eval_js(&script, check, "UTC");
+
+        let row = serde_json::json!([WEBGL_D3D11_NVIDIA.max_texture_size, 8, 8, 8, 8, 7]);
+        assert_eq!(value, serde_json::json!([row, row]));
+    }
+
+    #[test]
+    fn webgl_noise_is_seeded() {
+        let script = spoof_webgl_noise_script(42);
+        assert!(script.contains("const s=42,"));
+        assert!(script.contains("readPixels"));
+        assert!(script.contains("convertToBlob"));
+        assert!(!script.contains("Math.random"));
+        assert_ne!(script, spoof_webgl_noise_script(43));
+    }
+
+    /// A fake 2x2 WebGL canvas drawing two rows and a 2d canvas exporting its pixels.
+    const CANVAS: &str = r#"
+        class ImageData { constructor(data, width, height) { Object.assign(this, { data, width, height }); } }
+        class HTMLElement {}
+        class CanvasRenderingContext2D {
+            putImageData(d) { this.data = [...d.data]; }
+            getImageData(x, y, w, h) { return new ImageData(new Uint8ClampedArray(this.data || new Array(w * h * 4).fill(0).map((_, i) => [10, 20, 30, 255][i % 4])), w, h); }
+        }
+        class WebGLRenderingContext {
+            constructor() { this.drawingBufferWidth = 2; this.drawingBufferHeight = 2; }
+            readPixels(x, y, w, h, f, t, p, o = 0) {
+                for (let i = 0; i < w * h; i++) p.set([50 + 10 * Math.floor(i / w), 0, 255, i === 1 ? 0 : 255], o + 4 * i);
+            }
+        }
+        class WebGL2RenderingContext extends WebGLRenderingContext {}
+        class HTMLCanvasElement extends HTMLElement {
+            constructor() { super(); this.width = 2; this.height = 2; }
+            getContext(t) {
+                const k = t === '2d' ? '2d' : 'webgl';
+                if (this.c) return this.k === k ? this.c : null;
+                this.k = k;
+                this.c = k === '2d' ? new CanvasRenderingContext2D() : new WebGLRenderingContext();
+                this.c.canvas = this;
+                return this.c;
+            }
+            toDataURL() { return this.k === '2d' && this.c.data || 'blank'; }
+            toBlob(f) { f(this.k === '2d' && this.c.data || 'blank'); }
+        }
+        class OffscreenCanvas extends HTMLCanvasElement {
+            constructor(w, h) { super(); this.width = w; this.height = h; }
+            convertToBlob() { return Promise.resolve(this.c.data || 'blank'); }
+        }
+        const document = { createElement: () => new HTMLCanvasElement() };
+        Object.assign(globalThis, { window: globalThis, ImageData, HTMLElement, CanvasRenderingContext2D,
+            WebGLRenderingContext, WebGL2RenderingContext, HTMLCanvasElement, OffscreenCanvas, document });"#;
+
+    /// Run the script over the fake canvases and read back the pixels of `readPixels`,
+    /// `toDataURL` and `convertToBlob`.
+    fn noise(seed: u32) -> serde_json::Value {
+        let script = format!("{CANVAS}{}", spoof_webgl_noise_script(seed));
+        let check = r#"
+            const c = new HTMLCanvasElement(), g = c.getContext('webgl'), p = new Uint8Array(16), rgb = new Uint8Array(16);
+            g.readPixels(0, 0, 2, 2, 6408, 5121, p);
+            g.readPixels(0, 0, 2, 2, 6407, 5121, rgb);
+            const o = new OffscreenCanvas(1, 1);
+            o.getContext('2d');
+            return { pixels: [...p], rgb: [...rgb], url: c.toDataURL(), blob: await o.convertToBlob() };"#;
+
+        crate::test_js::eval_js(&script, check, "UTC")
+    }
+
+    #[test]
+    fn webgl_noise_shifts_the_exported_pixels() {
+        let value = noise(42);
+
+        let blob: Vec<i64> = serde_json::from_value(value["blob"].clone()).expect("blob");
+        let shift = [blob[0] - 10, blob[1] - 20, blob[2] - 30];
+        assert!(shift.iter().all(|s| [-2, -1, 1, 2].contains(s)));
+        assert_eq!(blob[3], 255);
+
+        // the color channels clamp, transparent pixels and other formats are left alone
+        let px = |red: i64| {
+            vec![
+                red + shift[0],
+                shift[1].max(0),
+                (255 + shift[2]).min(255),
+                255,
+            ]
+        };
+        let transparent = vec![50, 0, 255, 0];
+        let pixels = [px(50), transparent.clone(), px(60), px(60)].concat();
+        assert_eq!(value["pixels"], serde_json::json!(pixels));
+        assert_eq!(
+            value["rgb"],
+            serde_json::json!([50, 0, 255, 255, 50, 0, 255, 0, 60, 0, 255, 255, 60, 0, 255, 255])
+        );
+        // the WebGL canvas exports its flipped buffer through a 2d canvas
+        assert_eq!(
+            value["url"],
+            serde_json::json!([px(60), px(60), px(50), transparent].concat())
+        );
+
+        assert_eq!(noise(42), value);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn gpu_profile_without_webgl_uses_the_renderer() {
+        let profile = crate::profiles::gpu::select_random_gpu_profile(crate::configs::AgentOs::Mac);
+        let json = serde_json::to_value(profile).expect("serialize");
+        let restored: crate::profiles::gpu::GpuProfile =
+            serde_json::from_value(json.clone()).expect("deserialize");
+        assert_eq!(&restored, profile);
+
+        let mut legacy = json;
+        legacy.as_object_mut().unwrap().remove("webgl");
+        legacy.as_object_mut().unwrap().remove("webgpu_features");
+        let restored: crate::profiles::gpu::GpuProfile =
+            serde_json::from_value(legacy).expect("deserialize");
+        assert_eq!(
+            restored.webgl,
+            WebGlCapabilities::for_renderer(profile.webgl_renderer)
+        );
+        assert_eq!(restored.webgpu_features, profile.webgpu_features);
+    }
+
+    #[test]
+    fn webgl_exports_are_stable_with_the_canvas_noise() {
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.tier = crate::configs::Tier::None;
+        config.fingerprint = crate::Fingerprint::Basic;
+        let persona = crate::Persona::generate(ua, &config, &None, None, false, &None);
+        let script = format!("{CANVAS}{}", persona.emulate(&None).expect("script"));
+        let check = r#"
+            const c = new HTMLCanvasElement();
+            c.getContext('webgl');
+            const urls = [];
+            for (let i = 0; i < 5; i++) urls.push(c.toDataURL());
+            return urls;"#;
+
+        let value = crate::test_js::eval_js(&script, check, "UTC");
+
+        let urls = value.as_array().expect("urls");
+        assert_ne!(urls[0], "blank");
+        assert!(urls.iter().all(|url| *url == urls[0]));
+        // the alpha channel is left alone
+        assert_eq!(urls[0][3], 255);
+        assert_eq!(urls[0][15], 0);
+    }
+
+    #[test]
+    fn webgl_noise_follows_the_persona_seed() {
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.fingerprint = crate::Fingerprint::Basic;
+        let persona = crate::Persona::generate(ua, &config, &None, None, false, &None);
+        let script = persona.emulate(&None).unwrap_or_default();
+
+        assert!(script.contains(&spoof_webgl_noise_script(persona.noise_seed)));
+    }
+}
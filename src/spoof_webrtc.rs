@@ -0,0 +1,203 @@
+use std::net::IpAddr;
+
+/// How WebRTC exposes the network addresses of the machine. The peer connection is kept so
+/// the page still sees a regular Chrome.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WebRtcMode {
+    /// Only relay candidates are exposed like the Chrome `disable_non_proxied_udp` policy.
+    DisableNonProxiedUdp,
+    #[default]
+    /// Host candidates are replaced with mDNS `.local` names and server reflexive candidates
+    /// are dropped.
+    Mdns,
+    /// Host candidates use mDNS names and server reflexive candidates report the public ip
+    /// e.g. the proxy exit address.
+    PublicIp(IpAddr),
+}
+
+impl WebRtcMode {
+    /// The mode name used by the script.
+    fn name(&self) -> &'static str {
+        match self {
+            WebRtcMode::DisableNonProxiedUdp => "relay",
+            WebRtcMode::Mdns => "mdns",
+            WebRtcMode::PublicIp(_) => "ip",
+        }
+    }
+}
+
+/// Spoof the ICE candidates of `RTCPeerConnection` in the `icecandidate` events, the local
+/// session descriptions and `getStats` so the local ip does not leak.
+pub fn spoof_webrtc_script(mode: WebRtcMode) -> String {
+    let (ip, version) = match mode {
+        WebRtcMode::PublicIp(ip @ IpAddr::V4(_)) => (ip.to_string(), "IP4"),
+        WebRtcMode::PublicIp(ip @ IpAddr::V6(_)) => (ip.to_string(), "IP6"),
+        _ => (String::new(), "IP4"),
+    };
+
+    format!(
+        r#"(()=>{{const R=window.RTCPeerConnection;if(!R)return;const m='{mode}',p='{ip}',v='{version}',P=R.prototype,I=window.RTCIceCandidate,E=window.RTCPeerConnectionIceEvent,S=window.RTCSessionDescription,h=new Map(),W=new WeakMap(),H=new WeakMap(),u=()=>crypto.randomUUID?crypto.randomUUID():'10000000-1000-4000-8000-100000000000'.replace(/[018]/g,c=>(c^crypto.getRandomValues(new Uint8Array(1))[0]&15>>c/4).toString(16)),k=a=>{{if(a.endsWith('.local'))return a;let n=h.get(a);if(!n){{n=u()+'.local';h.set(a,n)}}return n}},x=c=>{{const f=c.split(' ');if(f.length<8)return c;const t=f[7];if(t==='host'&&m!=='relay')f[4]=k(f[4]);else if((t==='srflx'||t==='prflx')&&m==='ip')f[4]=p;else if(t!=='relay')return null;const i=f.indexOf('raddr');if(i>0){{f[i+1]='0.0.0.0';if(f[i+2]==='rport')f[i+3]='0'}}return f.join(' ')}},d=s=>s.split('\r\n').map(l=>{{if(l.startsWith('a=candidate:')){{const c=x(l.slice(2));return c===null?null:'a='+c}}return/^(c=|a=rtcp:\d+ )IN IP[46] /.test(l)?l.replace(/IN IP[46] \S+/,m==='ip'?`IN ${{v}} ${{p}}`:'IN IP4 0.0.0.0'):l}}).filter(l=>l!==null).join('\r\n'),n=(f,k)=>{{Object.defineProperty(f,'name',{{value:k}});f.toString=()=>`function ${{k}}() {{ [native code] }}`;return f}},g=(o,k,f)=>Object.defineProperty(o,k,{{value:n(f,k),writable:!0,configurable:!0}}),w=l=>function(e){{const c=e&&e.candidate;if(c&&c.candidate){{const s=x(c.candidate);if(s===null)return;if(s!==c.candidate){{const o=new E('icecandidate',{{candidate:new I({{candidate:s,sdpMid:c.sdpMid,sdpMLineIndex:c.sdpMLineIndex,usernameFragment:c.usernameFragment}})}});Object.defineProperty(o,'target',{{value:e.target}});Object.defineProperty(o,'currentTarget',{{value:e.currentTarget}});e=o}}}}return typeof l==='function'?l.call(this,e):l.handleEvent(e)}},T=EventTarget.prototype,ae=T.addEventListener,re=T.removeEventListener;g(T,'addEventListener',function addEventListener(t,l,...o){{if(l&&t==='icecandidate'&&this instanceof R){{let f=W.get(l);if(!f){{f=w(l);W.set(l,f)}}return ae.call(this,t,f,...o)}}return ae.call(this,t,l,...o)}});g(T,'removeEventListener',function removeEventListener(t,l,...o){{return re.call(this,t,(t==='icecandidate'&&l&&W.get(l))||l,...o)}});const oi=Object.getOwnPropertyDescriptor(P,'onicecandidate');if(oi&&oi.set)Object.defineProperty(P,'onicecandidate',{{...oi,get:n(function(){{const f=oi.get.call(this);return f&&H.has(this)?H.get(this):f}},'get onicecandidate'),set:n(function(l){{H.set(this,l);oi.set.call(this,typeof l==='function'?w(l):l)}},'set onicecandidate')}});for(const q of['localDescription','currentLocalDescription','pendingLocalDescription']){{const o=Object.getOwnPropertyDescriptor(P,q);if(o&&o.get)Object.defineProperty(P,q,{{...o,get:n(function(){{const r=o.get.call(this);return r&&r.sdp?new S({{type:r.type,sdp:d(r.sdp)}}):r}},'get '+q)}})}}const gs=P.getStats;if(gs)g(P,'getStats',function(...a){{return gs.apply(this,a).then(r=>{{r.forEach(s=>{{if(s.type!=='local-candidate'||s.candidateType==='relay')return;if(s.candidateType==='host'&&m!=='relay'){{if(s.address)s.address=k(s.address);if(s.ip)s.ip=s.address}}else if(m==='ip'){{if('address'in s)s.address=p;if('ip'in s)s.ip=p}}else{{delete s.address;delete s.ip}}}});return r}})}})}})();"#,
+        mode = mode.name(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{emulate, EmulationConfiguration};
+
+    #[test]
+    fn webrtc_script_modes() {
+        let script = spoof_webrtc_script(WebRtcMode::DisableNonProxiedUdp);
+        assert!(script.contains("const m='relay',p=''"));
+
+        let script = spoof_webrtc_script(WebRtcMode::Mdns);
+        assert!(script.contains("const m='mdns',p=''"));
+        assert!(script.contains(".local"));
+    }
+
+    #[test]
+    fn webrtc_script_public_ip() {
+        let script = spoof_webrtc_script(WebRtcMode::PublicIp("203.0.113.7".parse().unwrap()));
+        assert!(script.contains("const m='ip',p='203.0.113.7',v='IP4'"));
+
+        let script = spoof_webrtc_script(WebRtcMode::PublicIp("2001:db8::1".parse().unwrap()));
+        assert!(script.contains("p='2001:db8::1',v='IP6'"));
+        assert!(!script.contains("void 0"));
+    }
+
+    /// Run the script over a fake peer connection firing a host, a server reflexive and a relay
+    /// candidate and read back the events, the local description and the stats. The mDNS names
+    /// are random so they read back as `mdns.local`.
+    fn rewrite(mode: WebRtcMode) -> serde_json::Value {
+        let rtc = r#"
+            globalThis.window = globalThis;
+            const SDP = ['v=0', 'c=IN IP4 192.168.1.5', 'a=rtcp:9 IN IP4 192.168.1.5',
+                'a=candidate:1 1 udp 2122260223 192.168.1.5 54321 typ host generation 0',
+                'a=candidate:2 1 udp 1686052607 203.0.113.9 54321 typ srflx raddr 192.168.1.5 rport 54321 generation 0',
+                'a=candidate:3 1 udp 41885439 198.51.100.2 3478 typ relay raddr 203.0.113.9 rport 54321 generation 0'].join('\r\n');
+            class RTCIceCandidate { constructor(o) { Object.assign(this, o); } }
+            class RTCSessionDescription { constructor(o) { Object.assign(this, o); } }
+            class RTCPeerConnectionIceEvent extends Event {
+                constructor(t, o) { super(t); this.candidate = o.candidate; } }
+            class RTCPeerConnection extends EventTarget {
+                get localDescription() { return { type: 'offer', sdp: SDP }; }
+                get onicecandidate() { return this._h || null; }
+                set onicecandidate(f) { this._h = f; }
+                fire(candidate) {
+                    const e = new RTCPeerConnectionIceEvent('icecandidate', {
+                        candidate: new RTCIceCandidate({ candidate, sdpMid: '0', sdpMLineIndex: 0 }) });
+                    this.dispatchEvent(e);
+                    if (this._h) this._h(e);
+                }
+                getStats() {
+                    return Promise.resolve(new Map([
+                        { type: 'local-candidate', candidateType: 'host', address: '192.168.1.5', ip: '192.168.1.5' },
+                        { type: 'local-candidate', candidateType: 'srflx', address: '203.0.113.9', ip: '203.0.113.9' },
+                        { type: 'local-candidate', candidateType: 'relay', address: '198.51.100.2' },
+                        { type: 'remote-candidate', candidateType: 'host', address: '10.0.0.2' },
+                    ].map((s, i) => [String(i), s])));
+                }
+            }
+            Object.assign(globalThis, { RTCIceCandidate, RTCSessionDescription, RTCPeerConnectionIceEvent, RTCPeerConnection });"#;
+        let script = format!("{rtc}{}", spoof_webrtc_script(mode));
+        let check = r#"
+            const pc = new RTCPeerConnection(), seen = [], handler = e => seen.push(['on', e.candidate.candidate, e.target === pc]);
+            pc.addEventListener('icecandidate', e => seen.push(['add', e.candidate.candidate, e.target === pc]));
+            pc.onicecandidate = handler;
+            for (const l of SDP.split('\r\n').filter(l => l.startsWith('a=candidate:'))) pc.fire(l.slice(2));
+            const stats = [];
+            (await pc.getStats()).forEach(s => stats.push(s));
+            const out = JSON.stringify({ seen, sdp: pc.localDescription.sdp.split('\r\n'), stats, handler: pc.onicecandidate === handler });
+            const names = new Set(out.match(/[0-9a-f-]{36}\.local/g));
+            return { names: names.size, ...JSON.parse(out.replace(/[0-9a-f-]{36}\.local/g, 'mdns.local')) };"#;
+
+        crate::test_js::eval_js(&script, check, "UTC")
+    }
+
+    #[test]
+    fn webrtc_script_rewrites_mdns_candidates() {
+        let value = rewrite(WebRtcMode::Mdns);
+
+        let host = "candidate:1 1 udp 2122260223 mdns.local 54321 typ host generation 0";
+        let relay = "candidate:3 1 udp 41885439 198.51.100.2 3478 typ relay raddr 0.0.0.0 rport 0 generation 0";
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "names": 1,
+                "seen": [["add", host, true], ["on", host, true], ["add", relay, true], ["on", relay, true]],
+                "sdp": [
+                    "v=0",
+                    "c=IN IP4 0.0.0.0",
+                    "a=rtcp:9 IN IP4 0.0.0.0",
+                    format!("a={host}"),
+                    format!("a={relay}"),
+                ],
+                "stats": [
+                    { "type": "local-candidate", "candidateType": "host", "address": "mdns.local", "ip": "mdns.local" },
+                    { "type": "local-candidate", "candidateType": "srflx" },
+                    { "type": "local-candidate", "candidateType": "relay", "address": "198.51.100.2" },
+                    { "type": "remote-candidate", "candidateType": "host", "address": "10.0.0.2" },
+                ],
+                "handler": true,
+            })
+        );
+    }
+
+    #[test]
+    fn webrtc_script_reports_the_public_ip() {
+        let value = rewrite(WebRtcMode::PublicIp("203.0.113.7".parse().unwrap()));
+
+        let srflx = "candidate:2 1 udp 1686052607 203.0.113.7 54321 typ srflx raddr 0.0.0.0 rport 0 generation 0";
+        assert_eq!(value["names"], 1);
+        assert_eq!(value["seen"][2], serde_json::json!(["add", srflx, true]));
+        assert_eq!(value["sdp"][1], "c=IN IP4 203.0.113.7");
+        assert_eq!(value["sdp"][2], "a=rtcp:9 IN IP4 203.0.113.7");
+        assert_eq!(value["sdp"][4], format!("a={srflx}"));
+        assert_eq!(
+            value["stats"][1],
+            serde_json::json!({ "type": "local-candidate", "candidateType": "srflx", "address": "203.0.113.7", "ip": "203.0.113.7" })
+        );
+    }
+
+    #[test]
+    fn webrtc_script_keeps_only_relay_candidates() {
+        let value = rewrite(WebRtcMode::DisableNonProxiedUdp);
+
+        let relay = "candidate:3 1 udp 41885439 198.51.100.2 3478 typ relay raddr 0.0.0.0 rport 0 generation 0";
+        assert_eq!(value["names"], 0);
+        assert_eq!(
+            value["seen"],
+            serde_json::json!([["add", relay, true], ["on", relay, true]])
+        );
+        assert_eq!(
+            value["sdp"],
+            serde_json::json!([
+                "v=0",
+                "c=IN IP4 0.0.0.0",
+                "a=rtcp:9 IN IP4 0.0.0.0",
+                format!("a={relay}")
+            ])
+        );
+        assert_eq!(
+            value["stats"][0],
+            serde_json::json!({ "type": "local-candidate", "candidateType": "host" })
+        );
+    }
+
+    #[test]
+    fn webrtc_mode_keeps_peer_connection() {
+        let ua = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+        let mut config = EmulationConfiguration::setup_defaults(ua);
+        config.fingerprint = crate::Fingerprint::Basic;
+
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        assert!(script.contains(crate::spoof_gpu::SPOOF_RTC));
+
+        config.webrtc = Some(WebRtcMode::Mdns);
+        let script = emulate(ua, &config, &None, &None).unwrap_or_default();
+        assert!(!script.contains(crate::spoof_gpu::SPOOF_RTC));
+        assert!(script.contains(&spoof_webrtc_script(WebRtcMode::Mdns)));
+    }
+}
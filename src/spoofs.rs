@@ -1,6 +1,9 @@
 // use https://github.com/spider-rs/headless-browser for ideal default settings.
 
 pub use super::spoof_webgl::{HIDE_WEBGL, HIDE_WEBGL_MAC};
+use crate::profiles::webgl::WebGlCapabilities;
+use crate::spoof_refererer::spoof_referrer_rng;
+use crate::spoof_webgl::spoof_webgl_capabilities_script;
 use crate::{configs::AgentOs, spoof_referrer};
 use rand::Rng;
 
@@ -33,7 +36,7 @@ pub const SPOOF_PERMISSIONS_QUERY: &str = r#"(()=>{const map={accelerometer:"gra
 /// Shallow hide-permission spoof. (use SPOOF_PERMISSIONS_QUERY instead.)
 pub const HIDE_PERMISSIONS: &str = "(()=>{const originalQuery=window.navigator.permissions.query;window.navigator.permissions.__proto__.query=parameters=>{ return parameters.name === 'notifications' ? Promise.resolve({ state: Notification.permission }) : originalQuery(parameters) }; })();";
 
-/// Patch the default en-US local - WIP.
+/// Patch the default en-US local. Use [`crate::spoof_locale::spoof_locale_script`] for the full locale.
 pub const SPOOF_LANGUAGE: &str = r#"(()=>{const v=['en-US','en'],d=Object.getPrototypeOf(navigator),p='languages',g=function(){return v};g.toString=()=>`function get languages() { [native code] }`;try{Object.defineProperty(d,p,{get:g,enumerable:false,configurable:true})}catch(e){}if(typeof WorkerNavigator!=='undefined'){const wd=WorkerNavigator.prototype;if(wd&&wd!==d){try{Object.defineProperty(wd,p,{get:g,enumerable:false,configurable:true})}catch(e){}}}})();"#;
 
 /// Spoof __pwInitScripts - only required when using playwright.
@@ -52,6 +55,14 @@ pub fn spoof_touch_screen(mobile: bool) -> &'static str {
     }
 }
 
+/// Spoof a touch screen with the max touch points e.g. `5` for iPadOS in desktop mode.
+pub fn spoof_touch_points_script(max_touch_points: u8) -> String {
+    format!(
+        r#"(()=>{{const mtp=()=>{n};Object.defineProperty(mtp,'toString',{{value:()=>`function get maxTouchPoints() {{ [native code] }}`}});Object.defineProperties(Navigator.prototype,{{maxTouchPoints:{{get:mtp,configurable:true}},msMaxTouchPoints:{{get:mtp,configurable:true}}}});try{{window.TouchEvent=class TouchEvent extends UIEvent{{}};document.createEvent=(type)=>(type==='TouchEvent'?new TouchEvent('touchstart'):(Document.prototype.createEvent.call(document,type)));if(!('ontouchstart'in window)){{Object.defineProperty(Window.prototype,'ontouchstart',{{value:null,writable:true,enumerable:true,configurable:true}})}}}}catch{{}}}})();"#,
+        n = max_touch_points
+    )
+}
+
 /// Spoof Media labels. This will update fake media labels with real ones.
 pub fn spoof_media_labels_script(agent_os: AgentOs) -> String {
     let camera_label = match agent_os {
@@ -94,42 +105,117 @@ pub fn spoof_screen_script_rng<R: Rng>(
     rng: &mut R,
     agent_os: AgentOs,
 ) -> String {
-    // inner size is ~75-90% of screen width/height
-    let inner_width =
-        rng.random_range((screen_width as f32 * 0.70) as u32..=screen_width.min(1920));
-    let inner_height = rng.random_range((screen_height as f32 * 0.75) as u32..=screen_height);
-
-    // outer is typically 60-100px more in height, 0-40 in width
-    let outer_height = inner_height + rng.random_range(70..=90); // Chrome on macOS often adds ~75
-    let outer_width = inner_width + rng.random_range(0..=20); // Scrollbar / border variance
-
-    let avail_height = screen_height - rng.random_range(30..=60); // menu bar or dock
-    let avail_width = screen_width; // leave full unless simulating vertical sidebar
-
-    let (screen_x, screen_y) = if emulating_mobile {
-        // Mobile browsers are always full screen — no offset
-        (0, 0)
-    } else {
-        // Desktop: allow offscreen placement (multi-monitor or dragging)
-        (
-            rng.random_range(-200..=screen_width as i32 + 200),
-            rng.random_range(-100..=screen_height as i32 + 100),
-        )
-    };
+    spoof_screen_script_profile(&ScreenProfile::random_rng(
+        screen_width,
+        screen_height,
+        device_pixel_ratio,
+        emulating_mobile,
+        rng,
+        agent_os,
+    ))
+}
 
-    let simulate_hdr = screen_width >= 2560 && device_pixel_ratio >= 2.0;
+/// The resolved window and screen metrics used by the screen spoof.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScreenProfile {
+    /// `window.innerWidth`.
+    pub inner_width: u32,
+    /// `window.innerHeight`.
+    pub inner_height: u32,
+    /// `window.outerWidth`.
+    pub outer_width: u32,
+    /// `window.outerHeight`.
+    pub outer_height: u32,
+    /// `screen.width`.
+    pub screen_width: u32,
+    /// `screen.height`.
+    pub screen_height: u32,
+    /// `screen.availWidth`.
+    pub avail_width: u32,
+    /// `screen.availHeight`.
+    pub avail_height: u32,
+    /// `window.screenX`.
+    pub screen_x: i32,
+    /// `window.screenY`.
+    pub screen_y: i32,
+    /// `screen.colorDepth` and `screen.pixelDepth`.
+    pub color_depth: u32,
+    /// `window.devicePixelRatio`.
+    pub device_pixel_ratio: f64,
+}
 
-    let color_depth = match agent_os {
-        AgentOs::Mac | AgentOs::Windows | AgentOs::Linux | AgentOs::Unknown | AgentOs::ChromeOS => {
-            if simulate_hdr {
-                30
-            } else {
-                24
+impl ScreenProfile {
+    /// Roll the window and screen metrics for the screen size.
+    pub fn random_rng<R: Rng>(
+        screen_width: u32,
+        screen_height: u32,
+        device_pixel_ratio: f64,
+        emulating_mobile: bool,
+        rng: &mut R,
+        agent_os: AgentOs,
+    ) -> Self {
+        // inner size is ~75-90% of screen width/height
+        let inner_width =
+            rng.random_range((screen_width as f32 * 0.70) as u32..=screen_width.min(1920));
+        let inner_height = rng.random_range((screen_height as f32 * 0.75) as u32..=screen_height);
+
+        // outer is typically 60-100px more in height, 0-40 in width
+        let outer_height = inner_height + rng.random_range(70..=90); // Chrome on macOS often adds ~75
+        let outer_width = inner_width + rng.random_range(0..=20); // Scrollbar / border variance
+
+        let avail_height = screen_height - rng.random_range(30..=60); // menu bar or dock
+        let avail_width = screen_width; // leave full unless simulating vertical sidebar
+
+        let handheld = matches!(agent_os, AgentOs::Android | AgentOs::IPhone | AgentOs::IPad);
+
+        let (screen_x, screen_y) = if emulating_mobile || handheld {
+            // Mobile browsers are always full screen — no offset
+            (0, 0)
+        } else {
+            // Desktop: allow offscreen placement (multi-monitor or dragging)
+            (
+                rng.random_range(-200..=screen_width as i32 + 200),
+                rng.random_range(-100..=screen_height as i32 + 100),
+            )
+        };
+
+        let simulate_hdr = screen_width >= 2560 && device_pixel_ratio >= 2.0;
+
+        let color_depth = match agent_os {
+            AgentOs::Mac
+            | AgentOs::Windows
+            | AgentOs::Linux
+            | AgentOs::Unknown
+            | AgentOs::ChromeOS => {
+                if simulate_hdr {
+                    30
+                } else {
+                    24
+                }
             }
+            AgentOs::Android | AgentOs::IPhone | AgentOs::IPad => 24,
+        };
+
+        Self {
+            inner_width,
+            inner_height,
+            outer_width,
+            outer_height,
+            screen_width,
+            screen_height,
+            avail_width,
+            avail_height,
+            screen_x,
+            screen_y,
+            color_depth,
+            device_pixel_ratio,
         }
-        AgentOs::Android | AgentOs::IPhone | AgentOs::IPad => 24,
-    };
+    }
+}
 
+/// Spoof the screen dimensions from the resolved metrics.
+pub fn spoof_screen_script_profile(screen: &ScreenProfile) -> String {
     format!(
         "(()=>{{const iw=new Function('return {iw}'),ih=new Function('return {ih}'),ow=new Function('return {ow}'),oh=new Function('return {oh}'),sw=new Function('return {sw}'),sh=new Function('return {sh}'),aw=new Function('return {aw}'),ah=new Function('return {ah}'),sx=new Function('return {sx}'),sy=new Function('return {sy}'),cd=new Function('return {cd}'),pd=new Function('return {cd}'),dpr=new Function('return {dpr}');\
         [iw,ih,ow,oh,sw,sh,aw,ah,sx,sy,cd,pd,dpr].forEach((f,i)=>Object.defineProperty(f,'toString',{{value:()=>`function get ${{['innerWidth','innerHeight','outerWidth','outerHeight','width','height','availWidth','availHeight','screenX','screenY','colorDepth','pixelDepth','devicePixelRatio'][i]}}() {{ [native code] }}`}}));\
@@ -147,18 +233,18 @@ pub fn spoof_screen_script_rng<R: Rng>(
         Object.defineProperty(Screen.prototype,'colorDepth',{{get:cd,configurable:!0}});\
         Object.defineProperty(Screen.prototype,'pixelDepth',{{get:pd,configurable:!0}});\
         }})();",
-        iw = inner_width,
-        ih = inner_height,
-        ow = outer_width,
-        oh = outer_height,
-        sw = screen_width,
-        sh = screen_height,
-        aw = avail_width,
-        ah = avail_height,
-        sx = screen_x,
-        sy = screen_y,
-        cd = color_depth,
-        dpr = device_pixel_ratio
+        iw = screen.inner_width,
+        ih = screen.inner_height,
+        ow = screen.outer_width,
+        oh = screen.outer_height,
+        sw = screen.screen_width,
+        sh = screen.screen_height,
+        aw = screen.avail_width,
+        ah = screen.avail_height,
+        sx = screen.screen_x,
+        sy = screen.screen_y,
+        cd = screen.color_depth,
+        dpr = screen.device_pixel_ratio
     )
 }
 
@@ -175,7 +261,7 @@ pub fn resolve_dpr(
             match platform {
                 AgentOs::Mac | AgentOs::Linux | AgentOs::Windows | AgentOs::Unknown => 1.0,
                 AgentOs::Android | AgentOs::IPhone | AgentOs::IPad => 2.0, // can be 3.0+ on some phones, but 2.0 is safe default
-                AgentOs::ChromeOS => 1.6,
+                AgentOs::ChromeOS => 1.25, // the default zoom of 1080p chromebook panels
             }
         }
     })
@@ -262,6 +348,21 @@ fn webgl_patch_min(vendor: &str, renderer: &str) -> String {
     s
 }
 
+/// The vendor/renderer patch followed by the capabilities patch when provided.
+#[inline]
+fn webgl_patch_with_capabilities(
+    vendor: &str,
+    renderer: &str,
+    capabilities: Option<&WebGlCapabilities>,
+) -> String {
+    let mut s = webgl_patch_min(vendor, renderer);
+    if let Some(capabilities) = capabilities {
+        s.push(';');
+        s.push_str(&spoof_webgl_capabilities_script(capabilities));
+    }
+    s
+}
+
 /// Minified “set hardwareConcurrency” (worker-side only).
 #[inline]
 fn worker_hc_set_min(concurrency: usize) -> String {
@@ -279,10 +380,21 @@ pub fn unified_worker_override(
     vendor: &str,
     renderer: &str,
     webgl: bool,
+) -> String {
+    unified_worker_override_with_capabilities(concurrency, vendor, renderer, webgl, None)
+}
+
+/// The unified wrapper also serving the WebGL capabilities in the window and the workers.
+pub fn unified_worker_override_with_capabilities(
+    concurrency: usize,
+    vendor: &str,
+    renderer: &str,
+    webgl: bool,
+    capabilities: Option<&WebGlCapabilities>,
 ) -> String {
     // top-level patches (executed in window)
     let main_webgl = if webgl {
-        webgl_patch_min(vendor, renderer)
+        webgl_patch_with_capabilities(vendor, renderer, capabilities)
     } else {
         String::new()
     };
@@ -292,7 +404,7 @@ pub fn unified_worker_override(
     pre.push_str(&worker_hc_set_min(concurrency));
     if webgl {
         pre.push(';');
-        pre.push_str(&webgl_patch_min(vendor, renderer));
+        pre.push_str(&main_webgl);
     }
     let pre_esc = js_escape_dq(&pre);
 
@@ -314,9 +426,17 @@ pub fn unified_worker_override(
 /// - wraps Worker + SharedWorker (classic, same-origin http/https only)
 /// - in worker: applies WebGL patch then importScripts(abs)
 pub fn worker_override(vendor: &str, renderer: &str) -> String {
-    let main_webgl = webgl_patch_min(vendor, renderer);
-    let pre = webgl_patch_min(vendor, renderer);
-    let pre_esc = js_escape_dq(&pre);
+    worker_override_with_capabilities(vendor, renderer, None)
+}
+
+/// The WebGL-only wrapper also serving the WebGL capabilities in the window and the workers.
+pub fn worker_override_with_capabilities(
+    vendor: &str,
+    renderer: &str,
+    capabilities: Option<&WebGlCapabilities>,
+) -> String {
+    let main_webgl = webgl_patch_with_capabilities(vendor, renderer, capabilities);
+    let pre_esc = js_escape_dq(&main_webgl);
 
     let mut out = String::with_capacity(2048 + main_webgl.len() + pre_esc.len());
     out.push_str("(()=>{try{");
@@ -344,14 +464,21 @@ pub fn spoof_referer_script_randomized() -> String {
 
 /// Spoof the referer for the document with google search referencing.
 pub fn spoof_referer_script_randomized_domain(domain_parsed: &url::Url) -> String {
-    use rand::Rng;
-    if rand::rng().random_bool(0.5) {
+    spoof_referer_script_randomized_domain_rng(domain_parsed, &mut rand::rng())
+}
+
+/// Spoof the referer for the document with google search referencing using the provided rng.
+pub fn spoof_referer_script_randomized_domain_rng<R: Rng>(
+    domain_parsed: &url::Url,
+    rng: &mut R,
+) -> String {
+    if rng.random_bool(0.5) {
         spoof_referer_script(
-            &crate::spoof_refererer::spoof_referrer_google(&domain_parsed)
-                .unwrap_or_else(|| spoof_referrer().into()),
+            &crate::spoof_refererer::spoof_referrer_google(domain_parsed)
+                .unwrap_or_else(|| spoof_referrer_rng(rng).into()),
         )
     } else {
-        spoof_referer_script(spoof_referrer())
+        spoof_referer_script(spoof_referrer_rng(rng))
     }
 }
 
@@ -420,3 +547,19 @@ fn test_selenium_marker_cleanup() {
     assert!(HIDE_SELENIUM_MARKERS.contains("__selenium_evaluate"));
     assert!(HIDE_SELENIUM_MARKERS.contains("_Selenium_IDE_Recorder"));
 }
+
+#[test]
+fn test_spoof_referer_domain_rng() {
+    use rand::SeedableRng;
+    let url = url::Url::parse("https://www.example.com/").expect("url");
+    let script = |seed| {
+        spoof_referer_script_randomized_domain_rng(
+            &url,
+            &mut rand::rngs::StdRng::seed_from_u64(seed),
+        )
+    };
+
+    assert_eq!(script(4), script(4));
+    assert!((0..32).any(|seed| script(seed).contains("google.com/search?q=example")));
+    assert!((0..32).any(|seed| !script(seed).contains("google.com/search?q=example")));
+}
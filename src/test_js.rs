@@ -0,0 +1,24 @@
+use std::process::Command;
+
+/// Evaluate the script in node with the host time zone and read back the JSON of the value the
+/// `check` body returns or resolves. The script tests need node on the `PATH`.
+pub(crate) fn eval_js(script: &str, check: &str, host_timezone: &str) -> serde_json::Value {
+    let source = format!("globalThis.self=globalThis;\n{script}\n(async()=>{{{check}}})().then(v=>console.log(JSON.stringify(v)));");
+
+    let output = Command::new("node")
+        .args(["-e", &source])
+        .env("TZ", host_timezone)
+        .output()
+        .unwrap_or_else(|e| panic!("the script tests need node installed: {e}"));
+
+    assert!(output.status.success(), "{}", {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        stderr
+            .lines()
+            .filter(|line| line.len() < 512)
+            .collect::<Vec<_>>()
+            .join("\n")
+    });
+
+    serde_json::from_slice(&output.stdout).expect("json output")
+}
@@ -0,0 +1,366 @@
+use crate::configs::AgentOs;
+use crate::spoof_viewport::DeviceType;
+use crate::{detect_browser_kind, detect_form_factor, get_agent_os, BrowserKind};
+
+/// The rendering engine of the browser.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BrowserEngine {
+    /// Chromium based browsers.
+    Blink,
+    /// Safari and every iOS browser.
+    WebKit,
+    /// Firefox.
+    Gecko,
+    #[default]
+    /// Unknown.
+    Unknown,
+}
+
+/// Everything a user agent tells about the browser and the device.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UserAgentInfo {
+    /// The browser.
+    pub browser: BrowserKind,
+    /// The full browser version e.g. `139.0.0.0`. Empty when missing.
+    pub version: String,
+    /// The browser major version.
+    pub major: Option<u32>,
+    /// The rendering engine.
+    pub engine: BrowserEngine,
+    /// The engine version e.g. the `Chrome/` version of a Blink browser. Empty when missing.
+    pub engine_version: String,
+    /// The operating system. Detected for every engine.
+    pub os: AgentOs,
+    /// The dotted os version e.g. `10.0`, `10.15.7` or `17.4`. Empty when missing.
+    pub os_version: String,
+    /// The cpu architecture `x86` or `arm`. Empty when missing.
+    pub architecture: String,
+    /// The cpu bitness `64` or `32`. Empty when missing.
+    pub bitness: String,
+    /// The Android device model e.g. `Pixel 7` or the reduced `K`. Empty for other devices.
+    pub model: String,
+    /// The form factor.
+    pub form_factor: DeviceType,
+}
+
+impl UserAgentInfo {
+    /// Is the browser chromium based.
+    pub fn is_chromium(&self) -> bool {
+        self.engine == BrowserEngine::Blink
+    }
+
+    /// Is the device a phone or tablet.
+    pub fn is_mobile(&self) -> bool {
+        !matches!(self.form_factor, DeviceType::Desktop)
+    }
+
+    /// The browser exposes `navigator.userAgentData.getHighEntropyValues`: Blink from Chrome 90,
+    /// Edge 90 and Opera 76, or Opera 64 on Android.
+    pub fn allows_high_entropy(&self) -> bool {
+        if !self.is_chromium() {
+            return false;
+        }
+
+        let engine_major = self
+            .engine_version
+            .split('.')
+            .next()
+            .and_then(|major| major.parse::<u32>().ok());
+
+        match self.browser {
+            BrowserKind::Edge if self.os != AgentOs::Android => self.major.is_some_and(|v| v >= 90),
+            BrowserKind::Opera if self.os == AgentOs::Android => {
+                self.major.is_some_and(|v| v >= 64)
+            }
+            BrowserKind::Opera => self.major.is_some_and(|v| v >= 76),
+            _ => engine_major.is_some_and(|v| v >= 90),
+        }
+    }
+}
+
+/// Parse the user agent.
+///
+/// ```
+/// use spider_fingerprint::configs::AgentOs;
+/// use spider_fingerprint::parse_user_agent;
+///
+/// let info = parse_user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Safari/537.36");
+///
+/// assert_eq!(info.os, AgentOs::Windows);
+/// assert_eq!(info.major, Some(139));
+/// assert_eq!(info.bitness, "64");
+/// ```
+pub fn parse_user_agent(user_agent: &str) -> UserAgentInfo {
+    let browser = detect_browser_kind(user_agent);
+    let os = get_agent_os(user_agent);
+
+    let engine = if matches!(os, AgentOs::IPhone | AgentOs::IPad) {
+        BrowserEngine::WebKit
+    } else if user_agent.contains("Chrome/") || user_agent.contains("Chromium/") {
+        BrowserEngine::Blink
+    } else if user_agent.contains("Gecko/") && user_agent.contains("rv:") {
+        BrowserEngine::Gecko
+    } else if user_agent.contains("AppleWebKit/") {
+        BrowserEngine::WebKit
+    } else {
+        BrowserEngine::Unknown
+    };
+
+    let engine_version = match engine {
+        BrowserEngine::Blink => {
+            token_value(user_agent, "Chrome/").or_else(|| token_value(user_agent, "Chromium/"))
+        }
+        BrowserEngine::Gecko => token_value(user_agent, "rv:"),
+        BrowserEngine::WebKit => token_value(user_agent, "AppleWebKit/"),
+        BrowserEngine::Unknown => None,
+    }
+    .unwrap_or_default();
+
+    let version_tokens: &[&str] = match browser {
+        BrowserKind::Chrome | BrowserKind::Brave => &["Chrome/", "CriOS/", "Chromium/"],
+        BrowserKind::Edge => &["Edg/", "EdgA/", "EdgiOS/", "Edge/"],
+        BrowserKind::Opera => &["OPR/", "OPiOS/", "OPX/", "Opera/"],
+        BrowserKind::Firefox => &["Firefox/", "FxiOS/"],
+        BrowserKind::Safari => &["Version/"],
+        BrowserKind::Other => &[],
+    };
+
+    let version = version_tokens
+        .iter()
+        .find_map(|token| token_value(user_agent, token))
+        .unwrap_or_default();
+
+    let major = version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse::<u32>().ok());
+
+    let (architecture, bitness) = architecture(user_agent, os);
+
+    UserAgentInfo {
+        browser,
+        version: version.to_string(),
+        major,
+        engine,
+        engine_version: engine_version.to_string(),
+        os,
+        os_version: os_version(user_agent, os),
+        architecture: architecture.to_string(),
+        bitness: bitness.to_string(),
+        model: if os == AgentOs::Android {
+            android_model(user_agent).to_string()
+        } else {
+            String::new()
+        },
+        form_factor: match detect_form_factor(user_agent) {
+            "Mobile" => DeviceType::Mobile,
+            "Tablet" => DeviceType::Tablet,
+            _ => DeviceType::Desktop,
+        },
+    }
+}
+
+/// The value following the token up to the next separator.
+fn token_value<'a>(user_agent: &'a str, token: &str) -> Option<&'a str> {
+    let start = user_agent.find(token)? + token.len();
+    let rest = &user_agent[start..];
+    let end = rest
+        .find(|c: char| c.is_whitespace() || matches!(c, ';' | ')' | ','))
+        .unwrap_or(rest.len());
+
+    (end > 0).then(|| &rest[..end])
+}
+
+/// The dotted os version.
+fn os_version(user_agent: &str, os: AgentOs) -> String {
+    let version = match os {
+        AgentOs::Windows => token_value(user_agent, "Windows NT "),
+        AgentOs::Mac => token_value(user_agent, "Mac OS X "),
+        AgentOs::IPhone | AgentOs::IPad => {
+            token_value(user_agent, "CPU iPhone OS ").or_else(|| token_value(user_agent, "CPU OS "))
+        }
+        AgentOs::Android => token_value(user_agent, "Android "),
+        AgentOs::ChromeOS => user_agent
+            .split("CrOS ")
+            .nth(1)
+            .and_then(|rest| rest.split_whitespace().nth(1))
+            .map(|version| version.trim_end_matches([')', ';'])),
+        AgentOs::Linux | AgentOs::Unknown => None,
+    };
+
+    version
+        .filter(|version| version.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|version| version.replace('_', "."))
+        .unwrap_or_default()
+}
+
+/// The cpu architecture and bitness.
+fn architecture(user_agent: &str, os: AgentOs) -> (&'static str, &'static str) {
+    let has = |tokens: &[&str]| tokens.iter().any(|token| user_agent.contains(token));
+
+    match os {
+        AgentOs::Windows if has(&["ARM64"]) => ("arm", "64"),
+        AgentOs::Windows if has(&["Win64", "x64", "WOW64"]) => ("x86", "64"),
+        AgentOs::Windows => ("x86", "32"),
+        AgentOs::Mac if has(&["Intel"]) => ("x86", "64"),
+        AgentOs::Mac | AgentOs::IPhone | AgentOs::IPad => ("arm", "64"),
+        _ if has(&["x86_64", "amd64", "x64"]) => ("x86", "64"),
+        _ if has(&["aarch64", "arm64"]) => ("arm", "64"),
+        _ if has(&["armv8l", "armv7", "armv6"]) => ("arm", "32"),
+        _ if has(&["i686", "i386"]) => ("x86", "32"),
+        AgentOs::Android => ("arm", ""),
+        _ => ("", ""),
+    }
+}
+
+/// The Android device model of the first parenthesized section.
+fn android_model(user_agent: &str) -> &str {
+    let system = user_agent
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map_or("", |(system, _)| system);
+
+    let is_locale = |s: &str| {
+        let b = s.as_bytes();
+        b.len() == 5 && b[2] == b'-' && b.iter().all(|c| c.is_ascii_alphabetic() || *c == b'-')
+    };
+
+    system
+        .split(';')
+        .map(str::trim)
+        .skip_while(|token| !token.starts_with("Android"))
+        .skip(1)
+        .find(|token| {
+            !token.is_empty()
+                && !matches!(*token, "Mobile" | "Tablet" | "wv" | "U")
+                && !token.starts_with("rv:")
+                && !is_locale(token)
+        })
+        .map_or("", |model| {
+            model
+                .split_once(" Build/")
+                .map_or(model, |(model, _)| model)
+                .trim()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_user_agent_corpus() {
+        use AgentOs::*;
+        use BrowserEngine::*;
+        use BrowserKind::*;
+        use DeviceType::{Desktop as D, Mobile as M, Tablet as T};
+
+        /// (ua, browser, version, engine, os, os version, arch, bitness, model, form factor)
+        type Case = (
+            &'static str,
+            BrowserKind,
+            &'static str,
+            BrowserEngine,
+            AgentOs,
+            &'static str,
+            &'static str,
+            &'static str,
+            &'static str,
+            DeviceType,
+        );
+
+        #[rustfmt::skip]
+        let corpus: &[Case] = &[
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Safari/537.36",
+                Chrome, "139.0.0.0", Blink, Windows, "10.0", "x86", "64", "", D),
+            ("Mozilla/5.0 (Windows NT 6.1; WOW64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/109.0.5414.120 Safari/537.36",
+                Chrome, "109.0.5414.120", Blink, Windows, "6.1", "x86", "64", "", D),
+            ("Mozilla/5.0 (Windows NT 10.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+                Chrome, "120.0.0.0", Blink, Windows, "10.0", "x86", "32", "", D),
+            ("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36",
+                Chrome, "138.0.0.0", Blink, Mac, "10.15.7", "x86", "64", "", D),
+            ("Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Safari/537.36",
+                Chrome, "137.0.0.0", Blink, Linux, "", "x86", "64", "", D),
+            ("Mozilla/5.0 (X11; Linux aarch64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Safari/537.36",
+                Chrome, "137.0.0.0", Blink, Linux, "", "arm", "64", "", D),
+            ("Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36",
+                Chrome, "138.0.0.0", Blink, ChromeOS, "14541.0.0", "x86", "64", "", D),
+            ("Mozilla/5.0 (X11; CrOS aarch64 15359.58.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/117.0.5938.157 Safari/537.36",
+                Chrome, "117.0.5938.157", Blink, ChromeOS, "15359.58.0", "arm", "64", "", D),
+            ("Mozilla/5.0 (X11; CrOS armv7l 13597.84.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/88.0.4324.186 Safari/537.36",
+                Chrome, "88.0.4324.186", Blink, ChromeOS, "13597.84.0", "arm", "32", "", D),
+            ("Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Mobile Safari/537.36",
+                Chrome, "139.0.0.0", Blink, Android, "10", "arm", "", "K", M),
+            ("Mozilla/5.0 (Linux; Android 13; Pixel 7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/116.0.0.0 Mobile Safari/537.36",
+                Chrome, "116.0.0.0", Blink, Android, "13", "arm", "", "Pixel 7", M),
+            ("Mozilla/5.0 (Linux; Android 12; SM-X700 Build/SP1A.210812.016) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/114.0.5735.196 Safari/537.36",
+                Chrome, "114.0.5735.196", Blink, Android, "12", "arm", "", "SM-X700", T),
+            ("Mozilla/5.0 (Linux; Android 14; SM-S918B; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/124.0.6367.82 Mobile Safari/537.36",
+                Chrome, "124.0.6367.82", Blink, Android, "14", "arm", "", "SM-S918B", M),
+            ("Mozilla/5.0 (Linux; U; Android 4.0.3; ko-kr; LG-L160L Build/IML74K) AppleWebKit/534.30 (KHTML, like Gecko) Version/4.0 Mobile Safari/534.30",
+                Safari, "4.0", WebKit, Android, "4.0.3", "arm", "", "LG-L160L", M),
+            ("Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/124.0.6367.88 Mobile/15E148 Safari/604.1",
+                Chrome, "124.0.6367.88", WebKit, IPhone, "17.4", "arm", "64", "", M),
+            ("Mozilla/5.0 (iPhone; CPU iPhone OS 17_5 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Mobile/15E148 Safari/604.1",
+                Safari, "17.5", WebKit, IPhone, "17.5", "arm", "64", "", M),
+            ("Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1",
+                Safari, "16.6", WebKit, IPad, "16.6", "arm", "64", "", T),
+            ("Mozilla/5.0 (iPhone; CPU iPhone OS 17_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) FxiOS/125.0 Mobile/15E148 Safari/605.1.15",
+                Firefox, "125.0", WebKit, IPhone, "17.4", "arm", "64", "", M),
+            ("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.5 Safari/605.1.15",
+                Safari, "17.5", WebKit, Mac, "10.15.7", "x86", "64", "", D),
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64; rv:128.0) Gecko/20100101 Firefox/128.0",
+                Firefox, "128.0", Gecko, Windows, "10.0", "x86", "64", "", D),
+            ("Mozilla/5.0 (Macintosh; Intel Mac OS X 10.15; rv:127.0) Gecko/20100101 Firefox/127.0",
+                Firefox, "127.0", Gecko, Mac, "10.15", "x86", "64", "", D),
+            ("Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:126.0) Gecko/20100101 Firefox/126.0",
+                Firefox, "126.0", Gecko, Linux, "", "x86", "64", "", D),
+            ("Mozilla/5.0 (X11; Linux i686; rv:109.0) Gecko/20100101 Firefox/115.0",
+                Firefox, "115.0", Gecko, Linux, "", "x86", "32", "", D),
+            ("Mozilla/5.0 (Android 14; Mobile; rv:128.0) Gecko/128.0 Firefox/128.0",
+                Firefox, "128.0", Gecko, Android, "14", "arm", "", "", M),
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Safari/537.36 Edg/139.0.3405.86",
+                Edge, "139.0.3405.86", Blink, Windows, "10.0", "x86", "64", "", D),
+            ("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36 Edg/138.0.0.0",
+                Edge, "138.0.0.0", Blink, Mac, "10.15.7", "x86", "64", "", D),
+            ("Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Mobile Safari/537.36 EdgA/138.0.0.0",
+                Edge, "138.0.0.0", Blink, Android, "10", "arm", "", "K", M),
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36 OPR/122.0.0.0",
+                Opera, "122.0.0.0", Blink, Windows, "10.0", "x86", "64", "", D),
+            ("Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/137.0.0.0 Mobile Safari/537.36 OPR/90.0.0.0",
+                Opera, "90.0.0.0", Blink, Android, "10", "arm", "", "K", M),
+            ("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Safari/537.36 Brave/139",
+                Brave, "139.0.0.0", Blink, Windows, "10.0", "x86", "64", "", D),
+            ("Mozilla/5.0 (Windows NT 10.0; ARM64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/139.0.0.0 Safari/537.36",
+                Chrome, "139.0.0.0", Blink, Windows, "10.0", "arm", "64", "", D),
+            ("curl/8.5.0",
+                Other, "", BrowserEngine::Unknown, AgentOs::Unknown, "", "", "", "", D),
+        ];
+
+        for (ua, browser, version, engine, os, os_version, arch, bitness, model, form) in corpus {
+            let info = parse_user_agent(ua);
+
+            assert_eq!(info.browser, *browser, "{ua}");
+            assert_eq!(info.version, *version, "{ua}");
+            assert_eq!(info.engine, *engine, "{ua}");
+            assert_eq!(info.os, *os, "{ua}");
+            assert_eq!(info.os_version, *os_version, "{ua}");
+            assert_eq!(info.architecture, *arch, "{ua}");
+            assert_eq!(info.bitness, *bitness, "{ua}");
+            assert_eq!(info.model, *model, "{ua}");
+            assert_eq!(info.form_factor, *form, "{ua}");
+            assert_eq!(
+                info.major,
+                version.split('.').next().and_then(|m| m.parse().ok()),
+                "{ua}"
+            );
+            assert_eq!(
+                info.allows_high_entropy(),
+                crate::ua_allows_gethighentropy(ua),
+                "{ua}"
+            );
+        }
+    }
+}
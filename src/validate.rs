@@ -0,0 +1,452 @@
+use crate::configs::{AgentOs, Tier};
+use crate::profiles::gpu::{gpu_profiles_for_os, select_random_gpu_profile_rng, GpuProfile};
+use crate::spoof_viewport::{randomize_viewport_rng, DeviceType, Viewport};
+use crate::{is_ipad_desktop_mode, parse_user_agent, UserAgentInfo};
+use crate::{EmulationConfiguration, Fingerprint};
+use rand::{rng, Rng};
+
+/// The severity of a validation issue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// The output is unusual but can exist in the wild.
+    Warning,
+    /// The output contradicts itself and is trivially detectable.
+    Error,
+}
+
+/// A mismatch between the layers of an emulation.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Issue {
+    /// The configured agent os does not match the os of the user agent.
+    AgentOsMismatch {
+        /// The os of the user agent.
+        user_agent: AgentOs,
+        /// The configured os.
+        configured: AgentOs,
+    },
+    /// The firefox flag does not match the user agent.
+    FirefoxFlagMismatch {
+        /// The configured flag.
+        firefox_agent: bool,
+    },
+    /// The gpu profile ships with another operating system.
+    GpuProfileMismatch {
+        /// The os being emulated.
+        agent_os: AgentOs,
+        /// The os the gpu profile belongs to.
+        profile_os: AgentOs,
+    },
+    /// A mobile viewport is used with a desktop user agent.
+    MobileViewportDesktopAgent,
+    /// A desktop viewport is used with a mobile user agent.
+    DesktopViewportMobileAgent,
+    /// Touch emulation is enabled on a desktop os.
+    TouchScreenOnDesktop,
+    /// The native gpu fingerprint is used with a tier that spoofs the gpu.
+    NativeGpuWithGpuSpoof {
+        /// The configured tier.
+        tier: Tier,
+    },
+    #[cfg(feature = "headers")]
+    /// The near mimic header level is used with a chromium user agent.
+    NearMimicHeadersWithChromium,
+}
+
+impl Issue {
+    /// The severity of the issue.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::TouchScreenOnDesktop => Severity::Warning,
+            #[cfg(feature = "headers")]
+            Issue::NearMimicHeadersWithChromium => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::AgentOsMismatch {
+                user_agent,
+                configured,
+            } => write!(
+                f,
+                "the agent os {configured:?} does not match the user agent os {user_agent:?}"
+            ),
+            Issue::FirefoxFlagMismatch { firefox_agent } => write!(
+                f,
+                "firefox_agent is {firefox_agent} but the user agent says otherwise"
+            ),
+            Issue::GpuProfileMismatch {
+                agent_os,
+                profile_os,
+            } => write!(
+                f,
+                "the gpu profile belongs to {profile_os:?} while emulating {agent_os:?}"
+            ),
+            Issue::MobileViewportDesktopAgent => {
+                write!(f, "a mobile viewport is used with a desktop user agent")
+            }
+            Issue::DesktopViewportMobileAgent => {
+                write!(f, "a desktop viewport is used with a mobile user agent")
+            }
+            Issue::TouchScreenOnDesktop => write!(f, "touch emulation is enabled on a desktop os"),
+            Issue::NativeGpuWithGpuSpoof { tier } => write!(
+                f,
+                "the native gpu fingerprint is used with the gpu spoofing tier {tier:?}"
+            ),
+            #[cfg(feature = "headers")]
+            Issue::NearMimicHeadersWithChromium => write!(
+                f,
+                "the near mimic header level is not ideal for chromium user agents"
+            ),
+        }
+    }
+}
+
+/// The issues found while validating an emulation.
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationReport {
+    /// The issues found.
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    /// No issues were found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// No errors were found. Warnings are allowed.
+    pub fn is_valid(&self) -> bool {
+        !self
+            .issues
+            .iter()
+            .any(|issue| issue.severity() == Severity::Error)
+    }
+
+    /// The issues with the error severity.
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == Severity::Error)
+    }
+
+    /// The issues with the warning severity.
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == Severity::Warning)
+    }
+}
+
+/// Everything that goes into an emulation, checked together for consistency.
+///
+/// The user agent is the source of truth: auto-correcting rewrites the other layers to
+/// agree with it.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationTarget {
+    /// The user agent.
+    pub user_agent: String,
+    /// The emulation configuration.
+    pub config: EmulationConfiguration,
+    /// The gpu profile passed to the emulation.
+    pub gpu_profile: Option<GpuProfile>,
+    /// The viewport.
+    pub viewport: Option<Viewport>,
+    #[cfg(feature = "headers")]
+    /// The header detail level.
+    pub header_detail_level: Option<crate::spoof_headers::HeaderDetailLevel>,
+}
+
+impl ValidationTarget {
+    /// Create a new validation target.
+    pub fn new(user_agent: &str, config: &EmulationConfiguration) -> Self {
+        Self {
+            user_agent: user_agent.into(),
+            config: *config,
+            gpu_profile: None,
+            viewport: None,
+            #[cfg(feature = "headers")]
+            header_detail_level: None,
+        }
+    }
+
+    /// Set the gpu profile.
+    pub fn with_gpu_profile(mut self, gpu_profile: &GpuProfile) -> Self {
+        self.gpu_profile = Some(gpu_profile.clone());
+        self
+    }
+
+    /// Set the viewport.
+    pub fn with_viewport(mut self, viewport: &Viewport) -> Self {
+        self.viewport = Some(*viewport);
+        self
+    }
+
+    #[cfg(feature = "headers")]
+    /// Set the header detail level.
+    pub fn with_header_detail_level(
+        mut self,
+        header_detail_level: crate::spoof_headers::HeaderDetailLevel,
+    ) -> Self {
+        self.header_detail_level = Some(header_detail_level);
+        self
+    }
+
+    /// The os of the parsed user agent, falling back to the configured os.
+    fn agent_os(&self, info: &UserAgentInfo) -> AgentOs {
+        if is_ipad_desktop_mode(&self.user_agent, self.config.agent_os) {
+            return AgentOs::IPad;
+        }
+
+        match info.os {
+            AgentOs::Unknown => self.config.agent_os,
+            os => os,
+        }
+    }
+
+    /// Validate every layer against the user agent.
+    pub fn validate(&self) -> ValidationReport {
+        self.validate_info(&parse_user_agent(&self.user_agent))
+    }
+
+    /// Validate every layer against the parsed user agent.
+    fn validate_info(&self, info: &UserAgentInfo) -> ValidationReport {
+        let mut issues = Vec::new();
+        let user_agent = self.user_agent.as_str();
+        let ua_os = info.os;
+        let agent_os = self.agent_os(info);
+        let mobile = info.is_mobile();
+
+        if ua_os != AgentOs::Unknown
+            && self.config.agent_os != AgentOs::Unknown
+            && ua_os != self.config.agent_os
+            && !is_ipad_desktop_mode(user_agent, self.config.agent_os)
+        {
+            issues.push(Issue::AgentOsMismatch {
+                user_agent: ua_os,
+                configured: self.config.agent_os,
+            });
+        }
+
+        if self.config.firefox_agent != user_agent.contains("Firefox") {
+            issues.push(Issue::FirefoxFlagMismatch {
+                firefox_agent: self.config.firefox_agent,
+            });
+        }
+
+        if let Some(gpu_profile) = &self.gpu_profile {
+            if let Some(profile_os) = gpu_profile_os(gpu_profile, agent_os) {
+                issues.push(Issue::GpuProfileMismatch {
+                    agent_os,
+                    profile_os,
+                });
+            }
+        }
+
+        if let Some(viewport) = &self.viewport {
+            if viewport.emulating_mobile && !mobile {
+                issues.push(Issue::MobileViewportDesktopAgent);
+            } else if !viewport.emulating_mobile && mobile {
+                issues.push(Issue::DesktopViewportMobileAgent);
+            }
+        }
+
+        let desktop_os = matches!(agent_os, AgentOs::Mac | AgentOs::Windows | AgentOs::Linux);
+
+        if desktop_os
+            && !mobile
+            && (self.config.touch_screen || self.viewport.is_some_and(|v| v.has_touch))
+        {
+            issues.push(Issue::TouchScreenOnDesktop);
+        }
+
+        if self.config.fingerprint == Fingerprint::NativeGPU && self.config.tier == Tier::Full {
+            issues.push(Issue::NativeGpuWithGpuSpoof {
+                tier: self.config.tier,
+            });
+        }
+
+        #[cfg(feature = "headers")]
+        if self.header_detail_level == Some(crate::spoof_headers::HeaderDetailLevel::NearMimic)
+            && info.browser.is_chromium()
+        {
+            issues.push(Issue::NearMimicHeadersWithChromium);
+        }
+
+        ValidationReport { issues }
+    }
+
+    /// Rewrite the layers to agree with the user agent. Returns the issues that were corrected.
+    pub fn auto_correct(&mut self) -> ValidationReport {
+        self.auto_correct_rng(&mut rng())
+    }
+
+    /// Rewrite the layers to agree with the user agent drawing replacement values from the
+    /// provided rng. Returns the issues that were corrected.
+    pub fn auto_correct_rng<R: Rng>(&mut self, rng: &mut R) -> ValidationReport {
+        let info = parse_user_agent(&self.user_agent);
+        let report = self.validate_info(&info);
+        let agent_os = self.agent_os(&info);
+        let mobile = info.is_mobile();
+
+        for issue in &report.issues {
+            match issue {
+                Issue::AgentOsMismatch { user_agent, .. } => {
+                    self.config.agent_os = *user_agent;
+                }
+                Issue::FirefoxFlagMismatch { firefox_agent } => {
+                    self.config.firefox_agent = !firefox_agent;
+                }
+                Issue::GpuProfileMismatch { .. } => {
+                    self.gpu_profile = Some(select_random_gpu_profile_rng(agent_os, rng).clone());
+                }
+                Issue::MobileViewportDesktopAgent | Issue::DesktopViewportMobileAgent => {
+                    if let Some(viewport) = self.viewport.as_mut() {
+                        let device = if !mobile {
+                            DeviceType::Desktop
+                        } else if agent_os == AgentOs::IPad {
+                            DeviceType::Tablet
+                        } else {
+                            DeviceType::Mobile
+                        };
+                        let resized = randomize_viewport_rng(&device, rng);
+
+                        viewport.width = resized.width;
+                        viewport.height = resized.height;
+                        viewport.emulating_mobile = mobile;
+                        viewport.has_touch = mobile;
+                    }
+                }
+                Issue::TouchScreenOnDesktop => {
+                    self.config.touch_screen = false;
+                    if let Some(viewport) = self.viewport.as_mut() {
+                        viewport.has_touch = false;
+                    }
+                }
+                Issue::NativeGpuWithGpuSpoof { .. } => {
+                    self.config.tier = Tier::Mid;
+                }
+                #[cfg(feature = "headers")]
+                Issue::NearMimicHeadersWithChromium => {
+                    self.header_detail_level =
+                        Some(crate::spoof_headers::HeaderDetailLevel::Extensive);
+                }
+            }
+        }
+
+        report
+    }
+}
+
+/// The os a gpu profile ships with when it does not belong to the emulated os.
+fn gpu_profile_os(gpu_profile: &GpuProfile, agent_os: AgentOs) -> Option<AgentOs> {
+    if agent_os == AgentOs::Unknown || gpu_profiles_for_os(agent_os).contains(&gpu_profile) {
+        return None;
+    }
+
+    [
+        AgentOs::Mac,
+        AgentOs::Windows,
+        AgentOs::Linux,
+        AgentOs::Android,
+        AgentOs::IPhone,
+        AgentOs::IPad,
+        AgentOs::ChromeOS,
+    ]
+    .into_iter()
+    .find(|os| gpu_profiles_for_os(*os).contains(&gpu_profile))
+}
+
+/// Validate a user agent and configuration with the optional gpu profile and viewport.
+pub fn validate(
+    user_agent: &str,
+    config: &EmulationConfiguration,
+    gpu_profile: Option<&GpuProfile>,
+    viewport: &Option<&Viewport>,
+) -> ValidationReport {
+    let mut target = ValidationTarget::new(user_agent, config);
+    target.gpu_profile = gpu_profile.cloned();
+    target.viewport = viewport.copied();
+    target.validate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profiles::gpu::{GPU_PROFILES_MAC, GPU_PROFILES_WINDOWS};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const MAC_UA: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/138.0.0.0 Safari/537.36";
+
+    #[test]
+    fn defaults_are_valid() {
+        let config = EmulationConfiguration::setup_defaults(MAC_UA);
+        let report = validate(
+            MAC_UA,
+            &config,
+            Some(&GPU_PROFILES_MAC[0]),
+            &Some(&Viewport::new(1440, 900)),
+        );
+
+        assert!(report.is_empty(), "{:?}", report);
+    }
+
+    #[test]
+    fn detects_cross_layer_mismatches() {
+        let mut config = EmulationConfiguration::setup_defaults(MAC_UA);
+        config.touch_screen = true;
+        config.fingerprint = Fingerprint::NativeGPU;
+        config.tier = Tier::Full;
+        let mut viewport = Viewport::new(390, 844);
+        viewport.set_mobile(true);
+
+        let report = validate(
+            MAC_UA,
+            &config,
+            Some(&GPU_PROFILES_WINDOWS[0]),
+            &Some(&viewport),
+        );
+
+        assert!(!report.is_valid());
+        assert!(report.issues.contains(&Issue::GpuProfileMismatch {
+            agent_os: AgentOs::Mac,
+            profile_os: AgentOs::Windows,
+        }));
+        assert!(report.issues.contains(&Issue::MobileViewportDesktopAgent));
+        assert!(report
+            .issues
+            .contains(&Issue::NativeGpuWithGpuSpoof { tier: Tier::Full }));
+        assert_eq!(
+            report.warnings().collect::<Vec<_>>(),
+            vec![&Issue::TouchScreenOnDesktop]
+        );
+    }
+
+    #[test]
+    fn auto_correct_resolves_issues() {
+        let mut config = EmulationConfiguration::setup_defaults(MAC_UA);
+        config.agent_os = AgentOs::Windows;
+        config.touch_screen = true;
+        let mut viewport = Viewport::new(390, 844);
+        viewport.set_mobile(true);
+
+        let mut target = ValidationTarget::new(MAC_UA, &config)
+            .with_gpu_profile(&GPU_PROFILES_WINDOWS[0])
+            .with_viewport(&viewport);
+
+        let corrected = target.auto_correct_rng(&mut StdRng::seed_from_u64(1));
+
+        assert!(!corrected.is_empty());
+        assert!(target.validate().is_empty(), "{:?}", target.validate());
+        assert_eq!(target.config.agent_os, AgentOs::Mac);
+        assert!(target.viewport.is_some_and(|v| !v.emulating_mobile));
+    }
+}
@@ -17,11 +17,16 @@ lazy_static::lazy_static! {
         .and_then(|arr| arr.first().copied())
         .unwrap_or(&CHROME_STATIC_VERSION);
     /// The latest Chrome not a brand version, configurable via the `CHROME_NOT_A_BRAND_VERSION` env variable.
-    pub static ref CHROME_NOT_A_BRAND_VERSION: String = std::env::var("CHROME_NOT_A_BRAND_VERSION")
-        .ok()
-        .and_then(|v| if v.is_empty() { None } else { Some(v) })
+    pub static ref CHROME_NOT_A_BRAND_VERSION: String = CHROME_NOT_A_BRAND_VERSION_OVERRIDE
+        .clone()
         .unwrap_or("8.0.0.0".into());
 
+    /// The not a brand version pinned with the `CHROME_NOT_A_BRAND_VERSION` env variable.
+    /// The GREASE version is derived from the chrome major when unset.
+    pub(crate) static ref CHROME_NOT_A_BRAND_VERSION_OVERRIDE: Option<String> = std::env::var("CHROME_NOT_A_BRAND_VERSION")
+        .ok()
+        .and_then(|v| if v.is_empty() { None } else { Some(v) });
+
     /// Force the chrome version, configurable via the `CHROME_VERSION_FULL` env variable.
     pub static ref CHROME_VERSION_FULL: String = std::env::var("CHROME_VERSION_FULL")
         .ok()